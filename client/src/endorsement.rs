@@ -0,0 +1,60 @@
+//! Threshold checks of the endorsements of Metadata items against a trust list
+
+use anchor_lang::prelude::Pubkey;
+use mythic_metadata::state::*;
+
+use crate::errors::*;
+use crate::pda::*;
+use crate::registry::*;
+
+/// Returns the members of the trust list whose endorsement covers the current version of the item
+pub fn item_endorsers(
+    reader: &impl AccountReader,
+    seeds: &MetadataSeeds,
+    metadata: &Metadata,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    trust_list: &[Pubkey],
+) -> Result<Vec<Pubkey>> {
+    let item = metadata.item(collection_path, item_metadata_key_id)?;
+    let metadata = seeds.address();
+
+    let mut endorsers = vec![];
+    for endorser in trust_list {
+        let (endorsement, _) =
+            find_endorsement_address(&metadata, collection_path, item_metadata_key_id, endorser);
+        if read_account::<Endorsement>(reader, &endorsement)?
+            .is_some_and(|endorsement| endorsement.is_valid_for(item))
+        {
+            endorsers.push(*endorser);
+        }
+    }
+
+    Ok(endorsers)
+}
+
+/// Whether at least threshold members of the trust list endorsed the current version of the item
+pub fn is_endorsed(
+    reader: &impl AccountReader,
+    seeds: &MetadataSeeds,
+    metadata: &Metadata,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    trust_list: &[Pubkey],
+    threshold: usize,
+) -> Result<bool> {
+    let mut trust_list = trust_list.to_vec();
+    trust_list.sort();
+    trust_list.dedup();
+
+    Ok(item_endorsers(
+        reader,
+        seeds,
+        metadata,
+        collection_path,
+        item_metadata_key_id,
+        &trust_list,
+    )?
+    .len()
+        >= threshold)
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::Pubkey;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MythicMetadataClientError {
+    #[error("MetadataKey {0} not found")]
+    MetadataKeyNotFound(u64),
+    #[error("MetadataKey named {0} not found")]
+    MetadataKeyNameNotFound(String),
+    #[error("MetadataKey name {0} is used by more than one MetadataKey")]
+    AmbiguousMetadataKeyName(String),
+    #[error("Invalid {content_type} value: {reason}")]
+    InvalidValue {
+        content_type: String,
+        reason: String,
+    },
+    #[error("Invalid pubkey {0}")]
+    InvalidPubkey(String),
+    #[error("Desired Metadata {desired} does not match the on-chain Metadata {current}")]
+    MetadataMismatch { desired: Pubkey, current: Pubkey },
+    #[error("Transaction size {0} exceeds the maximum transaction size")]
+    TransactionTooLarge(usize),
+    #[error("Metadata immutable")]
+    ImmutableMetadata,
+    #[error("Failed to fetch {uri}: {reason}")]
+    ContentFetchFailed { uri: String, reason: String },
+    #[error("Content of {0} does not match the external reference")]
+    ContentMismatch(String),
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error(transparent)]
+    Program(#[from] anchor_lang::error::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, MythicMetadataClientError>;
@@ -0,0 +1,1535 @@
+//! Instruction builders for the Metadata program
+//!
+//! The builders sign append, remove and authority instructions with the issuing authority and
+//! don't pass a delegate record. Use [with_delegate] to sign them with a delegate instead
+//!
+//! Collections are identified by their paths, the MetadataKey Ids from the top level collection
+//! down to the collection, e.g. `&[socials, twitter]`. The root items are identified by
+//! `&[seeds.metadata_key_id]`
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{ed25519_program, sysvar};
+use anchor_lang::{system_program, InstructionData};
+use mythic_metadata::constants::TOKEN_METADATA_KEY_ID;
+use mythic_metadata::governance::SPL_GOVERNANCE_PROGRAM_ID;
+use mythic_metadata::signature::Ed25519SignatureOffsets;
+use mythic_metadata::state::{
+    Attestation, Endorsement, EvmAddressLink, MetadataChange, SignedItemUpdate, ATTESTATION_DOMAIN,
+    ITEM_UPDATE_DOMAIN,
+};
+use mythic_metadata::token_metadata::*;
+use mythic_metadata::{accounts, instruction};
+
+use crate::pda::*;
+
+/// Returns the collection MetadataKey address and the parent path of the collection
+fn split_collection_path(collection_path: &[u64]) -> (Pubkey, Vec<u64>) {
+    let (collection_metadata_key_id, parent_path) = collection_path
+        .split_last()
+        .expect("collection path can't be empty");
+
+    (
+        find_metadata_key_address(*collection_metadata_key_id).0,
+        parent_path.to_vec(),
+    )
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: mythic_metadata::id(),
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// The namespace_registry_page_index is the NamespaceRegistry's next_page_index, 0 for a new namespace,
+/// the MetadataKey isn't listed in the namespace registry without it
+pub fn create_metadata_key(
+    payer: &Pubkey,
+    namespace_authority: &Pubkey,
+    namespace_registry_page_index: Option<u32>,
+    args: mythic_metadata::CreateMetadataKeyArgs,
+) -> Instruction {
+    build(
+        accounts::CreateMetadataKey {
+            payer: *payer,
+            namespace_authority: *namespace_authority,
+            metadata_key: find_metadata_key_address(args.id).0,
+            system_program: system_program::ID,
+            namespace_registry: namespace_registry_page_index
+                .map(|_| find_namespace_registry_address(namespace_authority).0),
+            namespace_registry_page: namespace_registry_page_index.map(|page_index| {
+                find_namespace_registry_page_address(namespace_authority, page_index).0
+            }),
+        },
+        instruction::CreateMetadataKey { args },
+    )
+}
+
+/// The subject_registry_page_index is the SubjectRegistry's next_page_index, 0 for a new subject,
+/// see [crate::next_subject_registry_page_index]. Without it the Metadata isn't listed in the
+/// subject registry, it can be listed later with [register_metadata]
+pub fn create_metadata(
+    payer: &Pubkey,
+    seeds: &MetadataSeeds,
+    subject_registry_page_index: Option<u32>,
+    update_authority: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::CreateMetadata {
+            payer: *payer,
+            issuing_authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            system_program: system_program::ID,
+            subject_authority: None,
+            subject_account: None,
+            subject_registry: subject_registry_page_index
+                .map(|_| find_subject_registry_address(&seeds.subject).0),
+            subject_registry_page: subject_registry_page_index
+                .map(|page_index| find_subject_registry_page_address(&seeds.subject, page_index).0),
+        },
+        instruction::CreateMetadata {
+            args: mythic_metadata::CreateMetadataArgs {
+                subject: seeds.subject,
+                update_authority,
+            },
+        },
+    )
+}
+
+/// Creates a self-issued Metadata with a verified subject
+///
+/// The subject_authority is the subject itself or, with the subject_account, the mint authority
+/// of the subject mint or the upgrade authority of the subject program, whose ProgramData
+/// address is the subject_account
+///
+/// The subject_registry_page_index is the SubjectRegistry's next_page_index
+pub fn create_verified_metadata(
+    payer: &Pubkey,
+    seeds: &MetadataSeeds,
+    subject_registry_page_index: Option<u32>,
+    update_authority: Option<Pubkey>,
+    subject_authority: &Pubkey,
+    subject_account: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::CreateMetadata {
+            payer: *payer,
+            issuing_authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            system_program: system_program::ID,
+            subject_authority: Some(*subject_authority),
+            subject_registry: subject_registry_page_index
+                .map(|_| find_subject_registry_address(&seeds.subject).0),
+            subject_registry_page: subject_registry_page_index
+                .map(|page_index| find_subject_registry_page_address(&seeds.subject, page_index).0),
+            subject_account,
+        },
+        instruction::CreateMetadata {
+            args: mythic_metadata::CreateMetadataArgs {
+                subject: seeds.subject,
+                update_authority,
+            },
+        },
+    )
+}
+
+/// Creates the canonical Metadata of a DAO, see [MetadataSeeds::governance]
+///
+/// The instruction must be executed by a governance proposal. Instructions built for the returned
+/// Metadata are signed by the realm and must be passed to [with_delegate] with the governance
+/// authority and the Metadata's own MetadataKey Id as the path, except update_metadata_item which the
+/// governance authority signs as the update authority
+/// Only realms of the SPL Governance program are accepted
+///
+/// The subject_registry_page_index is the realm's SubjectRegistry next_page_index
+pub fn create_governance_metadata(
+    payer: &Pubkey,
+    metadata_key_id: u64,
+    realm: &Pubkey,
+    subject_registry_page_index: Option<u32>,
+    governance: &Pubkey,
+    governance_authority: &Pubkey,
+) -> Instruction {
+    let seeds = MetadataSeeds::governance(metadata_key_id, realm);
+    let metadata = seeds.address();
+
+    build(
+        accounts::CreateGovernanceMetadata {
+            payer: *payer,
+            governance_authority: *governance_authority,
+            governance: *governance,
+            realm: *realm,
+            governance_program: SPL_GOVERNANCE_PROGRAM_ID,
+            metadata,
+            metadata_metadata_key: seeds.metadata_key_address(),
+            delegate_record: find_metadata_delegate_address(
+                &metadata,
+                &[metadata_key_id],
+                governance_authority,
+            )
+            .0,
+            system_program: system_program::ID,
+            subject_registry: subject_registry_page_index
+                .map(|_| find_subject_registry_address(realm).0),
+            subject_registry_page: subject_registry_page_index
+                .map(|page_index| find_subject_registry_page_address(realm, page_index).0),
+        },
+        instruction::CreateGovernanceMetadata {},
+    )
+}
+
+/// Creates the Metadata identified by the seeds with copies of the source Metadata's items
+/// collection_paths selects the source collections to copy, all of them if it's empty
+/// The subject_registry_page_index is the SubjectRegistry's next_page_index
+pub fn clone_metadata(
+    payer: &Pubkey,
+    source_metadata: &Pubkey,
+    seeds: &MetadataSeeds,
+    subject_registry_page_index: Option<u32>,
+    update_authority: Option<Pubkey>,
+    collection_paths: Vec<Vec<u64>>,
+) -> Instruction {
+    build(
+        accounts::CloneMetadata {
+            payer: *payer,
+            issuing_authority: seeds.issuing_authority,
+            source_metadata: *source_metadata,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            system_program: system_program::ID,
+            subject_registry: subject_registry_page_index
+                .map(|_| find_subject_registry_address(&seeds.subject).0),
+            subject_registry_page: subject_registry_page_index
+                .map(|page_index| find_subject_registry_page_address(&seeds.subject, page_index).0),
+        },
+        instruction::CloneMetadata {
+            args: mythic_metadata::CloneMetadataArgs {
+                subject: seeds.subject,
+                update_authority,
+                collection_paths,
+            },
+        },
+    )
+}
+
+pub fn append_metadata_collection(
+    payer: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    update_authority: Option<Pubkey>,
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::AppendMetadataCollection {
+            payer: *payer,
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            system_program: system_program::ID,
+            delegate_record: None,
+        },
+        instruction::AppendMetadataCollection {
+            args: mythic_metadata::AppendMetadataCollectionArgs {
+                update_authority,
+                parent_path,
+            },
+        },
+    )
+}
+
+/// Removes the collection with its nested collections, their rent is refunded to the rent_payers
+/// See [crate::collection_rent_payers]
+pub fn remove_metadata_collection(
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    rent_payers: &[Pubkey],
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    let instruction = build(
+        accounts::RemoveMetadataCollection {
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            delegate_record: None,
+        },
+        instruction::RemoveMetadataCollection {
+            args: mythic_metadata::RemoveMetadataCollectionArgs { parent_path },
+        },
+    );
+    with_rent_payers(instruction, rent_payers)
+}
+
+/// Replaces the update authority immediately, a mistyped authority locks everyone out
+/// Prefer [propose_update_authority_transfer]
+pub fn unsafe_set_collection_update_authority(
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    new_update_authority: Option<Pubkey>,
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::SetCollectionUpdateAuthority {
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            delegate_record: None,
+        },
+        instruction::SetCollectionUpdateAuthority {
+            args: mythic_metadata::SetCollectionUpdateAuthorityArgs {
+                new_update_authority,
+                unsafe_skip_acceptance: true,
+                parent_path,
+            },
+        },
+    )
+}
+
+/// Sets the number of slots updates and removals of the collection items are queued for
+pub fn set_collection_timelock(
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    timelock_slots: u64,
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::SetCollectionTimelock {
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            delegate_record: None,
+        },
+        instruction::SetCollectionTimelock {
+            args: mythic_metadata::SetCollectionTimelockArgs {
+                timelock_slots,
+                parent_path,
+            },
+        },
+    )
+}
+
+/// Queues the change of the item of a timelocked collection
+/// The authority is an update authority for updates and the issuing authority for removals
+/// The payer prepays the rent of the growth of an update, unused lamports are refunded with the rent
+pub fn queue_metadata_change(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    change: MetadataChange,
+) -> Instruction {
+    let metadata = seeds.address();
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::QueueMetadataChange {
+            payer: *payer,
+            authority: *authority,
+            metadata,
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            item_metadata_key: find_metadata_key_address(item_metadata_key_id).0,
+            pending_change: find_pending_change_address(
+                &metadata,
+                collection_path,
+                item_metadata_key_id,
+            )
+            .0,
+            delegate_record: None,
+            multisig_authority: None,
+            system_program: system_program::ID,
+        },
+        instruction::QueueMetadataChange {
+            args: mythic_metadata::QueueMetadataChangeArgs {
+                change,
+                parent_path,
+            },
+        },
+    )
+}
+
+/// Cancels the pending change of the item, the rent is refunded to the payer who queued it
+pub fn cancel_metadata_change(
+    update_authority: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    payer: &Pubkey,
+) -> Instruction {
+    let metadata = seeds.address();
+    build(
+        accounts::CancelMetadataChange {
+            update_authority: *update_authority,
+            metadata,
+            pending_change: find_pending_change_address(
+                &metadata,
+                collection_path,
+                item_metadata_key_id,
+            )
+            .0,
+            payer: *payer,
+            multisig_authority: None,
+        },
+        instruction::CancelMetadataChange {},
+    )
+}
+
+/// Applies the pending change of the item once its timelock elapsed
+/// The rent is refunded to the payer who queued it, the rent of the removed or shrunk item
+/// to the rent_payers. See [crate::item_rent_payers]
+pub fn execute_metadata_change(
+    cranker: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    payer: &Pubkey,
+    rent_payers: &[Pubkey],
+) -> Instruction {
+    let metadata = seeds.address();
+    let instruction = build(
+        accounts::ExecuteMetadataChange {
+            cranker: *cranker,
+            metadata,
+            pending_change: find_pending_change_address(
+                &metadata,
+                collection_path,
+                item_metadata_key_id,
+            )
+            .0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::ExecuteMetadataChange {},
+    );
+    with_rent_payers(instruction, rent_payers)
+}
+
+pub fn revoke_collection_update_authority(
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::RevokeCollectionUpdateAuthority {
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            delegate_record: None,
+        },
+        instruction::RevokeCollectionUpdateAuthority {
+            args: mythic_metadata::RevokeCollectionUpdateAuthorityArgs { parent_path },
+        },
+    )
+}
+
+/// Proposes the new update authority of the collection, the root path for the Metadata
+/// The update authority changes once the new update authority accepts it
+pub fn propose_update_authority_transfer(
+    payer: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    new_update_authority: &Pubkey,
+) -> Instruction {
+    let metadata = seeds.address();
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::ProposeUpdateAuthorityTransfer {
+            payer: *payer,
+            authority: seeds.issuing_authority,
+            metadata,
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            authority_transfer: find_authority_transfer_address(&metadata, collection_path).0,
+            delegate_record: None,
+            system_program: system_program::ID,
+        },
+        instruction::ProposeUpdateAuthorityTransfer {
+            args: mythic_metadata::ProposeUpdateAuthorityTransferArgs {
+                new_update_authority: *new_update_authority,
+                parent_path,
+            },
+        },
+    )
+}
+
+/// Accepts the proposed transfer, signed by the new update authority
+/// A proposed MultisigAuthority accepts with [with_multisig_authority] signed by its signers
+/// The rent is refunded to the payer of the proposal
+pub fn accept_update_authority_transfer(
+    new_update_authority: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    payer: &Pubkey,
+) -> Instruction {
+    let metadata = seeds.address();
+    build(
+        accounts::AcceptUpdateAuthorityTransfer {
+            new_update_authority: *new_update_authority,
+            metadata,
+            authority_transfer: find_authority_transfer_address(&metadata, collection_path).0,
+            payer: *payer,
+            multisig_authority: None,
+        },
+        instruction::AcceptUpdateAuthorityTransfer {},
+    )
+}
+
+/// Cancels the proposed transfer, signed by the issuing authority, the Metadata update authority
+/// or the proposed update authority
+/// The rent is refunded to the payer of the proposal
+pub fn cancel_update_authority_transfer(
+    authority: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    payer: &Pubkey,
+) -> Instruction {
+    let metadata = seeds.address();
+    build(
+        accounts::CancelUpdateAuthorityTransfer {
+            authority: *authority,
+            metadata,
+            authority_transfer: find_authority_transfer_address(&metadata, collection_path).0,
+            payer: *payer,
+            delegate_record: None,
+        },
+        instruction::CancelUpdateAuthorityTransfer {},
+    )
+}
+
+/// Freezes the collection and its nested collections permanently
+/// The authority is the issuing authority or the Metadata update authority
+pub fn freeze_metadata_collection(
+    authority: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::FreezeMetadataCollection {
+            authority: *authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            multisig_authority: None,
+        },
+        instruction::FreezeMetadataCollection {
+            args: mythic_metadata::FreezeMetadataCollectionArgs { parent_path },
+        },
+    )
+}
+
+/// Finalizes the Metadata making it permanently immutable
+/// The authority is the issuing authority or the Metadata update authority
+/// The state_hash is the Metadata::state_hash of the reviewed on-chain Metadata,
+/// the finalization fails if the Metadata changed since
+pub fn finalize_metadata(
+    authority: &Pubkey,
+    seeds: &MetadataSeeds,
+    state_hash: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::FinalizeMetadata {
+            authority: *authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            multisig_authority: None,
+        },
+        instruction::FinalizeMetadata {
+            args: mythic_metadata::FinalizeMetadataArgs { state_hash },
+        },
+    )
+}
+
+pub fn append_metadata_item(
+    payer: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    value: Vec<u8>,
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::AppendMetadataItem {
+            payer: *payer,
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            item_metadata_key: find_metadata_key_address(item_metadata_key_id).0,
+            system_program: system_program::ID,
+            delegate_record: None,
+        },
+        instruction::AppendMetadataItem {
+            args: mythic_metadata::AppendMetadataItemArgs { value, parent_path },
+        },
+    )
+}
+
+/// Appends several items to a single collection
+/// The item MetadataKeys are passed as remaining accounts in the same order as the values
+pub fn append_metadata_items(
+    payer: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    items: Vec<(u64, Vec<u8>)>,
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    let (item_metadata_key_ids, value): (Vec<u64>, Vec<Vec<u8>>) = items.into_iter().unzip();
+
+    let mut instruction = build(
+        accounts::AppendMetadataItems {
+            payer: *payer,
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            system_program: system_program::ID,
+            delegate_record: None,
+        },
+        instruction::AppendMetadataItems {
+            args: mythic_metadata::AppendMetadataItemsArgs { value, parent_path },
+        },
+    );
+    instruction.accounts.extend(
+        item_metadata_key_ids
+            .into_iter()
+            .map(|id| AccountMeta::new_readonly(find_metadata_key_address(id).0, false)),
+    );
+    instruction
+}
+
+pub fn update_metadata_item(
+    update_authority: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    new_value: Vec<u8>,
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::UpdateMetadataItem {
+            update_authority: *update_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            item_metadata_key: find_metadata_key_address(item_metadata_key_id).0,
+            system_program: system_program::ID,
+            delegate_record: None,
+            multisig_authority: None,
+        },
+        instruction::UpdateMetadataItem {
+            args: mythic_metadata::UpdateMetadataItemArgs {
+                new_value,
+                parent_path,
+            },
+        },
+    )
+}
+
+/// Removes the item, its rent is refunded to the rent_payers of the item
+/// See [crate::item_rent_payers]
+pub fn remove_metadata_item(
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    rent_payers: &[Pubkey],
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    let instruction = build(
+        accounts::RemoveMetadataItem {
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            item_metadata_key: find_metadata_key_address(item_metadata_key_id).0,
+            delegate_record: None,
+        },
+        instruction::RemoveMetadataItem {
+            args: mythic_metadata::RemoveMetadataItemArgs { parent_path },
+        },
+    );
+    with_rent_payers(instruction, rent_payers)
+}
+
+/// Appends the value to the list of a multi-valued item, the item is appended if it doesn't exist
+pub fn push_metadata_list_value(
+    payer: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    value: Vec<u8>,
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::PushMetadataListValue {
+            payer: *payer,
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            item_metadata_key: find_metadata_key_address(item_metadata_key_id).0,
+            system_program: system_program::ID,
+            delegate_record: None,
+        },
+        instruction::PushMetadataListValue {
+            args: mythic_metadata::PushMetadataListValueArgs { value, parent_path },
+        },
+    )
+}
+
+pub fn replace_metadata_list_value(
+    update_authority: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    index: u16,
+    new_value: Vec<u8>,
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::ReplaceMetadataListValue {
+            update_authority: *update_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            item_metadata_key: find_metadata_key_address(item_metadata_key_id).0,
+            system_program: system_program::ID,
+            delegate_record: None,
+            multisig_authority: None,
+        },
+        instruction::ReplaceMetadataListValue {
+            args: mythic_metadata::ReplaceMetadataListValueArgs {
+                index,
+                new_value,
+                parent_path,
+            },
+        },
+    )
+}
+
+/// Removes the value, the rent the list shrinks by is refunded to the rent_payers of the item
+/// See [crate::item_rent_payers]
+pub fn remove_metadata_list_value(
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    index: u16,
+    rent_payers: &[Pubkey],
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    let instruction = build(
+        accounts::RemoveMetadataListValue {
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            item_metadata_key: find_metadata_key_address(item_metadata_key_id).0,
+            delegate_record: None,
+        },
+        instruction::RemoveMetadataListValue {
+            args: mythic_metadata::RemoveMetadataListValueArgs { index, parent_path },
+        },
+    );
+    with_rent_payers(instruction, rent_payers)
+}
+
+/// Clears the list, the rent the list shrinks by is refunded to the rent_payers of the item
+/// See [crate::item_rent_payers]
+pub fn clear_metadata_list(
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    rent_payers: &[Pubkey],
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    let instruction = build(
+        accounts::ClearMetadataList {
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            item_metadata_key: find_metadata_key_address(item_metadata_key_id).0,
+            delegate_record: None,
+        },
+        instruction::ClearMetadataList {
+            args: mythic_metadata::ClearMetadataListArgs { parent_path },
+        },
+    );
+    with_rent_payers(instruction, rent_payers)
+}
+
+/// Moves the item between collections, keeping its value and update slot
+pub fn move_metadata_item(
+    seeds: &MetadataSeeds,
+    source_collection_path: &[u64],
+    destination_collection_path: &[u64],
+    item_metadata_key_id: u64,
+) -> Instruction {
+    let (source_collection_metadata_key, source_parent_path) =
+        split_collection_path(source_collection_path);
+    let (destination_collection_metadata_key, destination_parent_path) =
+        split_collection_path(destination_collection_path);
+    build(
+        accounts::MoveMetadataItem {
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            source_collection_metadata_key,
+            destination_collection_metadata_key,
+            item_metadata_key: find_metadata_key_address(item_metadata_key_id).0,
+            delegate_record: None,
+        },
+        instruction::MoveMetadataItem {
+            args: mythic_metadata::MoveMetadataItemArgs {
+                source_parent_path,
+                destination_parent_path,
+            },
+        },
+    )
+}
+
+/// Changes the MetadataKey of the collection, its nested collections follow it
+/// The Metadata's delegate records, pending changes and authority transfers must be closed first,
+/// except for the delegate record authorizing the rekey
+pub fn rekey_metadata_collection(
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    new_collection_metadata_key_id: u64,
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::RekeyMetadataCollection {
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            new_collection_metadata_key: find_metadata_key_address(new_collection_metadata_key_id)
+                .0,
+            delegate_record: None,
+        },
+        instruction::RekeyMetadataCollection {
+            args: mythic_metadata::RekeyMetadataCollectionArgs { parent_path },
+        },
+    )
+}
+
+/// Lists the Metadata in the registry of its subject, anyone can register a Metadata
+/// The subject_registry_page_index is the SubjectRegistry's next_page_index
+pub fn register_metadata(
+    payer: &Pubkey,
+    seeds: &MetadataSeeds,
+    subject_registry_page_index: u32,
+) -> Instruction {
+    build(
+        accounts::RegisterMetadata {
+            payer: *payer,
+            metadata: seeds.address(),
+            subject_registry: find_subject_registry_address(&seeds.subject).0,
+            subject_registry_page: find_subject_registry_page_address(
+                &seeds.subject,
+                subject_registry_page_index,
+            )
+            .0,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterMetadata {},
+    )
+}
+
+/// Closes the Metadata, signed by the issuing authority
+/// The rent of the items and collections is refunded to the rent_payers and the rest to the
+/// issuing authority. See [crate::metadata_rent_payers]
+/// The registry_page_index is the Metadata's registry_page_index, its entry is removed from the page
+pub fn close_metadata(
+    seeds: &MetadataSeeds,
+    registry_page_index: Option<u32>,
+    rent_payers: &[Pubkey],
+) -> Instruction {
+    let instruction = build(
+        accounts::CloseMetadata {
+            issuing_authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            subject_registry_page: registry_page_index
+                .map(|page_index| find_subject_registry_page_address(&seeds.subject, page_index).0),
+        },
+        instruction::CloseMetadata {},
+    );
+    with_rent_payers(instruction, rent_payers)
+}
+
+/// Rewrites a Metadata account created in the legacy layout, the payer funds its growth
+pub fn migrate_metadata(payer: &Pubkey, metadata: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateMetadata {
+            payer: *payer,
+            metadata: *metadata,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateMetadata {},
+    )
+}
+
+/// Rewrites a MetadataKey account created in the legacy layout, the payer funds its growth
+pub fn migrate_metadata_key(payer: &Pubkey, metadata_key_id: u64) -> Instruction {
+    build(
+        accounts::MigrateMetadataKey {
+            payer: *payer,
+            metadata_key: find_metadata_key_address(metadata_key_id).0,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateMetadataKey {},
+    )
+}
+
+/// Builds the ed25519 program instruction verifying the signer's signature of the message
+/// The signature, public key and message are embedded in the instruction as the program expects
+pub fn ed25519_instruction(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    ed25519_signatures_instruction(&[(*signer, *signature)], message)
+}
+
+/// Builds the ed25519 program instruction verifying the signers' signatures of the same message
+/// The public keys and signatures are followed by the message they all refer to
+pub fn ed25519_signatures_instruction(
+    signatures: &[(Pubkey, [u8; 64])],
+    message: &[u8],
+) -> Instruction {
+    // The signature count and padding followed by the offsets of each signature
+    let signatures_offset = 2 + 14 * signatures.len();
+    let message_data_offset = signatures_offset + (32 + 64) * signatures.len();
+    let offsets = (0..signatures.len()).map(|index| {
+        let public_key_offset = signatures_offset + (32 + 64) * index;
+        Ed25519SignatureOffsets {
+            signature_offset: (public_key_offset + 32) as u16,
+            signature_instruction_index: u16::MAX,
+            public_key_offset: public_key_offset as u16,
+            public_key_instruction_index: u16::MAX,
+            message_data_offset: message_data_offset as u16,
+            message_data_size: message.len() as u16,
+            message_instruction_index: u16::MAX,
+        }
+    });
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data: [vec![signatures.len() as u8, 0]]
+            .into_iter()
+            .chain(offsets.map(|offsets| offsets.try_to_vec().unwrap()))
+            .chain(
+                signatures
+                    .iter()
+                    .map(|(signer, signature)| [signer.as_ref(), signature].concat()),
+            )
+            .chain([message.to_vec()])
+            .collect::<Vec<_>>()
+            .concat(),
+    }
+}
+
+/// Returns the Attestation the issuing authority signs off-chain, sign its message()
+pub fn attestation(
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    value: Vec<u8>,
+    nonce: u64,
+    expiry_slot: u64,
+) -> Attestation {
+    Attestation {
+        subject: seeds.subject,
+        metadata_key_id: seeds.metadata_key_id,
+        collection_path: collection_path.to_vec(),
+        item_metadata_key_id,
+        value,
+        nonce,
+        expiry_slot,
+    }
+}
+
+/// Posts the attestation signed by the issuing authority, returns the ed25519 instruction
+/// verifying the signature and the attest instruction which must immediately follow it
+pub fn attest_metadata_item(
+    payer: &Pubkey,
+    seeds: &MetadataSeeds,
+    attestation: Attestation,
+    signature: &[u8; 64],
+) -> [Instruction; 2] {
+    let metadata = seeds.address();
+    let (collection_metadata_key, parent_path) =
+        split_collection_path(&attestation.collection_path);
+
+    [
+        ed25519_instruction(&seeds.issuing_authority, signature, &attestation.message()),
+        build(
+            accounts::AttestMetadataItem {
+                payer: *payer,
+                metadata,
+                metadata_metadata_key: seeds.metadata_key_address(),
+                collection_metadata_key,
+                item_metadata_key: find_metadata_key_address(attestation.item_metadata_key_id).0,
+                signer_nonce: find_signer_nonce_address(
+                    ATTESTATION_DOMAIN,
+                    &metadata,
+                    &seeds.issuing_authority,
+                )
+                .0,
+                instructions_sysvar: sysvar::instructions::ID,
+                system_program: system_program::ID,
+            },
+            instruction::AttestMetadataItem {
+                args: mythic_metadata::AttestMetadataItemArgs {
+                    value: attestation.value,
+                    parent_path,
+                    nonce: attestation.nonce,
+                    expiry_slot: attestation.expiry_slot,
+                },
+            },
+        ),
+    ]
+}
+
+/// Returns the SignedItemUpdate the update authority signs off-chain, sign its message()
+/// The signers of a MultisigAuthority update authority all sign the message
+pub fn signed_item_update(
+    seeds: &MetadataSeeds,
+    update_authority: &Pubkey,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    new_value: Vec<u8>,
+    nonce: u64,
+    expiry_slot: u64,
+) -> SignedItemUpdate {
+    SignedItemUpdate {
+        metadata: seeds.address(),
+        update_authority: *update_authority,
+        collection_path: collection_path.to_vec(),
+        item_metadata_key_id,
+        new_value,
+        nonce,
+        expiry_slot,
+    }
+}
+
+/// Posts the item update signed by the update authority, returns the ed25519 instruction
+/// verifying the signature and the relay instruction which must immediately follow it
+/// Pass the delegate_collection_path if the update authority is a delegate
+pub fn relay_update_metadata_item(
+    relayer: &Pubkey,
+    seeds: &MetadataSeeds,
+    update: SignedItemUpdate,
+    signature: &[u8; 64],
+    delegate_collection_path: Option<&[u64]>,
+) -> [Instruction; 2] {
+    let metadata = seeds.address();
+    let delegate_record = delegate_collection_path.map(|delegate_collection_path| {
+        find_metadata_delegate_address(
+            &metadata,
+            delegate_collection_path,
+            &update.update_authority,
+        )
+        .0
+    });
+
+    [
+        ed25519_instruction(&update.update_authority, signature, &update.message()),
+        relay_instruction(relayer, seeds, update, delegate_record, None),
+    ]
+}
+
+/// Posts the item update signed by enough signers of the MultisigAuthority update authority,
+/// returns the ed25519 instruction verifying the signatures and the relay instruction
+pub fn relay_multisig_update_metadata_item(
+    relayer: &Pubkey,
+    seeds: &MetadataSeeds,
+    update: SignedItemUpdate,
+    signatures: &[(Pubkey, [u8; 64])],
+) -> [Instruction; 2] {
+    let multisig_authority = update.update_authority;
+
+    [
+        ed25519_signatures_instruction(signatures, &update.message()),
+        relay_instruction(relayer, seeds, update, None, Some(multisig_authority)),
+    ]
+}
+
+fn relay_instruction(
+    relayer: &Pubkey,
+    seeds: &MetadataSeeds,
+    update: SignedItemUpdate,
+    delegate_record: Option<Pubkey>,
+    multisig_authority: Option<Pubkey>,
+) -> Instruction {
+    let metadata = seeds.address();
+    let (collection_metadata_key, parent_path) = split_collection_path(&update.collection_path);
+
+    build(
+        accounts::RelayUpdateMetadataItem {
+            relayer: *relayer,
+            metadata,
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            item_metadata_key: find_metadata_key_address(update.item_metadata_key_id).0,
+            delegate_record,
+            multisig_authority,
+            signer_nonce: find_signer_nonce_address(
+                ITEM_UPDATE_DOMAIN,
+                &metadata,
+                &update.update_authority,
+            )
+            .0,
+            instructions_sysvar: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        },
+        instruction::RelayUpdateMetadataItem {
+            args: mythic_metadata::RelayUpdateMetadataItemArgs {
+                update_authority: update.update_authority,
+                new_value: update.new_value,
+                parent_path,
+                nonce: update.nonce,
+                expiry_slot: update.expiry_slot,
+            },
+        },
+    )
+}
+
+/// Endorses the item of the Metadata holding the value, fails if the item was updated since it was read
+pub fn endorse_metadata_item(
+    payer: &Pubkey,
+    endorser: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    value: &[u8],
+) -> Instruction {
+    let metadata = seeds.address();
+    build(
+        accounts::EndorseMetadataItem {
+            payer: *payer,
+            endorser: *endorser,
+            metadata,
+            endorsement: find_endorsement_address(
+                &metadata,
+                collection_path,
+                item_metadata_key_id,
+                endorser,
+            )
+            .0,
+            system_program: system_program::ID,
+        },
+        instruction::EndorseMetadataItem {
+            args: mythic_metadata::EndorseMetadataItemArgs {
+                collection_path: collection_path.to_vec(),
+                item_metadata_key_id,
+                value_hash: Endorsement::value_hash(value),
+            },
+        },
+    )
+}
+
+pub fn revoke_endorsement(
+    endorser: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+) -> Instruction {
+    build(
+        accounts::RevokeEndorsement {
+            endorser: *endorser,
+            endorsement: find_endorsement_address(
+                &seeds.address(),
+                collection_path,
+                item_metadata_key_id,
+                endorser,
+            )
+            .0,
+        },
+        instruction::RevokeEndorsement {},
+    )
+}
+
+/// Links the EVM address to the Metadata with the signature of EvmAddressLink::message for its subject
+pub fn link_evm_address(
+    payer: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    link: EvmAddressLink,
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::LinkEvmAddress {
+            payer: *payer,
+            authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            item_metadata_key: find_metadata_key_address(item_metadata_key_id).0,
+            delegate_record: None,
+            system_program: system_program::ID,
+        },
+        instruction::LinkEvmAddress {
+            args: mythic_metadata::LinkEvmAddressArgs {
+                address: link.address,
+                signature: link.signature,
+                recovery_id: link.recovery_id,
+                parent_path,
+            },
+        },
+    )
+}
+
+/// Imports the Metaplex Metadata of the mint into the Metadata identified by the seeds
+/// The seeds' issuing authority must be the Metaplex update authority and the subject the mint
+pub fn import_metaplex_metadata(payer: &Pubkey, seeds: &MetadataSeeds) -> Instruction {
+    build(
+        accounts::ImportMetaplexMetadata {
+            payer: *payer,
+            update_authority: seeds.issuing_authority,
+            mint: seeds.subject,
+            metaplex_metadata: crate::metaplex::find_metaplex_metadata_address(&seeds.subject).0,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            system_program: system_program::ID,
+        },
+        instruction::ImportMetaplexMetadata {},
+    )
+}
+
+/// Grants the delegate the permissions over the collection and its nested collections
+/// Pass the Metadata's own MetadataKey Id as the collection_path to grant them over the whole Metadata
+pub fn create_metadata_delegate(
+    payer: &Pubkey,
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    delegate: &Pubkey,
+    permissions: u8,
+) -> Instruction {
+    let (collection_metadata_key, parent_path) = split_collection_path(collection_path);
+    build(
+        accounts::CreateMetadataDelegate {
+            payer: *payer,
+            issuing_authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            collection_metadata_key,
+            delegate_record: find_metadata_delegate_address(
+                &seeds.address(),
+                collection_path,
+                delegate,
+            )
+            .0,
+            system_program: system_program::ID,
+        },
+        instruction::CreateMetadataDelegate {
+            args: mythic_metadata::CreateMetadataDelegateArgs {
+                delegate: *delegate,
+                permissions,
+                parent_path,
+            },
+        },
+    )
+}
+
+pub fn revoke_metadata_delegate(
+    seeds: &MetadataSeeds,
+    collection_path: &[u64],
+    delegate: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RevokeMetadataDelegate {
+            issuing_authority: seeds.issuing_authority,
+            metadata: seeds.address(),
+            metadata_metadata_key: seeds.metadata_key_address(),
+            delegate_record: find_metadata_delegate_address(
+                &seeds.address(),
+                collection_path,
+                delegate,
+            )
+            .0,
+        },
+        instruction::RevokeMetadataDelegate {},
+    )
+}
+
+/// Closes the delegate record of a governance Metadata granted to a governance, or its native
+/// treasury, which isn't the realm's authority anymore and grants one to the realm's authority
+/// realm_authority is the realm's current authority, None if the realm has none
+pub fn sync_governance_delegate(
+    payer: &Pubkey,
+    metadata_key_id: u64,
+    realm: &Pubkey,
+    stale_governance: &Pubkey,
+    stale_delegate: &Pubkey,
+    realm_authority: Option<Pubkey>,
+) -> Instruction {
+    let seeds = MetadataSeeds::governance(metadata_key_id, realm);
+    let metadata = seeds.address();
+
+    build(
+        accounts::SyncGovernanceDelegate {
+            payer: *payer,
+            realm: *realm,
+            governance_program: SPL_GOVERNANCE_PROGRAM_ID,
+            metadata,
+            metadata_metadata_key: seeds.metadata_key_address(),
+            stale_governance: *stale_governance,
+            stale_delegate_record: find_metadata_delegate_address(
+                &metadata,
+                &[metadata_key_id],
+                stale_delegate,
+            )
+            .0,
+            governance: realm_authority.unwrap_or(system_program::ID),
+            delegate_record: realm_authority.map(|realm_authority| {
+                find_metadata_delegate_address(&metadata, &[metadata_key_id], &realm_authority).0
+            }),
+            system_program: system_program::ID,
+        },
+        instruction::SyncGovernanceDelegate {},
+    )
+}
+
+/// Creates an M-of-N update authority at the address derived from the create_key
+pub fn create_multisig_authority(
+    payer: &Pubkey,
+    create_key: &Pubkey,
+    threshold: u8,
+    signers: Vec<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::CreateMultisigAuthority {
+            payer: *payer,
+            multisig_authority: find_multisig_authority_address(create_key).0,
+            system_program: system_program::ID,
+        },
+        instruction::CreateMultisigAuthority {
+            args: mythic_metadata::CreateMultisigAuthorityArgs {
+                create_key: *create_key,
+                threshold,
+                signers,
+            },
+        },
+    )
+}
+
+/// Signs an update instruction for the MultisigAuthority
+/// The instruction's update authority must be one of its signers, the co_signers are the others
+pub fn with_multisig_authority(
+    mut instruction: Instruction,
+    multisig_authority: &Pubkey,
+    co_signers: &[Pubkey],
+) -> Instruction {
+    // The multisig authority is the last optional account
+    if let Some(account) = instruction
+        .accounts
+        .iter_mut()
+        .rev()
+        .find(|account| account.pubkey == mythic_metadata::id())
+    {
+        account.pubkey = *multisig_authority;
+    }
+
+    instruction.accounts.extend(
+        co_signers
+            .iter()
+            .map(|co_signer| AccountMeta::new_readonly(*co_signer, true)),
+    );
+
+    instruction
+}
+
+/// Appends the rent payers receiving the refunds of removed or shrunk data as writable accounts
+/// Rent of payers which aren't passed stays in the Metadata account. See [crate::rent]
+pub fn with_rent_payers(mut instruction: Instruction, rent_payers: &[Pubkey]) -> Instruction {
+    instruction.accounts.extend(
+        rent_payers
+            .iter()
+            .map(|rent_payer| AccountMeta::new(*rent_payer, false)),
+    );
+    instruction
+}
+
+/// Signs an instruction built for the issuing authority or an update authority with a delegate instead
+/// The delegate record is scoped to the collection identified by delegate_collection_path
+pub fn with_delegate(
+    mut instruction: Instruction,
+    seeds: &MetadataSeeds,
+    authority: &Pubkey,
+    delegate: &Pubkey,
+    delegate_collection_path: &[u64],
+) -> Instruction {
+    let (delegate_record, _) =
+        find_metadata_delegate_address(&seeds.address(), delegate_collection_path, delegate);
+
+    // The authority always follows the payer
+    if let Some(account) = instruction
+        .accounts
+        .iter_mut()
+        .rev()
+        .find(|account| account.is_signer && account.pubkey == *authority)
+    {
+        account.pubkey = *delegate;
+    }
+
+    // Anchor passes the program id in place of an omitted optional account
+    if let Some(account) = instruction
+        .accounts
+        .iter_mut()
+        .find(|account| account.pubkey == mythic_metadata::id())
+    {
+        account.pubkey = delegate_record;
+    }
+
+    instruction
+}
+
+fn token_metadata_instruction(
+    accounts: Vec<AccountMeta>,
+    instruction: TokenMetadataInstruction,
+) -> Instruction {
+    Instruction {
+        program_id: mythic_metadata::id(),
+        accounts,
+        data: instruction.pack(),
+    }
+}
+
+/// Initializes the token Metadata of the mint, signed by the mint authority
+/// Without a payer this is the interface's 4 account form, the token Metadata address must
+/// then be funded with the rent exempt lamports beforehand
+pub fn initialize_token_metadata(
+    payer: Option<Pubkey>,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    update_authority: Option<Pubkey>,
+    args: Initialize,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(find_token_metadata_address(mint).0, false),
+        AccountMeta::new_readonly(update_authority.unwrap_or_default(), false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*mint_authority, true),
+    ];
+    if let Some(payer) = payer {
+        accounts.extend([
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]);
+    }
+
+    token_metadata_instruction(accounts, TokenMetadataInstruction::Initialize(args))
+}
+
+/// Updates a field of the token Metadata, the update authority pays for the account growth
+pub fn update_token_metadata_field(
+    mint: &Pubkey,
+    update_authority: &Pubkey,
+    field: Field,
+    value: String,
+) -> Instruction {
+    token_metadata_instruction(
+        vec![
+            AccountMeta::new(find_token_metadata_address(mint).0, false),
+            AccountMeta::new(*update_authority, true),
+            AccountMeta::new(*update_authority, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        TokenMetadataInstruction::UpdateField(UpdateField { field, value }),
+    )
+}
+
+/// Removes an additional field, its rent is refunded to the rent_payers of the field
+/// See [crate::item_rent_payers]
+pub fn remove_token_metadata_key(
+    mint: &Pubkey,
+    update_authority: &Pubkey,
+    key: String,
+    idempotent: bool,
+    rent_payers: &[Pubkey],
+) -> Instruction {
+    let instruction = token_metadata_instruction(
+        vec![
+            AccountMeta::new(find_token_metadata_address(mint).0, false),
+            AccountMeta::new_readonly(*update_authority, true),
+        ],
+        TokenMetadataInstruction::RemoveKey(RemoveKey { idempotent, key }),
+    );
+    with_rent_payers(instruction, rent_payers)
+}
+
+/// Proposes the new update authority of the token Metadata, which accepts it with
+/// accept_update_authority_transfer, the payer funds the AuthorityTransfer
+/// Without a new authority the token Metadata is made immutable right away
+pub fn update_token_metadata_authority(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    update_authority: &Pubkey,
+    new_authority: Option<Pubkey>,
+) -> Instruction {
+    let metadata = find_token_metadata_address(mint).0;
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(*update_authority, true),
+    ];
+    if new_authority.is_some() {
+        accounts.extend([
+            AccountMeta::new(
+                find_authority_transfer_address(&metadata, &[TOKEN_METADATA_KEY_ID]).0,
+                false,
+            ),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]);
+    }
+
+    token_metadata_instruction(
+        accounts,
+        TokenMetadataInstruction::UpdateAuthority(UpdateAuthority {
+            new_authority: new_authority.unwrap_or_default(),
+        }),
+    )
+}
+
+/// Signs a token Metadata update instruction for the MultisigAuthority update authority
+/// The instruction is built for the MultisigAuthority, the first signer pays for the growth
+/// of update_token_metadata_field
+pub fn with_token_metadata_multisig_authority(
+    mut instruction: Instruction,
+    signers: &[Pubkey],
+) -> Instruction {
+    let multisig_authority = instruction.accounts[1].pubkey;
+    instruction.accounts[1] = AccountMeta::new_readonly(multisig_authority, false);
+    if let (Some(payer), Some(signer)) = (instruction.accounts.get_mut(2), signers.first()) {
+        if payer.pubkey == multisig_authority {
+            *payer = AccountMeta::new(*signer, true);
+        }
+    }
+
+    instruction.accounts.extend(
+        signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    );
+
+    instruction
+}
+
+/// Returns the borsh serialized TokenMetadata, or the start..end range of it, as return data
+pub fn emit_token_metadata(mint: &Pubkey, start: Option<u64>, end: Option<u64>) -> Instruction {
+    token_metadata_instruction(
+        vec![AccountMeta::new_readonly(
+            find_token_metadata_address(mint).0,
+            false,
+        )],
+        TokenMetadataInstruction::Emit(Emit { start, end }),
+    )
+}
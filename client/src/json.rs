@@ -0,0 +1,238 @@
+//! Human readable JSON representation of a Metadata account
+//!
+//! Collections and items are named by their MetadataKey.name and values are decoded
+//! according to the MetadataKey.content_type and multi_valued flag, see [crate::value]
+//! Nested collections are nested in the collections of their parent
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::Pubkey;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::*;
+use crate::keys::*;
+use crate::value::*;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MetadataDocument {
+    #[serde(with = "pubkey_string")]
+    pub subject: Pubkey,
+
+    /// Name of the Metadata's MetadataKey
+    pub metadata_key: String,
+
+    #[serde(with = "pubkey_string")]
+    pub issuing_authority: Pubkey,
+
+    #[serde(default, with = "option_pubkey_string")]
+    pub update_authority: Option<Pubkey>,
+
+    /// Set by the program when the subject signed the creation, it can't be imported
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub verified_subject: bool,
+
+    /// Root items by MetadataKey name
+    #[serde(default)]
+    pub items: BTreeMap<String, Value>,
+
+    /// Collections by MetadataKey name
+    #[serde(default)]
+    pub collections: BTreeMap<String, MetadataCollectionDocument>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MetadataCollectionDocument {
+    #[serde(default, with = "option_pubkey_string")]
+    pub update_authority: Option<Pubkey>,
+
+    /// Collection items by MetadataKey name
+    #[serde(default)]
+    pub items: BTreeMap<String, Value>,
+
+    /// Nested collections by MetadataKey name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub collections: BTreeMap<String, MetadataCollectionDocument>,
+}
+
+fn export_items(items: &[MetadataItem], keys: &MetadataKeys) -> Result<BTreeMap<String, Value>> {
+    items
+        .iter()
+        .map(|item| {
+            let item_metadata_key = keys.get(item.metadata_key_id)?;
+            let value = decode_item_value(item_metadata_key, &item.value)?;
+            Ok((item_metadata_key.name.clone(), value))
+        })
+        .collect()
+}
+
+fn import_items(
+    items: &BTreeMap<String, Value>,
+    keys: &MetadataKeys,
+    slot: u64,
+) -> Result<Vec<MetadataItem>> {
+    let mut items = items
+        .iter()
+        .map(|(name, value)| {
+            let item_metadata_key = keys.get_by_name(name)?;
+            Ok(MetadataItem {
+                metadata_key_id: item_metadata_key.id,
+                update_slot: slot,
+                value: encode_item_value(item_metadata_key, value)?,
+                rent_payer: Pubkey::default(),
+                growth_rent_shares: vec![],
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    items.sort_by_key(|item| item.metadata_key_id);
+    Ok(items)
+}
+
+/// Flattens the collection documents and their nested collections into MetadataCollections
+fn import_collections(
+    collections: &BTreeMap<String, MetadataCollectionDocument>,
+    parent_path: &[u64],
+    keys: &MetadataKeys,
+    imported_collections: &mut Vec<MetadataCollection>,
+) -> Result<()> {
+    for (name, collection) in collections {
+        let metadata_key_id = keys.get_by_name(name)?.id;
+        imported_collections.push(MetadataCollection {
+            metadata_key_id,
+            parent_path: parent_path.to_vec(),
+            update_slot: 0,
+            update_authority: collection.update_authority,
+            timelock_slots: 0,
+            frozen: false,
+            items: import_items(&collection.items, keys, 0)?,
+            rent_payer: Pubkey::default(),
+        });
+
+        import_collections(
+            &collection.collections,
+            &collection_path(parent_path, metadata_key_id),
+            keys,
+            imported_collections,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Exports the Metadata account to a MetadataDocument
+/// All the MetadataKeys referenced by the Metadata must be present in keys
+pub fn export_metadata(metadata: &Metadata, keys: &MetadataKeys) -> Result<MetadataDocument> {
+    let mut collections: BTreeMap<String, MetadataCollectionDocument> = BTreeMap::new();
+
+    // Collections are sorted by their paths so the parents are exported before their nested collections
+    for collection in &metadata.collections {
+        let mut parent_collections = &mut collections;
+        for parent_metadata_key_id in &collection.parent_path {
+            let parent_name = &keys.get(*parent_metadata_key_id)?.name;
+            parent_collections = &mut parent_collections
+                .get_mut(parent_name)
+                .ok_or(anchor_lang::error::Error::from(
+                    MythicMetadataError::MetadataCollectionNonExistent,
+                ))?
+                .collections;
+        }
+
+        parent_collections.insert(
+            keys.get(collection.metadata_key_id)?.name.clone(),
+            MetadataCollectionDocument {
+                update_authority: collection.update_authority,
+                items: export_items(&collection.items, keys)?,
+                collections: BTreeMap::new(),
+            },
+        );
+    }
+
+    Ok(MetadataDocument {
+        subject: metadata.subject,
+        metadata_key: keys.get(metadata.metadata_key_id)?.name.clone(),
+        issuing_authority: metadata.issuing_authority,
+        update_authority: metadata.update_authority,
+        verified_subject: metadata.verified_subject,
+        items: export_items(&metadata.items, keys)?,
+        collections,
+    })
+}
+
+pub fn export_metadata_json(metadata: &Metadata, keys: &MetadataKeys) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&export_metadata(
+        metadata, keys,
+    )?)?)
+}
+
+/// Converts the MetadataDocument to the desired Metadata state
+/// The returned Metadata is validated against the program limits and can be passed to [crate::plan_import]
+pub fn import_metadata(document: &MetadataDocument, keys: &MetadataKeys) -> Result<Metadata> {
+    let mut collections = vec![];
+    import_collections(&document.collections, &[], keys, &mut collections)?;
+    collections.sort_by(|a, b| a.path().cmp(b.path()));
+
+    let metadata = Metadata {
+        subject: document.subject,
+        metadata_key_id: keys.get_by_name(&document.metadata_key)?.id,
+        issuing_authority: document.issuing_authority,
+        update_slot: 0,
+        update_authority: document.update_authority,
+        items: import_items(&document.items, keys, 0)?,
+        collections,
+        bump: 0,
+        verified_subject: document.verified_subject,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    };
+    metadata.validate()?;
+
+    Ok(metadata)
+}
+
+pub fn import_metadata_json(json: &str, keys: &MetadataKeys) -> Result<Metadata> {
+    import_metadata(&serde_json::from_str(json)?, keys)
+}
+
+mod pubkey_string {
+    use std::str::FromStr;
+
+    use anchor_lang::prelude::Pubkey;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(pubkey)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let pubkey = String::deserialize(deserializer)?;
+        Pubkey::from_str(&pubkey).map_err(D::Error::custom)
+    }
+}
+
+mod option_pubkey_string {
+    use std::str::FromStr;
+
+    use anchor_lang::prelude::Pubkey;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        pubkey: &Option<Pubkey>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match pubkey {
+            Some(pubkey) => serializer.collect_str(pubkey),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Pubkey>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|pubkey| Pubkey::from_str(&pubkey).map_err(D::Error::custom))
+            .transpose()
+    }
+}
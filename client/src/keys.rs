@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+
+use mythic_metadata::state::MetadataKey;
+
+use crate::errors::*;
+
+/// A set of fetched MetadataKey accounts used to resolve ids to names and content types
+#[derive(Clone, Debug, Default)]
+pub struct MetadataKeys {
+    keys: BTreeMap<u64, MetadataKey>,
+}
+
+impl MetadataKeys {
+    pub fn new(keys: impl IntoIterator<Item = MetadataKey>) -> Self {
+        Self {
+            keys: keys.into_iter().map(|key| (key.id, key)).collect(),
+        }
+    }
+
+    pub fn insert(&mut self, key: MetadataKey) {
+        self.keys.insert(key.id, key);
+    }
+
+    pub fn get(&self, id: u64) -> Result<&MetadataKey> {
+        self.keys
+            .get(&id)
+            .ok_or(MythicMetadataClientError::MetadataKeyNotFound(id))
+    }
+
+    /// Returns the MetadataKey with the given name
+    /// Names are not unique on-chain so a name shared by several keys is rejected
+    pub fn get_by_name(&self, name: &str) -> Result<&MetadataKey> {
+        let mut keys = self.keys.values().filter(|key| key.name == name);
+        match (keys.next(), keys.next()) {
+            (Some(key), None) => Ok(key),
+            (Some(_), Some(_)) => Err(MythicMetadataClientError::AmbiguousMetadataKeyName(
+                name.to_string(),
+            )),
+            (None, _) => Err(MythicMetadataClientError::MetadataKeyNameNotFound(
+                name.to_string(),
+            )),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MetadataKey> {
+        self.keys.values()
+    }
+}
@@ -0,0 +1,30 @@
+//! Client library for the Metadata program
+//!
+//! Provides PDA derivation, instruction builders, JSON import/export of Metadata
+//! accounts, the Metaplex bridge, verification of external references, readers of the on-chain
+//! registries and endorsements, the rent payers of Metadata data and planners which
+//! turn a desired Metadata state into instructions
+
+pub mod endorsement;
+pub mod errors;
+pub mod instructions;
+pub mod json;
+pub mod keys;
+pub mod metaplex;
+pub mod pda;
+pub mod plan;
+pub mod reference;
+pub mod registry;
+pub mod rent;
+pub mod value;
+
+pub use endorsement::*;
+pub use errors::*;
+pub use json::*;
+pub use keys::*;
+pub use metaplex::*;
+pub use pda::*;
+pub use plan::*;
+pub use reference::*;
+pub use registry::*;
+pub use rent::*;
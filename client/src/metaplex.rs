@@ -0,0 +1,57 @@
+//! Bridge between Metaplex Token Metadata and the Metadata imported by import_metaplex_metadata
+
+use anchor_lang::prelude::Pubkey;
+use mythic_metadata::constants::*;
+pub use mythic_metadata::metaplex::{MetaplexMetadata, MPL_TOKEN_METADATA_PROGRAM_ID};
+use mythic_metadata::state::Metadata;
+
+use crate::errors::*;
+
+pub fn find_metaplex_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            MPL_TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &MPL_TOKEN_METADATA_PROGRAM_ID,
+    )
+}
+
+/// Reads the Metaplex fields back from a Metadata the Metaplex Metadata was imported into
+///
+/// The update_authority is the Metadata issuing authority, which was the Metaplex update authority
+/// at the time of the import. Returns None if nothing was imported
+pub fn read_metaplex_metadata(metadata: &Metadata) -> Result<Option<MetaplexMetadata>> {
+    let value = |metadata_key_id: u64| {
+        metadata
+            .items
+            .binary_search_by_key(&metadata_key_id, |item| item.metadata_key_id)
+            .ok()
+            .map(|item_index| {
+                String::from_utf8(metadata.items[item_index].value.clone()).map_err(|err| {
+                    MythicMetadataClientError::InvalidValue {
+                        content_type: "string".to_string(),
+                        reason: err.to_string(),
+                    }
+                })
+            })
+            .transpose()
+    };
+
+    let (Some(name), Some(symbol), Some(uri)) = (
+        value(METAPLEX_NAME_KEY_ID)?,
+        value(METAPLEX_SYMBOL_KEY_ID)?,
+        value(METAPLEX_URI_KEY_ID)?,
+    ) else {
+        return Ok(None);
+    };
+
+    Ok(Some(MetaplexMetadata {
+        update_authority: metadata.issuing_authority,
+        mint: metadata.subject,
+        name,
+        symbol,
+        uri,
+    }))
+}
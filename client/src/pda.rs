@@ -0,0 +1,211 @@
+use anchor_lang::prelude::Pubkey;
+use mythic_metadata::constants::*;
+use mythic_metadata::state::{item_seed, path_seed, Metadata};
+
+pub fn find_metadata_key_address(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PREFIX, METADATA_KEY, &id.to_le_bytes()],
+        &mythic_metadata::id(),
+    )
+}
+
+pub fn find_metadata_address(
+    metadata_key_id: u64,
+    issuing_authority: &Pubkey,
+    subject: &Pubkey,
+) -> (Pubkey, u8) {
+    let (metadata_key, _) = find_metadata_key_address(metadata_key_id);
+    Pubkey::find_program_address(
+        &[
+            PREFIX,
+            METADATA,
+            metadata_key.as_ref(),
+            issuing_authority.as_ref(),
+            subject.as_ref(),
+        ],
+        &mythic_metadata::id(),
+    )
+}
+
+pub fn find_metadata_delegate_address(
+    metadata: &Pubkey,
+    collection_path: &[u64],
+    delegate: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX,
+            DELEGATE,
+            metadata.as_ref(),
+            &path_seed(collection_path),
+            delegate.as_ref(),
+        ],
+        &mythic_metadata::id(),
+    )
+}
+
+/// Derives the registry indexing the Metadata accounts about the subject
+pub fn find_subject_registry_address(subject: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PREFIX, SUBJECT_REGISTRY, subject.as_ref()],
+        &mythic_metadata::id(),
+    )
+}
+
+/// Derives the page of the subject's registry listing Metadata accounts
+pub fn find_subject_registry_page_address(subject: &Pubkey, page_index: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX,
+            SUBJECT_REGISTRY,
+            subject.as_ref(),
+            &page_index.to_le_bytes(),
+        ],
+        &mythic_metadata::id(),
+    )
+}
+
+pub fn find_namespace_registry_address(namespace_authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PREFIX, NAMESPACE_REGISTRY, namespace_authority.as_ref()],
+        &mythic_metadata::id(),
+    )
+}
+
+pub fn find_namespace_registry_page_address(
+    namespace_authority: &Pubkey,
+    page_index: u32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX,
+            NAMESPACE_REGISTRY,
+            namespace_authority.as_ref(),
+            &page_index.to_le_bytes(),
+        ],
+        &mythic_metadata::id(),
+    )
+}
+
+/// Derives the record of the last nonce of the signer's messages of the domain applied to the Metadata
+/// The domain is ATTESTATION_DOMAIN or ITEM_UPDATE_DOMAIN
+pub fn find_signer_nonce_address(
+    domain: &[u8],
+    metadata: &Pubkey,
+    signer: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PREFIX, NONCE, domain, metadata.as_ref(), signer.as_ref()],
+        &mythic_metadata::id(),
+    )
+}
+
+pub fn find_endorsement_address(
+    metadata: &Pubkey,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+    endorser: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX,
+            ENDORSEMENT,
+            metadata.as_ref(),
+            &item_seed(collection_path, item_metadata_key_id),
+            endorser.as_ref(),
+        ],
+        &mythic_metadata::id(),
+    )
+}
+
+pub fn find_pending_change_address(
+    metadata: &Pubkey,
+    collection_path: &[u64],
+    item_metadata_key_id: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX,
+            PENDING_CHANGE,
+            metadata.as_ref(),
+            &item_seed(collection_path, item_metadata_key_id),
+        ],
+        &mythic_metadata::id(),
+    )
+}
+
+pub fn find_authority_transfer_address(metadata: &Pubkey, collection_path: &[u64]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX,
+            AUTHORITY_TRANSFER,
+            metadata.as_ref(),
+            &path_seed(collection_path),
+        ],
+        &mythic_metadata::id(),
+    )
+}
+
+pub fn find_multisig_authority_address(create_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PREFIX, MULTISIG, create_key.as_ref()],
+        &mythic_metadata::id(),
+    )
+}
+
+/// Derives the canonical Metadata of a DAO from its SPL Governance realm
+pub fn find_governance_metadata_address(metadata_key_id: u64, realm: &Pubkey) -> (Pubkey, u8) {
+    find_metadata_address(metadata_key_id, realm, realm)
+}
+
+pub fn find_native_treasury_address(
+    governance: &Pubkey,
+    governance_program: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NATIVE_TREASURY, governance.as_ref()], governance_program)
+}
+
+/// Derives the Metadata a Token-2022 metadata pointer should point at, see [mythic_metadata::token_metadata]
+pub fn find_token_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
+    mythic_metadata::token_metadata::processor::find_token_metadata_address(
+        mint,
+        &mythic_metadata::id(),
+    )
+}
+
+/// The seeds which identify a Metadata account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetadataSeeds {
+    pub metadata_key_id: u64,
+    pub issuing_authority: Pubkey,
+    pub subject: Pubkey,
+}
+
+impl MetadataSeeds {
+    /// The seeds of the canonical Metadata of a DAO
+    pub fn governance(metadata_key_id: u64, realm: &Pubkey) -> Self {
+        Self {
+            metadata_key_id,
+            issuing_authority: *realm,
+            subject: *realm,
+        }
+    }
+
+    pub fn address(&self) -> Pubkey {
+        find_metadata_address(self.metadata_key_id, &self.issuing_authority, &self.subject).0
+    }
+
+    pub fn metadata_key_address(&self) -> Pubkey {
+        find_metadata_key_address(self.metadata_key_id).0
+    }
+}
+
+impl From<&Metadata> for MetadataSeeds {
+    fn from(metadata: &Metadata) -> Self {
+        Self {
+            metadata_key_id: metadata.metadata_key_id,
+            issuing_authority: metadata.issuing_authority,
+            subject: metadata.subject,
+        }
+    }
+}
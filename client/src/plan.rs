@@ -0,0 +1,399 @@
+//! Planners which compute the instructions needed to bring an on-chain Metadata to a desired state
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::message::Message;
+use mythic_metadata::state::*;
+
+use crate::errors::*;
+use crate::instructions::*;
+use crate::pda::*;
+use crate::rent::*;
+
+/// Maximum size of a serialized transaction (the network packet size)
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// The authority which has to sign a planned step in addition to the fee payer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepSigner {
+    /// The Metadata issuing_authority, required by create, append, remove and authority instructions
+    IssuingAuthority(Pubkey),
+    /// The Metadata or collection update_authority, required by update instructions
+    UpdateAuthority(Pubkey),
+}
+
+impl StepSigner {
+    pub fn pubkey(&self) -> Pubkey {
+        match self {
+            StepSigner::IssuingAuthority(pubkey) | StepSigner::UpdateAuthority(pubkey) => *pubkey,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanStep {
+    pub instruction: Instruction,
+    pub signer: StepSigner,
+}
+
+/// Returns the serialized size of a legacy transaction with the given instructions
+pub fn transaction_size(payer: &Pubkey, instructions: &[Instruction]) -> usize {
+    let message = Message::new(instructions, Some(payer));
+    let signatures = usize::from(message.header.num_required_signatures);
+    let signatures_len = if signatures < 0x80 { 1 } else { 2 };
+
+    signatures_len + 64 * signatures + message.serialize().len()
+}
+
+/// Groups the steps, in order, into as few transactions as possible
+/// Fails if a single step doesn't fit into a transaction on its own
+pub fn batch_steps(payer: &Pubkey, steps: Vec<PlanStep>) -> Result<Vec<Vec<PlanStep>>> {
+    let mut transactions: Vec<Vec<PlanStep>> = vec![];
+    let mut instructions: Vec<Instruction> = vec![];
+
+    for step in steps {
+        instructions.push(step.instruction.clone());
+        if transactions.is_empty() || transaction_size(payer, &instructions) > MAX_TRANSACTION_SIZE
+        {
+            instructions = vec![step.instruction.clone()];
+            let size = transaction_size(payer, &instructions);
+            if size > MAX_TRANSACTION_SIZE {
+                return Err(MythicMetadataClientError::TransactionTooLarge(size));
+            }
+            transactions.push(vec![step]);
+        } else {
+            transactions.last_mut().unwrap().push(step);
+        }
+    }
+
+    Ok(transactions)
+}
+
+/// Returns the update authority allowed to update items of the given collection,
+/// the nearest collection update authority inherited from the parent collections or
+/// the Metadata update authority, which falls back to the issuing authority when revoked
+fn item_update_authority(metadata: &Metadata, collection_path: &[u64]) -> Result<Pubkey> {
+    let collection_update_authority = if metadata.is_root(collection_path) {
+        None
+    } else {
+        (1..=collection_path.len()).rev().find_map(|depth| {
+            metadata
+                .collection_index(&collection_path[..depth])
+                .ok()
+                .and_then(|collection_index| {
+                    metadata.collections[collection_index].update_authority
+                })
+        })
+    };
+
+    match collection_update_authority {
+        Some(update_authority) => Ok(update_authority),
+        None => metadata
+            .effective_update_authority()
+            .ok_or(MythicMetadataClientError::ImmutableMetadata),
+    }
+}
+
+fn collection_path_of(collection: &MetadataCollection) -> Vec<u64> {
+    collection.path().copied().collect()
+}
+
+struct Planner<'a> {
+    payer: &'a Pubkey,
+    seeds: MetadataSeeds,
+    steps: Vec<PlanStep>,
+}
+
+impl<'a> Planner<'a> {
+    fn issuing_authority_step(&mut self, instruction: Instruction) {
+        self.steps.push(PlanStep {
+            instruction,
+            signer: StepSigner::IssuingAuthority(self.seeds.issuing_authority),
+        });
+    }
+
+    /// Appends the items using as few append_metadata_items instructions as fit into transactions
+    fn append_items(&mut self, collection_path: &[u64], items: Vec<(u64, Vec<u8>)>) {
+        let mut chunk: Vec<(u64, Vec<u8>)> = vec![];
+
+        for item in items {
+            chunk.push(item);
+            let instruction =
+                append_metadata_items(self.payer, &self.seeds, collection_path, chunk.clone());
+            if chunk.len() > 1
+                && transaction_size(self.payer, &[instruction]) > MAX_TRANSACTION_SIZE
+            {
+                let item = chunk.pop().unwrap();
+                let instruction = append_metadata_items(
+                    self.payer,
+                    &self.seeds,
+                    collection_path,
+                    std::mem::replace(&mut chunk, vec![item]),
+                );
+                self.issuing_authority_step(instruction);
+            }
+        }
+
+        if !chunk.is_empty() {
+            let instruction =
+                append_metadata_items(self.payer, &self.seeds, collection_path, chunk);
+            self.issuing_authority_step(instruction);
+        }
+    }
+
+    /// Plans the appends, updates and, when pruning, removals which make the items of
+    /// a single collection match the desired items
+    fn items(
+        &mut self,
+        current: &Metadata,
+        collection_path: &[u64],
+        desired_items: &[MetadataItem],
+        current_items: &[MetadataItem],
+        prune: bool,
+    ) -> Result<()> {
+        let mut appended_items = vec![];
+        let mut updated_items = vec![];
+
+        for desired_item in desired_items {
+            match current_items
+                .binary_search_by_key(&desired_item.metadata_key_id, |item| item.metadata_key_id)
+            {
+                Ok(item_index) if current_items[item_index].value == desired_item.value => {}
+                Ok(item_index) => updated_items.push((&current_items[item_index], desired_item)),
+                Err(_) => {
+                    appended_items.push((desired_item.metadata_key_id, desired_item.value.clone()))
+                }
+            }
+        }
+
+        if prune {
+            for current_item in current_items {
+                if desired_items
+                    .binary_search_by_key(&current_item.metadata_key_id, |item| {
+                        item.metadata_key_id
+                    })
+                    .is_err()
+                {
+                    let instruction = remove_metadata_item(
+                        &self.seeds,
+                        collection_path,
+                        current_item.metadata_key_id,
+                        &item_rent_payers(current_item),
+                    );
+                    self.issuing_authority_step(instruction);
+                }
+            }
+        }
+
+        self.append_items(collection_path, appended_items);
+
+        for (current_item, desired_item) in updated_items {
+            let update_authority = item_update_authority(current, collection_path)?;
+            let instruction = update_metadata_item(
+                &update_authority,
+                &self.seeds,
+                collection_path,
+                desired_item.metadata_key_id,
+                desired_item.value.clone(),
+            );
+            self.steps.push(PlanStep {
+                // The rent payers are refunded if the item shrinks
+                instruction: with_rent_payers(instruction, &item_rent_payers(current_item)),
+                signer: StepSigner::UpdateAuthority(update_authority),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn update_authority(
+        &mut self,
+        collection_path: &[u64],
+        desired: Option<Pubkey>,
+        current: Option<Pubkey>,
+    ) {
+        if desired == current {
+            return;
+        }
+
+        let instruction = match desired {
+            Some(desired) => propose_update_authority_transfer(
+                self.payer,
+                &self.seeds,
+                collection_path,
+                &desired,
+            ),
+            None => revoke_collection_update_authority(&self.seeds, collection_path),
+        };
+        self.issuing_authority_step(instruction);
+    }
+}
+
+fn plan(
+    payer: &Pubkey,
+    desired: &Metadata,
+    current: Option<&Metadata>,
+    subject_registry_page_index: u32,
+    prune: bool,
+) -> Result<Vec<PlanStep>> {
+    let mut planner = Planner {
+        payer,
+        seeds: MetadataSeeds::from(desired),
+        steps: vec![],
+    };
+
+    let created;
+    let current = match current {
+        Some(current) => {
+            let current_seeds = MetadataSeeds::from(current);
+            if current_seeds != planner.seeds {
+                return Err(MythicMetadataClientError::MetadataMismatch {
+                    desired: planner.seeds.address(),
+                    current: current_seeds.address(),
+                });
+            }
+            current
+        }
+        None => {
+            let instruction = create_metadata(
+                payer,
+                &planner.seeds,
+                Some(subject_registry_page_index),
+                desired.update_authority,
+            );
+            planner.issuing_authority_step(instruction);
+            created = Metadata {
+                items: vec![],
+                collections: vec![],
+                ..desired.clone()
+            };
+            &created
+        }
+    };
+
+    if prune {
+        let mut removed_paths: Vec<Vec<u64>> = vec![];
+        for current_collection in &current.collections {
+            // Nested collections are removed together with their parent
+            if removed_paths
+                .iter()
+                .any(|removed_path| current_collection.is_descendant_of(removed_path))
+            {
+                continue;
+            }
+
+            let collection_path = collection_path_of(current_collection);
+            if desired.collection_index(&collection_path).is_err() {
+                let instruction = remove_metadata_collection(
+                    &planner.seeds,
+                    &collection_path,
+                    &collection_rent_payers(current, &collection_path)?,
+                );
+                planner.issuing_authority_step(instruction);
+                removed_paths.push(collection_path);
+            }
+        }
+    }
+
+    planner.items(
+        current,
+        &[current.metadata_key_id],
+        &desired.items,
+        &current.items,
+        prune,
+    )?;
+
+    // Collections are sorted by their paths so parents are appended before their nested collections
+    for desired_collection in &desired.collections {
+        let collection_path = collection_path_of(desired_collection);
+        let current_collection = current
+            .collection_index(&collection_path)
+            .map(|collection_index| &current.collections[collection_index]);
+
+        let current_items = match current_collection {
+            Ok(current_collection) => current_collection.items.as_slice(),
+            Err(_) => {
+                let instruction = append_metadata_collection(
+                    payer,
+                    &planner.seeds,
+                    &collection_path,
+                    desired_collection.update_authority,
+                );
+                planner.issuing_authority_step(instruction);
+                &[]
+            }
+        };
+
+        planner.items(
+            current,
+            &collection_path,
+            &desired_collection.items,
+            current_items,
+            prune,
+        )?;
+    }
+
+    // Authorities are changed last so the updates above are signed by the current authorities
+    if prune {
+        for desired_collection in &desired.collections {
+            let collection_path = collection_path_of(desired_collection);
+            if let Ok(collection_index) = current.collection_index(&collection_path) {
+                planner.update_authority(
+                    &collection_path,
+                    desired_collection.update_authority,
+                    current.collections[collection_index].update_authority,
+                );
+            }
+        }
+        planner.update_authority(
+            &[current.metadata_key_id],
+            desired.update_authority,
+            current.update_authority,
+        );
+    }
+
+    if current.finalized_slot.is_some() && !planner.steps.is_empty() {
+        return Err(MythicMetadataClientError::ImmutableMetadata);
+    }
+
+    Ok(planner.steps)
+}
+
+/// Plans the minimal set of instructions which creates the desired collections and items
+///
+/// The Metadata is created if current is None and listed in the subject registry page
+/// subject_registry_page_index, see [crate::next_subject_registry_page_index].
+/// Missing collections are appended, missing items are appended with as few
+/// append_metadata_items instructions as fit into transactions and items with a different value
+/// are updated. Collections and items which are present on-chain but not desired are kept
+pub fn plan_import(
+    payer: &Pubkey,
+    desired: &Metadata,
+    current: Option<&Metadata>,
+    subject_registry_page_index: u32,
+) -> Result<Vec<Instruction>> {
+    Ok(
+        plan(payer, desired, current, subject_registry_page_index, false)?
+            .into_iter()
+            .map(|step| step.instruction)
+            .collect(),
+    )
+}
+
+/// Plans the steps which make the on-chain Metadata match the desired Metadata exactly
+///
+/// In addition to [plan_import] collections and items which are not desired are removed and
+/// the Metadata and collection update authorities are revoked or transferred to the desired ones.
+/// Transfers are proposed and take effect once the new update authorities accept them.
+/// The steps are ordered as: create, collection removals, then for the root items and each
+/// collection the item removals, appends and updates, and finally the authority changes.
+/// Updates are therefore signed by the update authorities in effect before the sync.
+/// Use [batch_steps] to group the steps into transactions
+pub fn plan_sync(
+    payer: &Pubkey,
+    desired: &Metadata,
+    current: Option<&Metadata>,
+    subject_registry_page_index: u32,
+) -> Result<Vec<PlanStep>> {
+    plan(payer, desired, current, subject_registry_page_index, true)
+}
@@ -0,0 +1,75 @@
+//! Fetching and verification of the off-chain content anchored by ExternalReference items
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use mythic_metadata::state::ExternalReference;
+
+use crate::errors::*;
+
+/// Fetches the content an ExternalReference uri points at
+pub trait ContentFetcher {
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>>;
+}
+
+/// Fetches `file://` uris and paths relative to the root directory
+pub struct FileFetcher {
+    pub root: PathBuf,
+}
+
+impl ContentFetcher for FileFetcher {
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>> {
+        let path = uri.strip_prefix("file://").unwrap_or(uri);
+        std::fs::read(self.root.join(path)).map_err(|error| {
+            MythicMetadataClientError::ContentFetchFailed {
+                uri: uri.to_string(),
+                reason: error.to_string(),
+            }
+        })
+    }
+}
+
+/// Serves fixed content by uri, a stand-in for an HTTP client
+#[derive(Default)]
+pub struct StaticFetcher {
+    pub content: HashMap<String, Vec<u8>>,
+}
+
+impl StaticFetcher {
+    pub fn insert(&mut self, uri: impl Into<String>, content: Vec<u8>) {
+        self.content.insert(uri.into(), content);
+    }
+}
+
+impl ContentFetcher for StaticFetcher {
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>> {
+        self.content.get(uri).cloned().ok_or_else(|| {
+            MythicMetadataClientError::ContentFetchFailed {
+                uri: uri.to_string(),
+                reason: "not found".to_string(),
+            }
+        })
+    }
+}
+
+/// Verifies the content against the size and digest recorded on-chain
+pub fn verify_content(external_reference: &ExternalReference, content: &[u8]) -> Result<()> {
+    if external_reference.matches(content) {
+        Ok(())
+    } else {
+        Err(MythicMetadataClientError::ContentMismatch(
+            external_reference.uri.clone(),
+        ))
+    }
+}
+
+/// Fetches the referenced content and returns it only if it matches the on-chain record
+pub fn fetch_and_verify(
+    external_reference: &ExternalReference,
+    fetcher: &impl ContentFetcher,
+) -> Result<Vec<u8>> {
+    let content = fetcher.fetch(&external_reference.uri)?;
+    verify_content(external_reference, &content)?;
+
+    Ok(content)
+}
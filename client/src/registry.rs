@@ -0,0 +1,117 @@
+//! Readers of the on-chain registries listing the Metadata of a subject and the MetadataKeys
+//! of a namespace
+
+use std::collections::HashMap;
+
+use anchor_lang::prelude::{AccountDeserialize, AccountSerialize, Pubkey};
+use mythic_metadata::state::*;
+
+use crate::errors::*;
+use crate::pda::*;
+
+/// Reads the data of accounts, a stand-in for an RPC client
+pub trait AccountReader {
+    /// Returns None if the account doesn't exist
+    fn read(&self, address: &Pubkey) -> Result<Option<Vec<u8>>>;
+}
+
+/// Serves fixed account data by address
+#[derive(Default)]
+pub struct StaticAccounts {
+    pub accounts: HashMap<Pubkey, Vec<u8>>,
+}
+
+impl StaticAccounts {
+    pub fn insert(&mut self, address: Pubkey, account: &impl AccountSerialize) {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        self.accounts.insert(address, data);
+    }
+}
+
+impl AccountReader for StaticAccounts {
+    fn read(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        Ok(self.accounts.get(address).cloned())
+    }
+}
+
+pub(crate) fn read_account<T: AccountDeserialize>(
+    reader: &impl AccountReader,
+    address: &Pubkey,
+) -> Result<Option<T>> {
+    reader
+        .read(address)?
+        .map(|data| Ok(T::try_deserialize(&mut data.as_slice())?))
+        .transpose()
+}
+
+/// Walks the registry pages of the subject and returns the seeds of its Metadata accounts
+/// in registration order
+pub fn subject_metadata(
+    reader: &impl AccountReader,
+    subject: &Pubkey,
+) -> Result<Vec<MetadataSeeds>> {
+    let (subject_registry, _) = find_subject_registry_address(subject);
+    let Some(subject_registry) = read_account::<SubjectRegistry>(reader, &subject_registry)? else {
+        return Ok(vec![]);
+    };
+
+    let mut seeds = vec![];
+    for page_index in 0..subject_registry.page_count() {
+        let (page, _) = find_subject_registry_page_address(subject, page_index);
+        let page = read_account::<SubjectRegistryPage>(reader, &page)?
+            .ok_or(MythicMetadataClientError::AccountNotFound(page))?;
+        seeds.extend(page.entries.iter().map(|entry| MetadataSeeds {
+            metadata_key_id: entry.metadata_key_id,
+            issuing_authority: entry.issuing_authority,
+            subject: *subject,
+        }));
+    }
+
+    Ok(seeds)
+}
+
+/// Returns the index of the registry page the next Metadata about the subject is listed in
+pub fn next_subject_registry_page_index(
+    reader: &impl AccountReader,
+    subject: &Pubkey,
+) -> Result<u32> {
+    let (subject_registry, _) = find_subject_registry_address(subject);
+    Ok(read_account::<SubjectRegistry>(reader, &subject_registry)?
+        .map_or(0, |subject_registry| subject_registry.next_page_index()))
+}
+
+/// Walks the registry pages of the namespace and returns its MetadataKeys in creation order
+pub fn namespace_keys(
+    reader: &impl AccountReader,
+    namespace_authority: &Pubkey,
+) -> Result<Vec<NamespaceRegistryEntry>> {
+    let (namespace_registry, _) = find_namespace_registry_address(namespace_authority);
+    let Some(namespace_registry) = read_account::<NamespaceRegistry>(reader, &namespace_registry)?
+    else {
+        return Ok(vec![]);
+    };
+
+    let mut keys = vec![];
+    for page_index in 0..namespace_registry.page_count() {
+        let (page, _) = find_namespace_registry_page_address(namespace_authority, page_index);
+        let page = read_account::<NamespaceRegistryPage>(reader, &page)?
+            .ok_or(MythicMetadataClientError::AccountNotFound(page))?;
+        keys.extend(page.entries);
+    }
+
+    Ok(keys)
+}
+
+/// Returns the MetadataKeys of the namespace with the content type, sorted by name for a key picker
+pub fn namespace_keys_of_type(
+    reader: &impl AccountReader,
+    namespace_authority: &Pubkey,
+    content_type: &str,
+) -> Result<Vec<NamespaceRegistryEntry>> {
+    let mut keys = namespace_keys(reader, namespace_authority)?;
+    keys.retain(|key| key.content_type == content_type);
+    keys.sort_by(|key, other| key.name.cmp(&other.name));
+
+    Ok(keys)
+}
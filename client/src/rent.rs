@@ -0,0 +1,49 @@
+//! Rent payers which receive the refunds of removed Metadata data
+
+use anchor_lang::prelude::Pubkey;
+use mythic_metadata::state::*;
+
+use crate::errors::*;
+
+/// Returns the distinct rent payers of the shares in the order they're first seen
+fn distinct_rent_payers(rent_shares: impl Iterator<Item = RentShare>) -> Vec<Pubkey> {
+    let mut rent_payers: Vec<Pubkey> = vec![];
+    for rent_share in rent_shares {
+        if !rent_payers.contains(&rent_share.rent_payer) {
+            rent_payers.push(rent_share.rent_payer);
+        }
+    }
+    rent_payers
+}
+
+/// Returns the rent payers of the item to pass to [crate::instructions::remove_metadata_item]
+/// and to the instructions which may shrink it, see [crate::instructions::with_rent_payers]
+pub fn item_rent_payers(item: &MetadataItem) -> Vec<Pubkey> {
+    distinct_rent_payers(item.rent_shares())
+}
+
+/// Returns the rent payers of the collection, its nested collections and their items
+/// to pass to [crate::instructions::remove_metadata_collection]
+pub fn collection_rent_payers(metadata: &Metadata, collection_path: &[u64]) -> Result<Vec<Pubkey>> {
+    let collection_index = metadata.collection_index(collection_path)?;
+
+    Ok(distinct_rent_payers(
+        metadata.collections[collection_index..]
+            .iter()
+            .take_while(|collection| {
+                collection.path().eq(collection_path.iter())
+                    || collection.is_descendant_of(collection_path)
+            })
+            .flat_map(MetadataCollection::rent_shares),
+    ))
+}
+
+/// Returns the rent payers of the items and collections, other than the issuing authority,
+/// to pass to [crate::instructions::close_metadata]
+pub fn metadata_rent_payers(metadata: &Metadata) -> Vec<Pubkey> {
+    distinct_rent_payers(
+        metadata
+            .rent_shares()
+            .filter(|rent_share| rent_share.rent_payer != metadata.issuing_authority),
+    )
+}
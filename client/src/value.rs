@@ -0,0 +1,221 @@
+//! Conversion between raw MetadataItem values and JSON values based on MetadataKey.content_type
+//!
+//! Supported content types:
+//! - `string`, `url`: UTF-8 text as a JSON string
+//! - `json`: UTF-8 JSON document embedded as is
+//! - `pubkey`: 32 bytes as a base58 JSON string
+//! - `bool`: a single 0 or 1 byte as a JSON bool
+//! - `u64`, `i64`: 8 little endian bytes as a JSON number
+//! - `external-reference`: a borsh ExternalReference as a JSON object with the uri,
+//!   the hash_algorithm (`sha256`, `keccak256` or `blake3`), the hex digest and the optional size
+//! - `evm-address`: a borsh EvmAddressLink as a JSON object with the `0x` prefixed address,
+//!   the hex signature and the recovery_id
+//!
+//! Values of any other content type are represented as a lowercase hex JSON string
+//!
+//! The values of items of multi-valued MetadataKeys are represented as a JSON array
+
+use std::str::FromStr;
+
+use anchor_lang::prelude::{AnchorSerialize, Pubkey};
+use mythic_metadata::constants::{
+    EVM_ADDRESS_CONTENT_TYPE, EXTERNAL_REFERENCE_CONTENT_TYPE, MAX_LIST_LEN,
+};
+use mythic_metadata::state::{
+    decode_list, EvmAddressLink, ExternalReference, HashAlgorithm, MetadataKey,
+};
+use serde_json::{json, Value};
+
+use crate::errors::*;
+
+fn invalid_value(content_type: &str, reason: impl ToString) -> MythicMetadataClientError {
+    MythicMetadataClientError::InvalidValue {
+        content_type: content_type.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Decodes the value of an item of the MetadataKey
+pub fn decode_item_value(metadata_key: &MetadataKey, value: &[u8]) -> Result<Value> {
+    if metadata_key.multi_valued {
+        decode_list(value)
+            .map_err(|error| invalid_value(&metadata_key.content_type, error))?
+            .iter()
+            .map(|value| decode_value(&metadata_key.content_type, value))
+            .collect()
+    } else {
+        decode_value(&metadata_key.content_type, value)
+    }
+}
+
+/// Encodes the value of an item of the MetadataKey
+pub fn encode_item_value(metadata_key: &MetadataKey, value: &Value) -> Result<Vec<u8>> {
+    match (metadata_key.multi_valued, value) {
+        (true, Value::Array(values)) if values.len() <= MAX_LIST_LEN => Ok(values
+            .iter()
+            .map(|value| encode_value(&metadata_key.content_type, value))
+            .collect::<Result<Vec<_>>>()?
+            .try_to_vec()
+            .unwrap()),
+        (true, value) => Err(invalid_value(
+            &metadata_key.content_type,
+            format!(
+                "expected a list of at most {} values, got {}",
+                MAX_LIST_LEN, value
+            ),
+        )),
+        (false, value) => encode_value(&metadata_key.content_type, value),
+    }
+}
+
+pub fn decode_value(content_type: &str, value: &[u8]) -> Result<Value> {
+    match content_type {
+        "string" | "url" => String::from_utf8(value.to_vec())
+            .map(Value::String)
+            .map_err(|error| invalid_value(content_type, error)),
+        "json" => serde_json::from_slice(value).map_err(|error| invalid_value(content_type, error)),
+        "pubkey" => <[u8; 32]>::try_from(value)
+            .map(|bytes| Value::String(Pubkey::new_from_array(bytes).to_string()))
+            .map_err(|error| invalid_value(content_type, error)),
+        "bool" => match value {
+            [0] => Ok(Value::Bool(false)),
+            [1] => Ok(Value::Bool(true)),
+            _ => Err(invalid_value(content_type, "expected a single 0 or 1 byte")),
+        },
+        "u64" => <[u8; 8]>::try_from(value)
+            .map(|bytes| Value::from(u64::from_le_bytes(bytes)))
+            .map_err(|error| invalid_value(content_type, error)),
+        "i64" => <[u8; 8]>::try_from(value)
+            .map(|bytes| Value::from(i64::from_le_bytes(bytes)))
+            .map_err(|error| invalid_value(content_type, error)),
+        EXTERNAL_REFERENCE_CONTENT_TYPE => ExternalReference::from_value(value)
+            .map(|external_reference| decode_external_reference(&external_reference))
+            .map_err(|error| invalid_value(content_type, error)),
+        EVM_ADDRESS_CONTENT_TYPE => EvmAddressLink::from_value(value)
+            .map(|link| decode_evm_address_link(&link))
+            .map_err(|error| invalid_value(content_type, error)),
+        _ => Ok(Value::String(encode_hex(value))),
+    }
+}
+
+pub fn encode_value(content_type: &str, value: &Value) -> Result<Vec<u8>> {
+    match (content_type, value) {
+        ("string" | "url", Value::String(value)) => Ok(value.as_bytes().to_vec()),
+        ("json", value) => Ok(serde_json::to_vec(value)?),
+        ("pubkey", Value::String(value)) => Pubkey::from_str(value)
+            .map(|pubkey| pubkey.to_bytes().to_vec())
+            .map_err(|error| invalid_value(content_type, error)),
+        ("bool", Value::Bool(value)) => Ok(vec![u8::from(*value)]),
+        ("u64", Value::Number(value)) => value
+            .as_u64()
+            .map(|value| value.to_le_bytes().to_vec())
+            .ok_or_else(|| invalid_value(content_type, format!("{} is not a u64", value))),
+        ("i64", Value::Number(value)) => value
+            .as_i64()
+            .map(|value| value.to_le_bytes().to_vec())
+            .ok_or_else(|| invalid_value(content_type, format!("{} is not an i64", value))),
+        (EXTERNAL_REFERENCE_CONTENT_TYPE, value) => encode_external_reference(value)
+            .ok_or_else(|| invalid_value(content_type, format!("unexpected JSON value {}", value)))
+            .and_then(|external_reference| {
+                external_reference
+                    .validate()
+                    .map_err(|error| invalid_value(content_type, error))?;
+                Ok(external_reference.try_to_vec().unwrap())
+            }),
+        (EVM_ADDRESS_CONTENT_TYPE, value) => encode_evm_address_link(value)
+            .map(|link| link.try_to_vec().unwrap())
+            .ok_or_else(|| invalid_value(content_type, format!("unexpected JSON value {}", value))),
+        ("string" | "url" | "pubkey" | "bool" | "u64" | "i64", value) => Err(invalid_value(
+            content_type,
+            format!("unexpected JSON value {}", value),
+        )),
+        (_, Value::String(value)) => {
+            decode_hex(value).ok_or_else(|| invalid_value(content_type, "expected a hex string"))
+        }
+        (_, value) => Err(invalid_value(
+            content_type,
+            format!("expected a hex string, found {}", value),
+        )),
+    }
+}
+
+fn hash_algorithm_name(hash_algorithm: HashAlgorithm) -> &'static str {
+    match hash_algorithm {
+        HashAlgorithm::Sha256 => "sha256",
+        HashAlgorithm::Keccak256 => "keccak256",
+        HashAlgorithm::Blake3 => "blake3",
+    }
+}
+
+fn decode_external_reference(external_reference: &ExternalReference) -> Value {
+    let mut value = json!({
+        "uri": external_reference.uri,
+        "hash_algorithm": hash_algorithm_name(external_reference.hash_algorithm),
+        "digest": encode_hex(&external_reference.digest),
+    });
+    if let Some(size) = external_reference.size {
+        value["size"] = Value::from(size);
+    }
+    value
+}
+
+fn encode_external_reference(value: &Value) -> Option<ExternalReference> {
+    let hash_algorithm = [
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Keccak256,
+        HashAlgorithm::Blake3,
+    ]
+    .into_iter()
+    .find(|hash_algorithm| value["hash_algorithm"] == hash_algorithm_name(*hash_algorithm))?;
+
+    let size = match &value["size"] {
+        Value::Null => None,
+        size => Some(size.as_u64()?),
+    };
+
+    Some(ExternalReference {
+        uri: value["uri"].as_str()?.to_string(),
+        hash_algorithm,
+        digest: decode_hex(value["digest"].as_str()?)?.try_into().ok()?,
+        size,
+    })
+}
+
+fn decode_evm_address_link(link: &EvmAddressLink) -> Value {
+    json!({
+        "address": format!("0x{}", encode_hex(&link.address)),
+        "signature": encode_hex(&link.signature),
+        "recovery_id": link.recovery_id,
+    })
+}
+
+fn encode_evm_address_link(value: &Value) -> Option<EvmAddressLink> {
+    let address = value["address"].as_str()?;
+    let address = address.strip_prefix("0x").unwrap_or(address);
+
+    Some(EvmAddressLink {
+        address: decode_hex(address)?.try_into().ok()?,
+        signature: decode_hex(value["signature"].as_str()?)?.try_into().ok()?,
+        recovery_id: value["recovery_id"].as_u64()?.try_into().ok()?,
+    })
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{:02x}", byte);
+        hex
+    })
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).ok())
+        .collect()
+}
@@ -0,0 +1,152 @@
+//! Building the ed25519 instructions and the instructions posting off-chain signed messages
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::Discriminator;
+use mythic_metadata::instruction as ix;
+use mythic_metadata::signature::Ed25519Signature;
+use mythic_metadata::state::{ATTESTATION_DOMAIN, ITEM_UPDATE_DOMAIN};
+use mythic_metadata_client::instructions::*;
+use mythic_metadata_client::*;
+
+#[test]
+fn attestations_are_posted_after_their_signature() {
+    let seeds = MetadataSeeds {
+        metadata_key_id: 1,
+        issuing_authority: Pubkey::new_unique(),
+        subject: Pubkey::new_unique(),
+    };
+    let attestation = attestation(&seeds, &[2, 3], 4, b"verified".to_vec(), 1, 1_000);
+    let message = attestation.message();
+
+    let [verify, attest] =
+        attest_metadata_item(&Pubkey::new_unique(), &seeds, attestation, &[7; 64]);
+
+    assert_eq!(verify.program_id, ed25519_program::ID);
+    assert_eq!(
+        Ed25519Signature::parse(&verify.data).unwrap(),
+        Ed25519Signature {
+            signer: seeds.issuing_authority,
+            message,
+        }
+    );
+
+    assert_eq!(attest.data[..8], ix::AttestMetadataItem::DISCRIMINATOR);
+    // The issuing authority doesn't sign, only the payer does
+    assert_eq!(
+        attest
+            .accounts
+            .iter()
+            .filter(|account| account.is_signer)
+            .count(),
+        1
+    );
+    assert!(attest.accounts.iter().any(|account| account.pubkey
+        == find_signer_nonce_address(
+            ATTESTATION_DOMAIN,
+            &seeds.address(),
+            &seeds.issuing_authority
+        )
+        .0));
+    // Relayed updates signed by the same authority don't consume the attestation nonces
+    assert_ne!(
+        find_signer_nonce_address(
+            ATTESTATION_DOMAIN,
+            &seeds.address(),
+            &seeds.issuing_authority
+        ),
+        find_signer_nonce_address(
+            ITEM_UPDATE_DOMAIN,
+            &seeds.address(),
+            &seeds.issuing_authority
+        )
+    );
+}
+
+#[test]
+fn relayed_updates_are_paid_by_the_relayer() {
+    let seeds = MetadataSeeds {
+        metadata_key_id: 1,
+        issuing_authority: Pubkey::new_unique(),
+        subject: Pubkey::new_unique(),
+    };
+    let relayer = Pubkey::new_unique();
+    let update_authority = Pubkey::new_unique();
+    let update = signed_item_update(
+        &seeds,
+        &update_authority,
+        &[1],
+        4,
+        b"updated".to_vec(),
+        7,
+        1_000,
+    );
+    let message = update.message();
+
+    let [verify, relay] =
+        relay_update_metadata_item(&relayer, &seeds, update, &[7; 64], Some(&[1]));
+
+    assert_eq!(
+        Ed25519Signature::parse(&verify.data).unwrap(),
+        Ed25519Signature {
+            signer: update_authority,
+            message,
+        }
+    );
+    assert_eq!(relay.data[..8], ix::RelayUpdateMetadataItem::DISCRIMINATOR);
+
+    let signers = relay
+        .accounts
+        .iter()
+        .filter(|account| account.is_signer)
+        .map(|account| account.pubkey)
+        .collect::<Vec<_>>();
+    assert_eq!(signers, vec![relayer]);
+    assert!(relay.accounts.iter().any(|account| account.pubkey
+        == find_metadata_delegate_address(&seeds.address(), &[1], &update_authority).0));
+}
+
+#[test]
+fn multisig_relayed_updates_carry_every_signature() {
+    let seeds = MetadataSeeds {
+        metadata_key_id: 1,
+        issuing_authority: Pubkey::new_unique(),
+        subject: Pubkey::new_unique(),
+    };
+    let (multisig_authority, _) = find_multisig_authority_address(&Pubkey::new_unique());
+    let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let update = signed_item_update(
+        &seeds,
+        &multisig_authority,
+        &[1],
+        4,
+        b"updated".to_vec(),
+        7,
+        1_000,
+    );
+    let message = update.message();
+
+    let [verify, relay] = relay_multisig_update_metadata_item(
+        &Pubkey::new_unique(),
+        &seeds,
+        update,
+        &[(signers[0], [7; 64]), (signers[1], [8; 64])],
+    );
+
+    assert_eq!(
+        Ed25519Signature::parse_all(&verify.data).unwrap(),
+        signers
+            .iter()
+            .map(|signer| Ed25519Signature {
+                signer: *signer,
+                message: message.clone(),
+            })
+            .collect::<Vec<_>>()
+    );
+    assert!(relay
+        .accounts
+        .iter()
+        .any(|account| account.pubkey == multisig_authority));
+    assert!(relay.accounts.iter().any(|account| account.pubkey
+        == find_signer_nonce_address(ITEM_UPDATE_DOMAIN, &seeds.address(), &multisig_authority).0));
+}
@@ -0,0 +1,93 @@
+//! Threshold checks of item endorsements against a trust list
+
+use anchor_lang::prelude::Pubkey;
+use mythic_metadata::state::*;
+use mythic_metadata_client::*;
+
+const ROOT: u64 = 1;
+const NAME: u64 = 2;
+
+fn metadata(seeds: &MetadataSeeds, value: &[u8], update_slot: u64) -> Metadata {
+    Metadata {
+        subject: seeds.subject,
+        metadata_key_id: seeds.metadata_key_id,
+        issuing_authority: seeds.issuing_authority,
+        update_slot,
+        update_authority: None,
+        items: vec![MetadataItem {
+            metadata_key_id: NAME,
+            update_slot,
+            value: value.to_vec(),
+            rent_payer: Pubkey::default(),
+            growth_rent_shares: vec![],
+        }],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    }
+}
+
+/// Stores endorsements of the root NAME item like endorse_metadata_item does
+fn endorsements(
+    seeds: &MetadataSeeds,
+    metadata: &Metadata,
+    endorsers: &[Pubkey],
+) -> StaticAccounts {
+    let item = metadata.item(&[ROOT], NAME).unwrap();
+    let mut accounts = StaticAccounts::default();
+    for endorser in endorsers {
+        accounts.insert(
+            find_endorsement_address(&seeds.address(), &[ROOT], NAME, endorser).0,
+            &Endorsement {
+                metadata: seeds.address(),
+                endorser: *endorser,
+                collection_path: vec![ROOT],
+                item_metadata_key_id: NAME,
+                value_hash: Endorsement::value_hash(&item.value),
+                item_update_slot: item.update_slot,
+                bump: 255,
+            },
+        );
+    }
+    accounts
+}
+
+#[test]
+fn endorsements_are_counted_against_the_trust_list() {
+    let seeds = MetadataSeeds {
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_unique(),
+        subject: Pubkey::new_unique(),
+    };
+    let trust_list = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let outsider = Pubkey::new_unique();
+
+    let endorsed = metadata(&seeds, b"Mythic", 1);
+    let accounts = endorsements(&seeds, &endorsed, &[trust_list[0], trust_list[2], outsider]);
+
+    assert_eq!(
+        item_endorsers(&accounts, &seeds, &endorsed, &[ROOT], NAME, &trust_list).unwrap(),
+        vec![trust_list[0], trust_list[2]]
+    );
+    assert!(is_endorsed(&accounts, &seeds, &endorsed, &[ROOT], NAME, &trust_list, 2).unwrap());
+    assert!(!is_endorsed(&accounts, &seeds, &endorsed, &[ROOT], NAME, &trust_list, 3).unwrap());
+
+    // Listing an endorser twice doesn't count it twice
+    let repeated = [trust_list[0], trust_list[0]];
+    assert!(!is_endorsed(&accounts, &seeds, &endorsed, &[ROOT], NAME, &repeated, 2).unwrap());
+
+    // The update invalidates the endorsements
+    let updated = metadata(&seeds, b"Mythic", 2);
+    assert!(
+        item_endorsers(&accounts, &seeds, &updated, &[ROOT], NAME, &trust_list)
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -0,0 +1,249 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use mythic_metadata::state::*;
+use mythic_metadata_client::*;
+use serde_json::json;
+
+const ROOT: u64 = 1;
+const SOCIALS: u64 = 2;
+const NAME: u64 = 10;
+const TREASURY: u64 = 11;
+const TWITTER: u64 = 12;
+const MEMBERS: u64 = 13;
+const ACCOUNTS: u64 = 14;
+const HANDLE: u64 = 15;
+const LINKS: u64 = 16;
+
+fn metadata_key(id: u64, name: &str, content_type: &str) -> MetadataKey {
+    MetadataKey {
+        id,
+        namespace_authority: Pubkey::default(),
+        name: name.to_string(),
+        label: name.to_string(),
+        description: String::new(),
+        content_type: content_type.to_string(),
+        bump: 255,
+        multi_valued: false,
+    }
+}
+
+fn keys() -> MetadataKeys {
+    MetadataKeys::new([
+        metadata_key(ROOT, "dao", "metadata-root-collection"),
+        metadata_key(SOCIALS, "socials", "metadata-collection"),
+        metadata_key(NAME, "name", "string"),
+        metadata_key(TREASURY, "treasury", "pubkey"),
+        metadata_key(TWITTER, "twitter", "url"),
+        metadata_key(MEMBERS, "members", "u64"),
+        metadata_key(ACCOUNTS, "accounts", "metadata-collection"),
+        metadata_key(HANDLE, "handle", "string"),
+        MetadataKey {
+            multi_valued: true,
+            ..metadata_key(LINKS, "links", "url")
+        },
+    ])
+}
+
+fn document() -> serde_json::Value {
+    json!({
+        "subject": Pubkey::new_from_array([1; 32]).to_string(),
+        "metadata_key": "dao",
+        "issuing_authority": Pubkey::new_from_array([2; 32]).to_string(),
+        "update_authority": Pubkey::new_from_array([3; 32]).to_string(),
+        "items": {
+            "name": "Mythic DAO",
+            "treasury": Pubkey::new_from_array([4; 32]).to_string(),
+            "members": 42,
+        },
+        "collections": {
+            "socials": {
+                "update_authority": null,
+                "items": {
+                    "twitter": "https://twitter.com/mythic",
+                },
+            },
+        },
+    })
+}
+
+#[test]
+fn import_export_round_trip() {
+    let keys = keys();
+    let metadata = import_metadata_json(&document().to_string(), &keys).unwrap();
+
+    assert_eq!(metadata.metadata_key_id, ROOT);
+    assert_eq!(
+        metadata
+            .items
+            .iter()
+            .map(|item| item.metadata_key_id)
+            .collect::<Vec<_>>(),
+        vec![NAME, TREASURY, MEMBERS]
+    );
+    assert_eq!(metadata.items[0].value, b"Mythic DAO");
+    assert_eq!(metadata.items[2].value, 42u64.to_le_bytes());
+    assert_eq!(metadata.collections[0].metadata_key_id, SOCIALS);
+
+    let exported: serde_json::Value =
+        serde_json::from_str(&export_metadata_json(&metadata, &keys).unwrap()).unwrap();
+    assert_eq!(exported, document());
+}
+
+#[test]
+fn nested_collections_round_trip() {
+    let keys = keys();
+    let mut document = document();
+    document["collections"]["socials"]["collections"] = json!({
+        "accounts": {
+            "update_authority": Pubkey::new_from_array([5; 32]).to_string(),
+            "items": { "handle": "@mythic" },
+            "collections": {
+                "socials": { "update_authority": null, "items": { "handle": "@nested" } },
+            },
+        },
+    });
+
+    let metadata = import_metadata_json(&document.to_string(), &keys).unwrap();
+    assert_eq!(
+        metadata
+            .collections
+            .iter()
+            .map(|collection| collection.path().copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>(),
+        vec![
+            vec![SOCIALS],
+            vec![SOCIALS, ACCOUNTS],
+            vec![SOCIALS, ACCOUNTS, SOCIALS]
+        ]
+    );
+    assert_eq!(metadata.collections[1].items[0].metadata_key_id, HANDLE);
+
+    let exported: serde_json::Value =
+        serde_json::from_str(&export_metadata_json(&metadata, &keys).unwrap()).unwrap();
+    assert_eq!(exported, document);
+}
+
+#[test]
+fn multi_valued_items_are_json_arrays() {
+    let keys = keys();
+    let mut document = document();
+    document["items"]["links"] = json!(["https://mythic.dev", "https://github.com/mythic"]);
+
+    let metadata = import_metadata_json(&document.to_string(), &keys).unwrap();
+    let links = metadata
+        .items
+        .iter()
+        .find(|item| item.metadata_key_id == LINKS)
+        .unwrap();
+    assert_eq!(
+        decode_list(&links.value).unwrap(),
+        vec![
+            b"https://mythic.dev".to_vec(),
+            b"https://github.com/mythic".to_vec()
+        ]
+    );
+
+    let exported: serde_json::Value =
+        serde_json::from_str(&export_metadata_json(&metadata, &keys).unwrap()).unwrap();
+    assert_eq!(exported, document);
+
+    document["items"]["links"] = json!("https://mythic.dev");
+    assert!(matches!(
+        import_metadata_json(&document.to_string(), &keys),
+        Err(MythicMetadataClientError::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn import_rejects_unknown_and_invalid_values() {
+    let keys = keys();
+
+    let mut unknown = document();
+    unknown["items"]["website"] = json!("https://mythic.dev");
+    assert!(matches!(
+        import_metadata_json(&unknown.to_string(), &keys),
+        Err(MythicMetadataClientError::MetadataKeyNameNotFound(_))
+    ));
+
+    let mut invalid = document();
+    invalid["items"]["members"] = json!(-1);
+    assert!(matches!(
+        import_metadata_json(&invalid.to_string(), &keys),
+        Err(MythicMetadataClientError::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn plan_import_appends_and_updates_only_differences() {
+    let keys = keys();
+    let desired = import_metadata_json(&document().to_string(), &keys).unwrap();
+    let payer = Pubkey::new_unique();
+
+    let instructions = plan_import(&payer, &desired, None, 0).unwrap();
+    let discriminators =
+        |instructions: &[anchor_lang::solana_program::instruction::Instruction]| {
+            instructions
+                .iter()
+                .map(|instruction| instruction.data[..8].to_vec())
+                .collect::<Vec<_>>()
+        };
+    assert_eq!(
+        discriminators(&instructions),
+        vec![
+            mythic_metadata::instruction::CreateMetadata::DISCRIMINATOR.to_vec(),
+            mythic_metadata::instruction::AppendMetadataItems::DISCRIMINATOR.to_vec(),
+            mythic_metadata::instruction::AppendMetadataCollection::DISCRIMINATOR.to_vec(),
+            mythic_metadata::instruction::AppendMetadataItems::DISCRIMINATOR.to_vec(),
+        ]
+    );
+    // Root items are appended in a single instruction with one MetadataKey per item
+    assert_eq!(instructions[1].accounts.len(), 7 + 3);
+
+    let mut current = desired.clone();
+    current.items[0].value = b"Old name".to_vec();
+    current.collections[0].items.clear();
+
+    let instructions = plan_import(&payer, &desired, Some(&current), 0).unwrap();
+    assert_eq!(
+        discriminators(&instructions),
+        vec![
+            mythic_metadata::instruction::UpdateMetadataItem::DISCRIMINATOR.to_vec(),
+            mythic_metadata::instruction::AppendMetadataItems::DISCRIMINATOR.to_vec(),
+        ]
+    );
+    assert_eq!(
+        instructions[0].accounts[0].pubkey,
+        desired.update_authority.unwrap()
+    );
+
+    assert!(plan_import(&payer, &desired, Some(&desired), 0)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn evm_address_json_round_trip() {
+    use mythic_metadata::constants::EVM_ADDRESS_CONTENT_TYPE;
+    use mythic_metadata_client::value::{decode_value, encode_value};
+
+    let evm_address_link = EvmAddressLink {
+        address: [0xab; 20],
+        signature: [7; 64],
+        recovery_id: 1,
+    };
+    let value = anchor_lang::AnchorSerialize::try_to_vec(&evm_address_link).unwrap();
+
+    let decoded = decode_value(EVM_ADDRESS_CONTENT_TYPE, &value).unwrap();
+    assert_eq!(decoded["address"], format!("0x{}", "ab".repeat(20)));
+    assert_eq!(decoded["recovery_id"], 1);
+    assert_eq!(
+        encode_value(EVM_ADDRESS_CONTENT_TYPE, &decoded).unwrap(),
+        value
+    );
+
+    assert!(encode_value(
+        EVM_ADDRESS_CONTENT_TYPE,
+        &json!({ "address": "0xab", "signature": "07", "recovery_id": 1 })
+    )
+    .is_err());
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::Pubkey;
+use mythic_metadata::constants::*;
+use mythic_metadata::state::*;
+use mythic_metadata_client::*;
+
+fn metadata(items: Vec<MetadataItem>) -> Metadata {
+    Metadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: 1,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: None,
+        items,
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    }
+}
+
+fn item(metadata_key_id: u64, value: &str) -> MetadataItem {
+    MetadataItem {
+        metadata_key_id,
+        update_slot: 0,
+        value: value.as_bytes().to_vec(),
+        rent_payer: Pubkey::default(),
+        growth_rent_shares: vec![],
+    }
+}
+
+#[test]
+fn read_metaplex_metadata_returns_imported_fields() {
+    let metadata = metadata(vec![
+        item(2, "other"),
+        item(METAPLEX_URI_KEY_ID, "https://example.com/1.json"),
+        item(METAPLEX_SYMBOL_KEY_ID, "MYTH"),
+        item(METAPLEX_NAME_KEY_ID, "Mythic #1"),
+    ]);
+
+    assert_eq!(
+        read_metaplex_metadata(&metadata).unwrap(),
+        Some(MetaplexMetadata {
+            update_authority: metadata.issuing_authority,
+            mint: metadata.subject,
+            name: "Mythic #1".to_string(),
+            symbol: "MYTH".to_string(),
+            uri: "https://example.com/1.json".to_string(),
+        })
+    );
+}
+
+#[test]
+fn read_metaplex_metadata_requires_all_fields() {
+    let metadata = metadata(vec![item(METAPLEX_NAME_KEY_ID, "Mythic #1")]);
+
+    assert_eq!(read_metaplex_metadata(&metadata).unwrap(), None);
+}
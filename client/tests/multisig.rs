@@ -0,0 +1,88 @@
+//! Signing update instructions for a MultisigAuthority
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use mythic_metadata::token_metadata::Field;
+use mythic_metadata_client::instructions::*;
+use mythic_metadata_client::*;
+
+#[test]
+fn co_signers_follow_the_accounts() {
+    let seeds = MetadataSeeds {
+        metadata_key_id: 1,
+        issuing_authority: Pubkey::new_unique(),
+        subject: Pubkey::new_unique(),
+    };
+    let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let (multisig_authority, _) = find_multisig_authority_address(&Pubkey::new_unique());
+
+    let update = update_metadata_item(&members[0], &seeds, &[1], 2, b"Mythic".to_vec());
+    let accounts_len = update.accounts.len();
+    let instruction = with_multisig_authority(update, &multisig_authority, &members[1..]);
+
+    assert_eq!(instruction.accounts.len(), accounts_len + 1);
+    assert_eq!(
+        instruction.accounts[accounts_len - 2].pubkey,
+        multisig_authority
+    );
+    assert!(instruction.accounts[accounts_len].is_signer);
+    assert_eq!(instruction.accounts[accounts_len].pubkey, members[1]);
+
+    // The delegate record stays omitted
+    assert_eq!(
+        instruction.accounts[accounts_len - 3].pubkey,
+        mythic_metadata::id()
+    );
+}
+
+#[test]
+fn multisig_authorities_accept_transfers() {
+    let seeds = MetadataSeeds {
+        metadata_key_id: 1,
+        issuing_authority: Pubkey::new_unique(),
+        subject: Pubkey::new_unique(),
+    };
+    let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let (multisig_authority, _) = find_multisig_authority_address(&Pubkey::new_unique());
+
+    let accept = accept_update_authority_transfer(&members[0], &seeds, &[1], &Pubkey::new_unique());
+    let accounts_len = accept.accounts.len();
+    let instruction = with_multisig_authority(accept, &multisig_authority, &members[1..]);
+
+    assert_eq!(instruction.accounts[0].pubkey, members[0]);
+    assert_eq!(
+        instruction.accounts[accounts_len - 1].pubkey,
+        multisig_authority
+    );
+    assert_eq!(instruction.accounts[accounts_len].pubkey, members[1]);
+    assert!(instruction.accounts[accounts_len].is_signer);
+}
+
+#[test]
+fn multisig_authorities_update_token_metadata() {
+    let mint = Pubkey::new_unique();
+    let (multisig_authority, _) = find_multisig_authority_address(&Pubkey::new_unique());
+    let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    let instruction = with_token_metadata_multisig_authority(
+        update_token_metadata_field(&mint, &multisig_authority, Field::Uri, "uri".to_string()),
+        &members,
+    );
+
+    // The MultisigAuthority doesn't sign, the first member pays for the growth
+    assert_eq!(
+        instruction.accounts[1],
+        AccountMeta::new_readonly(multisig_authority, false)
+    );
+    assert_eq!(instruction.accounts[2], AccountMeta::new(members[0], true));
+    assert_eq!(
+        instruction.accounts[4..],
+        members.map(|member| AccountMeta::new_readonly(member, true))
+    );
+
+    let instruction = with_token_metadata_multisig_authority(
+        remove_token_metadata_key(&mint, &multisig_authority, "key".to_string(), false, &[]),
+        &members,
+    );
+    assert_eq!(instruction.accounts.len(), 4);
+    assert!(!instruction.accounts[1].is_signer);
+}
@@ -0,0 +1,251 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::Discriminator;
+use mythic_metadata::instruction as ix;
+use mythic_metadata::state::*;
+use mythic_metadata_client::*;
+
+const ROOT: u64 = 1;
+const SOCIALS: u64 = 2;
+const TEAM: u64 = 3;
+const TWITTER: u64 = 4;
+
+fn item(metadata_key_id: u64, value: &[u8]) -> MetadataItem {
+    MetadataItem {
+        metadata_key_id,
+        update_slot: 0,
+        value: value.to_vec(),
+        rent_payer: Pubkey::default(),
+        growth_rent_shares: vec![],
+    }
+}
+
+fn collection(
+    metadata_key_id: u64,
+    update_authority: Option<Pubkey>,
+    items: Vec<MetadataItem>,
+) -> MetadataCollection {
+    MetadataCollection {
+        metadata_key_id,
+        parent_path: vec![],
+        update_slot: 0,
+        update_authority,
+        timelock_slots: 0,
+        frozen: false,
+        items,
+        rent_payer: Pubkey::default(),
+    }
+}
+
+fn nested(parent_path: &[u64], mut collection: MetadataCollection) -> MetadataCollection {
+    collection.parent_path = parent_path.to_vec();
+    collection
+}
+
+fn metadata(update_authority: Option<Pubkey>) -> Metadata {
+    Metadata {
+        subject: Pubkey::new_from_array([1; 32]),
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_from_array([2; 32]),
+        update_slot: 0,
+        update_authority,
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    }
+}
+
+fn discriminator(instruction: &Instruction) -> [u8; 8] {
+    instruction.data[..8].try_into().unwrap()
+}
+
+#[test]
+fn plan_sync_removes_appends_updates_and_transfers_authorities() {
+    let payer = Pubkey::new_unique();
+    let update_authority = Pubkey::new_unique();
+    let collection_update_authority = Pubkey::new_unique();
+    let new_update_authority = Pubkey::new_unique();
+
+    let mut current = metadata(Some(update_authority));
+    current.items = vec![item(10, b"name"), item(11, b"removed")];
+    current.collections = vec![
+        collection(
+            SOCIALS,
+            Some(collection_update_authority),
+            vec![item(20, b"old")],
+        ),
+        collection(TEAM, None, vec![item(30, b"member")]),
+    ];
+
+    let mut desired = metadata(Some(new_update_authority));
+    desired.items = vec![item(10, b"name"), item(12, b"appended")];
+    desired.collections = vec![collection(SOCIALS, None, vec![item(20, b"new")])];
+
+    let steps = plan_sync(&payer, &desired, Some(&current), 0).unwrap();
+    let issuing_authority = StepSigner::IssuingAuthority(current.issuing_authority);
+    assert_eq!(
+        steps
+            .iter()
+            .map(|step| (discriminator(&step.instruction), step.signer))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                ix::RemoveMetadataCollection::DISCRIMINATOR,
+                issuing_authority
+            ),
+            (ix::RemoveMetadataItem::DISCRIMINATOR, issuing_authority),
+            (ix::AppendMetadataItems::DISCRIMINATOR, issuing_authority),
+            (
+                ix::UpdateMetadataItem::DISCRIMINATOR,
+                StepSigner::UpdateAuthority(collection_update_authority)
+            ),
+            (
+                ix::RevokeCollectionUpdateAuthority::DISCRIMINATOR,
+                issuing_authority
+            ),
+            (
+                ix::ProposeUpdateAuthorityTransfer::DISCRIMINATOR,
+                issuing_authority
+            ),
+        ]
+    );
+
+    assert!(plan_sync(&payer, &desired, Some(&desired), 0)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn plan_sync_updates_with_the_issuing_authority_when_the_update_authority_is_revoked() {
+    let payer = Pubkey::new_unique();
+
+    let mut current = metadata(None);
+    current.items = vec![item(10, b"old")];
+    let mut desired = metadata(None);
+    desired.items = vec![item(10, b"new")];
+
+    let steps = plan_sync(&payer, &desired, Some(&current), 0).unwrap();
+    assert_eq!(steps.len(), 1);
+    assert_eq!(
+        steps[0].signer,
+        StepSigner::UpdateAuthority(current.issuing_authority)
+    );
+}
+
+#[test]
+fn plan_sync_rejects_changes_of_finalized_metadata() {
+    let payer = Pubkey::new_unique();
+
+    let mut current = metadata(Some(Pubkey::new_unique()));
+    current.items = vec![item(10, b"old")];
+    current.finalized_slot = Some(1);
+    let mut desired = current.clone();
+    desired.finalized_slot = None;
+
+    assert!(plan_sync(&payer, &desired, Some(&current), 0)
+        .unwrap()
+        .is_empty());
+
+    desired.items = vec![item(10, b"new")];
+    assert!(matches!(
+        plan_sync(&payer, &desired, Some(&current), 0),
+        Err(MythicMetadataClientError::ImmutableMetadata)
+    ));
+}
+
+#[test]
+fn steps_are_batched_into_transactions() {
+    let payer = Pubkey::new_unique();
+
+    let mut desired = metadata(Some(Pubkey::new_unique()));
+    desired.items = (0..40).map(|id| item(100 + id, &[id as u8; 100])).collect();
+
+    // A single transaction can't carry 4000 bytes of values
+    let steps = plan_sync(&payer, &desired, None, 0).unwrap();
+    assert!(steps.len() > 2);
+    for step in &steps {
+        assert!(transaction_size(&payer, &[step.instruction.clone()]) <= MAX_TRANSACTION_SIZE);
+    }
+
+    // Small updates are grouped together
+    let mut current = desired.clone();
+    for item in &mut current.items {
+        item.value = vec![];
+    }
+    let steps = plan_sync(&payer, &desired, Some(&current), 0).unwrap();
+    assert_eq!(steps.len(), 40);
+
+    let transactions = batch_steps(&payer, steps.clone()).unwrap();
+    assert!(transactions.len() > 1 && transactions.len() < steps.len());
+    assert_eq!(
+        transactions.iter().flatten().cloned().collect::<Vec<_>>(),
+        steps
+    );
+    for transaction in &transactions {
+        let instructions = transaction
+            .iter()
+            .map(|step| step.instruction.clone())
+            .collect::<Vec<_>>();
+        assert!(transaction_size(&payer, &instructions) <= MAX_TRANSACTION_SIZE);
+    }
+
+    desired.items = vec![item(100, &[0; 2000])];
+    assert!(matches!(
+        batch_steps(&payer, plan_sync(&payer, &desired, None, 0).unwrap()),
+        Err(MythicMetadataClientError::TransactionTooLarge(_))
+    ));
+}
+
+#[test]
+fn plan_sync_handles_nested_collections() {
+    let payer = Pubkey::new_unique();
+    let update_authority = Pubkey::new_unique();
+    let socials_update_authority = Pubkey::new_unique();
+
+    let mut current = metadata(Some(update_authority));
+    current.collections = vec![
+        collection(SOCIALS, Some(socials_update_authority), vec![]),
+        nested(
+            &[SOCIALS],
+            collection(TWITTER, None, vec![item(40, b"old")]),
+        ),
+        collection(TEAM, None, vec![]),
+        nested(&[TEAM], collection(TWITTER, None, vec![])),
+    ];
+
+    let mut desired = current.clone();
+    desired.collections[1].items = vec![item(40, b"new")];
+    desired.collections.truncate(2);
+    desired
+        .collections
+        .push(nested(&[SOCIALS, TWITTER], collection(TEAM, None, vec![])));
+
+    let steps = plan_sync(&payer, &desired, Some(&current), 0).unwrap();
+    let issuing_authority = StepSigner::IssuingAuthority(current.issuing_authority);
+    assert_eq!(
+        steps
+            .iter()
+            .map(|step| (discriminator(&step.instruction), step.signer))
+            .collect::<Vec<_>>(),
+        vec![
+            // The nested team/twitter collection is removed with its parent
+            (
+                ix::RemoveMetadataCollection::DISCRIMINATOR,
+                issuing_authority
+            ),
+            // The socials/twitter items inherit the socials update authority
+            (
+                ix::UpdateMetadataItem::DISCRIMINATOR,
+                StepSigner::UpdateAuthority(socials_update_authority)
+            ),
+            (
+                ix::AppendMetadataCollection::DISCRIMINATOR,
+                issuing_authority
+            ),
+        ]
+    );
+}
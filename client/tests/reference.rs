@@ -0,0 +1,98 @@
+//! External reference values in JSON and verification of the fetched content
+
+use mythic_metadata::constants::EXTERNAL_REFERENCE_CONTENT_TYPE;
+use mythic_metadata::state::*;
+use mythic_metadata_client::value::{decode_value, encode_value};
+use mythic_metadata_client::*;
+use serde_json::json;
+
+const URI: &str = "https://example.com/doc.json";
+const CONTENT: &[u8] = b"{\"name\":\"Mythic\"}";
+
+#[test]
+fn external_reference_json_round_trip() {
+    let external_reference =
+        ExternalReference::new(URI.to_string(), HashAlgorithm::Keccak256, CONTENT);
+    let value = encode_value(
+        EXTERNAL_REFERENCE_CONTENT_TYPE,
+        &decode_value(
+            EXTERNAL_REFERENCE_CONTENT_TYPE,
+            &anchor_lang::AnchorSerialize::try_to_vec(&external_reference).unwrap(),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        ExternalReference::from_value(&value).unwrap(),
+        external_reference
+    );
+
+    let decoded = decode_value(EXTERNAL_REFERENCE_CONTENT_TYPE, &value).unwrap();
+    assert_eq!(decoded["uri"], URI);
+    assert_eq!(decoded["hash_algorithm"], "keccak256");
+    assert_eq!(decoded["size"], CONTENT.len());
+
+    // The size is optional
+    let value = encode_value(
+        EXTERNAL_REFERENCE_CONTENT_TYPE,
+        &json!({"uri": URI, "hash_algorithm": "sha256", "digest": "00".repeat(32)}),
+    )
+    .unwrap();
+    assert_eq!(ExternalReference::from_value(&value).unwrap().size, None);
+
+    for invalid in [
+        json!({"uri": URI, "hash_algorithm": "md5", "digest": "00".repeat(32)}),
+        json!({"uri": URI, "hash_algorithm": "sha256", "digest": "00"}),
+        json!({"uri": "", "hash_algorithm": "sha256", "digest": "00".repeat(32)}),
+        json!(URI),
+    ] {
+        assert!(encode_value(EXTERNAL_REFERENCE_CONTENT_TYPE, &invalid).is_err());
+    }
+}
+
+#[test]
+fn fetched_content_is_verified() {
+    let external_reference =
+        ExternalReference::new(URI.to_string(), HashAlgorithm::Blake3, CONTENT);
+
+    let mut fetcher = StaticFetcher::default();
+    fetcher.insert(URI, CONTENT.to_vec());
+    assert_eq!(
+        fetch_and_verify(&external_reference, &fetcher).unwrap(),
+        CONTENT
+    );
+
+    fetcher.insert(URI, b"tampered".to_vec());
+    assert!(matches!(
+        fetch_and_verify(&external_reference, &fetcher),
+        Err(MythicMetadataClientError::ContentMismatch(uri)) if uri == URI
+    ));
+
+    assert!(matches!(
+        fetch_and_verify(&external_reference, &StaticFetcher::default()),
+        Err(MythicMetadataClientError::ContentFetchFailed { .. })
+    ));
+}
+
+#[test]
+fn file_content_is_verified() {
+    let root = std::env::temp_dir().join(format!("mythic-reference-{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("doc.json"), CONTENT).unwrap();
+
+    let fetcher = FileFetcher { root: root.clone() };
+    for uri in ["doc.json", "file://doc.json"] {
+        let external_reference =
+            ExternalReference::new(uri.to_string(), HashAlgorithm::Sha256, CONTENT);
+        assert_eq!(
+            fetch_and_verify(&external_reference, &fetcher).unwrap(),
+            CONTENT
+        );
+    }
+
+    let missing =
+        ExternalReference::new("missing.json".to_string(), HashAlgorithm::Sha256, CONTENT);
+    assert!(fetch_and_verify(&missing, &fetcher).is_err());
+
+    std::fs::remove_dir_all(root).unwrap();
+}
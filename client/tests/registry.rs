@@ -0,0 +1,205 @@
+//! Walking the subject and namespace registries
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use mythic_metadata::constants::{NAMESPACE_REGISTRY_PAGE_LEN, SUBJECT_REGISTRY_PAGE_LEN};
+use mythic_metadata::state::*;
+use mythic_metadata::CreateMetadataKeyArgs;
+use mythic_metadata_client::instructions::{create_metadata, create_metadata_key};
+use mythic_metadata_client::*;
+
+fn key(id: u64, content_type: &str) -> NamespaceRegistryEntry {
+    NamespaceRegistryEntry {
+        id,
+        name: format!("key-{id:03}"),
+        content_type: content_type.to_string(),
+    }
+}
+
+/// Lists the keys in pages like create_metadata_key does
+fn namespace(namespace_authority: Pubkey, keys: &[NamespaceRegistryEntry]) -> StaticAccounts {
+    let mut accounts = StaticAccounts::default();
+    accounts.insert(
+        find_namespace_registry_address(&namespace_authority).0,
+        &NamespaceRegistry {
+            namespace_authority,
+            keys_count: keys.len() as u64,
+            bump: 255,
+        },
+    );
+    for (page_index, entries) in keys.chunks(NAMESPACE_REGISTRY_PAGE_LEN).enumerate() {
+        accounts.insert(
+            find_namespace_registry_page_address(&namespace_authority, page_index as u32).0,
+            &NamespaceRegistryPage {
+                namespace_authority,
+                page_index: page_index as u32,
+                entries: entries.to_vec(),
+                bump: 255,
+            },
+        );
+    }
+    accounts
+}
+
+#[test]
+fn namespace_keys_are_walked_across_pages() {
+    let namespace_authority = Pubkey::new_unique();
+    let keys = (0..NAMESPACE_REGISTRY_PAGE_LEN as u64 + 3)
+        .map(|id| key(id, if id % 2 == 0 { "string" } else { "image" }))
+        .collect::<Vec<_>>();
+    let accounts = namespace(namespace_authority, &keys);
+
+    assert_eq!(
+        namespace_keys(&accounts, &namespace_authority).unwrap(),
+        keys
+    );
+
+    let images = namespace_keys_of_type(&accounts, &namespace_authority, "image").unwrap();
+    assert_eq!(images.len(), keys.len() / 2);
+    assert!(images.windows(2).all(|pair| pair[0].name < pair[1].name));
+
+    // A namespace without keys has no registry
+    assert!(namespace_keys(&accounts, &Pubkey::new_unique())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn keys_are_listed_only_with_a_page_index() {
+    let payer = Pubkey::new_unique();
+    let namespace_authority = Pubkey::new_unique();
+    let args = || CreateMetadataKeyArgs {
+        id: 1,
+        name: "handle".to_string(),
+        label: "Handle".to_string(),
+        description: "Handle".to_string(),
+        content_type: "string".to_string(),
+        multi_valued: false,
+    };
+
+    let instruction = create_metadata_key(&payer, &namespace_authority, Some(1), args());
+    assert_eq!(
+        instruction.accounts[4..],
+        [
+            AccountMeta::new(
+                find_namespace_registry_address(&namespace_authority).0,
+                false
+            ),
+            AccountMeta::new(
+                find_namespace_registry_page_address(&namespace_authority, 1).0,
+                false
+            ),
+        ]
+    );
+
+    // Omitted registry accounts are passed as the program id
+    let instruction = create_metadata_key(&payer, &namespace_authority, None, args());
+    assert_eq!(
+        instruction.accounts[4..],
+        vec![AccountMeta::new_readonly(mythic_metadata::ID, false); 2]
+    );
+}
+
+#[test]
+fn metadata_is_listed_only_with_a_page_index() {
+    let payer = Pubkey::new_unique();
+    let seeds = MetadataSeeds {
+        metadata_key_id: 1,
+        issuing_authority: Pubkey::new_unique(),
+        subject: Pubkey::new_unique(),
+    };
+
+    // The registry accounts follow the system program and the subject authority accounts
+    let instruction = create_metadata(&payer, &seeds, Some(2), None);
+    assert_eq!(
+        instruction.accounts[7..],
+        [
+            AccountMeta::new(find_subject_registry_address(&seeds.subject).0, false),
+            AccountMeta::new(
+                find_subject_registry_page_address(&seeds.subject, 2).0,
+                false
+            ),
+        ]
+    );
+
+    let instruction = create_metadata(&payer, &seeds, None, None);
+    assert_eq!(
+        instruction.accounts[4],
+        AccountMeta::new_readonly(anchor_lang::system_program::ID, false)
+    );
+    assert_eq!(
+        instruction.accounts[7..],
+        vec![AccountMeta::new_readonly(mythic_metadata::ID, false); 2]
+    );
+}
+
+#[test]
+fn missing_pages_are_reported() {
+    let namespace_authority = Pubkey::new_unique();
+    let keys = (0..NAMESPACE_REGISTRY_PAGE_LEN as u64 + 1)
+        .map(|id| key(id, "string"))
+        .collect::<Vec<_>>();
+    let mut accounts = namespace(namespace_authority, &keys);
+    let (last_page, _) = find_namespace_registry_page_address(&namespace_authority, 1);
+    accounts.accounts.remove(&last_page);
+
+    assert!(matches!(
+        namespace_keys(&accounts, &namespace_authority),
+        Err(MythicMetadataClientError::AccountNotFound(address)) if address == last_page
+    ));
+}
+
+#[test]
+fn subject_metadata_is_walked_across_pages() {
+    let subject = Pubkey::new_unique();
+    let issuing_authority = Pubkey::new_unique();
+    let seeds = (0..SUBJECT_REGISTRY_PAGE_LEN as u64 + 2)
+        .map(|metadata_key_id| MetadataSeeds {
+            metadata_key_id,
+            issuing_authority,
+            subject,
+        })
+        .collect::<Vec<_>>();
+
+    let mut accounts = StaticAccounts::default();
+    accounts.insert(
+        find_subject_registry_address(&subject).0,
+        &SubjectRegistry {
+            subject,
+            registered_count: seeds.len() as u64,
+            bump: 255,
+        },
+    );
+    for (page_index, page_seeds) in seeds.chunks(SUBJECT_REGISTRY_PAGE_LEN).enumerate() {
+        accounts.insert(
+            find_subject_registry_page_address(&subject, page_index as u32).0,
+            &SubjectRegistryPage {
+                subject,
+                page_index: page_index as u32,
+                entries: page_seeds
+                    .iter()
+                    .map(|seeds| SubjectRegistryEntry {
+                        metadata_key_id: seeds.metadata_key_id,
+                        issuing_authority,
+                    })
+                    .collect(),
+                bump: 255,
+            },
+        );
+    }
+
+    assert_eq!(subject_metadata(&accounts, &subject).unwrap(), seeds);
+    assert_eq!(
+        next_subject_registry_page_index(&accounts, &subject).unwrap(),
+        1
+    );
+
+    // A subject without Metadata has no registry
+    let other_subject = Pubkey::new_unique();
+    assert!(subject_metadata(&accounts, &other_subject)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        next_subject_registry_page_index(&accounts, &other_subject).unwrap(),
+        0
+    );
+}
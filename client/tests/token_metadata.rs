@@ -0,0 +1,86 @@
+//! Building the spl-token-metadata-interface instructions
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use mythic_metadata::constants::TOKEN_METADATA_KEY_ID;
+use mythic_metadata::token_metadata::*;
+use mythic_metadata_client::instructions::*;
+use mythic_metadata_client::pda::*;
+
+fn initialize() -> Initialize {
+    Initialize {
+        name: "Mythic".to_string(),
+        symbol: "MYTH".to_string(),
+        uri: "https://example.com/myth.json".to_string(),
+    }
+}
+
+#[test]
+fn initialize_takes_the_interface_accounts_without_a_payer() {
+    let mint = Pubkey::new_unique();
+    let mint_authority = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+
+    let instruction = initialize_token_metadata(None, &mint, &mint_authority, None, initialize());
+    assert_eq!(instruction.accounts.len(), 4);
+    assert_eq!(
+        instruction.accounts[3],
+        AccountMeta::new_readonly(mint_authority, true)
+    );
+
+    let instruction =
+        initialize_token_metadata(Some(payer), &mint, &mint_authority, None, initialize());
+    assert_eq!(instruction.accounts.len(), 6);
+    assert_eq!(instruction.accounts[4], AccountMeta::new(payer, true));
+}
+
+#[test]
+fn removed_keys_refund_their_rent_payers() {
+    let mint = Pubkey::new_unique();
+    let update_authority = Pubkey::new_unique();
+    let rent_payers = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    let instruction = remove_token_metadata_key(
+        &mint,
+        &update_authority,
+        "color".to_string(),
+        false,
+        &rent_payers,
+    );
+    assert_eq!(
+        instruction.accounts[2..],
+        rent_payers.map(|rent_payer| AccountMeta::new(rent_payer, false))
+    );
+}
+
+#[test]
+fn update_authority_is_proposed_unless_cleared() {
+    let mint = Pubkey::new_unique();
+    let update_authority = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let metadata = find_token_metadata_address(&mint).0;
+
+    // The new update authority accepts the AuthorityTransfer funded by the payer
+    let instruction =
+        update_token_metadata_authority(&payer, &mint, &update_authority, Some(new_authority));
+    assert_eq!(
+        instruction.accounts[2..4],
+        [
+            AccountMeta::new(
+                find_authority_transfer_address(&metadata, &[TOKEN_METADATA_KEY_ID]).0,
+                false
+            ),
+            AccountMeta::new(payer, true),
+        ]
+    );
+    assert_eq!(
+        TokenMetadataInstruction::unpack(&instruction.data)
+            .unwrap()
+            .unwrap(),
+        TokenMetadataInstruction::UpdateAuthority(UpdateAuthority { new_authority })
+    );
+
+    // Clearing the update authority makes the token Metadata immutable right away
+    let instruction = update_token_metadata_authority(&payer, &mint, &update_authority, None);
+    assert_eq!(instruction.accounts.len(), 2);
+}
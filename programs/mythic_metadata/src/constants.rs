@@ -1,7 +1,23 @@
+use anchor_lang::prelude::*;
+
 pub const PREFIX: &[u8] = b"mythic_metadata";
 pub const COUNTER: &[u8] = b"counter";
 pub const METADATA_KEY: &[u8] = b"metadata_key";
 pub const METADATA: &[u8] = b"metadata";
+pub const DELEGATE: &[u8] = b"delegate";
+pub const SUBJECT_REGISTRY: &[u8] = b"subject_registry";
+pub const NAMESPACE_REGISTRY: &[u8] = b"namespace_registry";
+pub const NONCE: &[u8] = b"nonce";
+pub const ENDORSEMENT: &[u8] = b"endorsement";
+pub const MULTISIG: &[u8] = b"multisig";
+pub const PENDING_CHANGE: &[u8] = b"pending_change";
+pub const AUTHORITY_TRANSFER: &[u8] = b"authority_transfer";
+
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// SPL Governance native treasury seed
+pub const NATIVE_TREASURY: &[u8] = b"native-treasury";
 
 pub const MAX_NAME_LEN: usize = 4 + 50;
 pub const MAX_LABEL_LEN: usize = 4 + 30;
@@ -10,3 +26,35 @@ pub const MAX_CONTENT_TYPE_LEN: usize = 4 + 50;
 pub const MAX_VALUE_LEN: usize = 10000;
 pub const MAX_COLLECTIONS_PER_METADATA: usize = 100;
 pub const MAX_ITEMS_PER_COLLECTION: usize = 100;
+/// The maximum number of collection levels, top level collections are at depth 1
+pub const MAX_COLLECTION_DEPTH: usize = 8;
+pub const MAX_URI_LEN: usize = 200;
+/// The maximum number of values of a multi-valued item
+pub const MAX_LIST_LEN: usize = 64;
+/// The number of Metadata accounts listed in a SubjectRegistryPage
+pub const SUBJECT_REGISTRY_PAGE_LEN: usize = 32;
+/// The number of MetadataKeys listed in a NamespaceRegistryPage
+pub const NAMESPACE_REGISTRY_PAGE_LEN: usize = 32;
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+/// MetadataKey content_type of items holding an ExternalReference
+pub const EXTERNAL_REFERENCE_CONTENT_TYPE: &str = "external-reference";
+
+/// MetadataKey content_type of items holding an EvmAddressLink, written only by link_evm_address
+pub const EVM_ADDRESS_CONTENT_TYPE: &str = "evm-address";
+
+/// MetadataKey Ids reserved for the Token-2022 metadata interface, MetadataKeys can't be created for them
+/// The Metadata of a mint uses TOKEN_METADATA_KEY_ID, the name, symbol and uri are its root items
+/// and the additional fields are items of the TOKEN_METADATA_FIELDS_KEY_ID collection
+pub const TOKEN_METADATA_KEY_ID: u64 = u64::MAX;
+pub const TOKEN_METADATA_NAME_KEY_ID: u64 = u64::MAX - 1;
+pub const TOKEN_METADATA_SYMBOL_KEY_ID: u64 = u64::MAX - 2;
+pub const TOKEN_METADATA_URI_KEY_ID: u64 = u64::MAX - 3;
+pub const TOKEN_METADATA_FIELDS_KEY_ID: u64 = u64::MAX - 4;
+
+/// Well-known MetadataKey Ids of the items imported from a Metaplex Metadata account
+pub const METAPLEX_NAME_KEY_ID: u64 = u64::MAX - 5;
+pub const METAPLEX_SYMBOL_KEY_ID: u64 = u64::MAX - 6;
+pub const METAPLEX_URI_KEY_ID: u64 = u64::MAX - 7;
+
+pub const MIN_RESERVED_METADATA_KEY_ID: u64 = METAPLEX_URI_KEY_ID;
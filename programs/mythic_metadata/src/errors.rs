@@ -25,5 +25,67 @@ pub enum MythicMetadataError {
     #[msg("Metadata item does not exist")]
     MetadataItemNonExistent,
     #[msg("Metadata item value len exceeded")]
-    MetadataItemValueLenExceeded
+    MetadataItemValueLenExceeded,
+    #[msg("Invalid delegate permissions")]
+    InvalidDelegatePermissions,
+    #[msg("Invalid governance account")]
+    InvalidGovernanceAccount,
+    #[msg("Invalid subject account")]
+    InvalidSubjectAccount,
+    #[msg("Invalid token metadata field")]
+    InvalidTokenMetadataField,
+    #[msg("Invalid Metaplex metadata")]
+    InvalidMetaplexMetadata,
+    #[msg("Invalid external reference")]
+    InvalidExternalReference,
+    #[msg("Metadata collection depth exceeded")]
+    MetadataCollectionDepthExceeded,
+    #[msg("MetadataKey is not multi-valued")]
+    MetadataKeyNotMultiValued,
+    #[msg("Invalid metadata list")]
+    InvalidMetadataList,
+    #[msg("Metadata list is full")]
+    MetadataListFull,
+    #[msg("Metadata list index out of bounds")]
+    MetadataListIndexOutOfBounds,
+    #[msg("Subject registry page of the Metadata is missing")]
+    SubjectRegistryPageMissing,
+    #[msg("Invalid signature instruction")]
+    InvalidSignatureInstruction,
+    #[msg("Signed message does not match the instruction")]
+    SignedMessageMismatch,
+    #[msg("Nonce already used")]
+    NonceAlreadyUsed,
+    #[msg("Signed message expired")]
+    SignedMessageExpired,
+    #[msg("Invalid EVM address link")]
+    InvalidEvmAddressLink,
+    #[msg("EVM addresses must be linked with link_evm_address")]
+    EvmAddressNotLinked,
+    #[msg("Endorsed value does not match the item")]
+    EndorsedValueMismatch,
+    #[msg("Invalid multisig authority")]
+    InvalidMultisigAuthority,
+    #[msg("Metadata collection is timelocked, queue the change")]
+    MetadataCollectionTimelocked,
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Timelock overflows the executable slot")]
+    TimelockOverflow,
+    #[msg("Metadata collection is frozen")]
+    MetadataCollectionFrozen,
+    #[msg("Authority changes must be proposed and accepted unless explicitly unsafe")]
+    UnsafeAuthorityChange,
+    #[msg("Metadata state hash does not match")]
+    MetadataStateHashMismatch,
+    #[msg("Metadata has open delegate records, pending changes or authority transfers")]
+    MetadataRecordsOpen,
+    #[msg("Account is already in the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Governance program is not the SPL Governance program")]
+    InvalidGovernanceProgram,
+    #[msg("Delegate record belongs to the realm's authority")]
+    GovernanceDelegateNotStale,
+    #[msg("Rent payer is required to grow the metadata")]
+    RentPayerRequired,
 }
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when a Metadata is finalized and becomes permanently immutable
+#[event]
+pub struct MetadataFinalized {
+    pub metadata: Pubkey,
+
+    /// The slot of the finalization, recorded in Metadata.finalized_slot
+    pub slot: u64,
+}
@@ -0,0 +1,152 @@
+//! Minimal readers for SPL Governance accounts
+//!
+//! Only the fields needed to verify a governance authority are read so the program doesn't
+//! depend on the spl-governance crate. Accounts are trusted only if owned by the SPL Governance
+//! program: anyone can deploy a program owning accounts in the realm layout, so realms of
+//! other deployments can't vouch for the subject of a governance Metadata
+
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+
+/// The SPL Governance program
+pub const SPL_GOVERNANCE_PROGRAM_ID: Pubkey =
+    pubkey!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+
+/// GovernanceAccountType discriminators of the realm accounts
+const REALM_ACCOUNT_TYPES: [u8; 2] = [
+    1,  // RealmV1
+    16, // RealmV2
+];
+
+/// GovernanceAccountType discriminators of the governance accounts
+const GOVERNANCE_ACCOUNT_TYPES: [u8; 8] = [
+    3,  // GovernanceV1
+    4,  // ProgramGovernanceV1
+    9,  // MintGovernanceV1
+    10, // TokenGovernanceV1
+    18, // GovernanceV2
+    19, // ProgramGovernanceV2
+    20, // MintGovernanceV2
+    21, // TokenGovernanceV2
+];
+
+/// Offset of RealmConfig.council_mint in a realm account
+/// account_type (1) + community_mint (32) + legacy1 (1) + legacy2 (1) + reserved (6)
+/// + min_community_weight_to_create_governance (8) + community_mint_max_voter_weight_source (9)
+const REALM_COUNCIL_MINT_OFFSET: usize = 58;
+
+/// Size of the Realm fields between RealmConfig and authority: reserved (6) + legacy1 (2)
+const REALM_AUTHORITY_GAP: usize = 8;
+
+fn account_type(account: &AccountInfo, governance_program: &Pubkey) -> Result<u8> {
+    require_keys_eq!(
+        *governance_program,
+        SPL_GOVERNANCE_PROGRAM_ID,
+        MythicMetadataError::InvalidGovernanceProgram
+    );
+    require_keys_eq!(
+        *account.owner,
+        *governance_program,
+        MythicMetadataError::InvalidAccountOwner
+    );
+
+    let data = account.try_borrow_data()?;
+    data.first()
+        .copied()
+        .ok_or_else(|| error!(MythicMetadataError::InvalidGovernanceAccount))
+}
+
+/// Returns the authority of the realm
+pub fn realm_authority(realm: &AccountInfo, governance_program: &Pubkey) -> Result<Option<Pubkey>> {
+    require!(
+        REALM_ACCOUNT_TYPES.contains(&account_type(realm, governance_program)?),
+        MythicMetadataError::InvalidGovernanceAccount
+    );
+
+    let data = realm.try_borrow_data()?;
+    let mut reader = data
+        .get(REALM_COUNCIL_MINT_OFFSET..)
+        .ok_or_else(|| error!(MythicMetadataError::InvalidGovernanceAccount))?;
+
+    Option::<Pubkey>::deserialize(&mut reader)
+        .ok()
+        .and_then(|_council_mint| reader.get(REALM_AUTHORITY_GAP..))
+        .and_then(|mut reader| Option::<Pubkey>::deserialize(&mut reader).ok())
+        .ok_or_else(|| error!(MythicMetadataError::InvalidGovernanceAccount))
+}
+
+/// Returns the realm the governance belongs to
+pub fn governance_realm(governance: &AccountInfo, governance_program: &Pubkey) -> Result<Pubkey> {
+    require!(
+        GOVERNANCE_ACCOUNT_TYPES.contains(&account_type(governance, governance_program)?),
+        MythicMetadataError::InvalidGovernanceAccount
+    );
+
+    let data = governance.try_borrow_data()?;
+    data.get(1..)
+        .and_then(|mut reader| Pubkey::deserialize(&mut reader).ok())
+        .ok_or_else(|| error!(MythicMetadataError::InvalidGovernanceAccount))
+}
+
+pub fn find_native_treasury_address(governance: &Pubkey, governance_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[NATIVE_TREASURY, governance.as_ref()], governance_program).0
+}
+
+/// Verifies the authority is the governance, or its native treasury, of the realm's authority
+/// Both can only sign through the execution of a governance proposal
+pub fn verify_governance_authority(
+    authority: &Pubkey,
+    governance: &AccountInfo,
+    realm: &AccountInfo,
+    governance_program: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(
+        governance_realm(governance, governance_program)?,
+        realm.key(),
+        MythicMetadataError::InvalidGovernanceAccount
+    );
+
+    require!(
+        realm_authority(realm, governance_program)? == Some(governance.key()),
+        MythicMetadataError::Unauthorized
+    );
+
+    require!(
+        governance.key.eq(authority)
+            || find_native_treasury_address(governance.key, governance_program).eq(authority),
+        MythicMetadataError::Unauthorized
+    );
+
+    Ok(())
+}
+
+/// Verifies the delegate is a governance of the realm, or its native treasury, which isn't the
+/// realm's authority anymore and returns the realm's current authority
+pub fn verify_stale_governance_delegate(
+    delegate: &Pubkey,
+    stale_governance: &AccountInfo,
+    realm: &AccountInfo,
+    governance_program: &Pubkey,
+) -> Result<Option<Pubkey>> {
+    require_keys_eq!(
+        governance_realm(stale_governance, governance_program)?,
+        realm.key(),
+        MythicMetadataError::InvalidGovernanceAccount
+    );
+
+    require!(
+        stale_governance.key.eq(delegate)
+            || find_native_treasury_address(stale_governance.key, governance_program).eq(delegate),
+        MythicMetadataError::InvalidGovernanceAccount
+    );
+
+    let authority = realm_authority(realm, governance_program)?;
+    require!(
+        authority != Some(stale_governance.key()),
+        MythicMetadataError::GovernanceDelegateNotStale
+    );
+
+    Ok(authority)
+}
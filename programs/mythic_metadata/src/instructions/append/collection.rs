@@ -9,7 +9,8 @@ use crate::utils::*;
 pub struct AppendMetadataCollection<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    pub issuing_authority: Signer<'info>,
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
     #[account(
         mut,
         constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
@@ -17,11 +18,10 @@ pub struct AppendMetadataCollection<'info> {
             PREFIX,
             METADATA,
             metadata_metadata_key.key().as_ref(),
-            issuing_authority.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
             metadata.subject.as_ref()
         ],
         bump,
-        has_one = issuing_authority,
     )]
     pub metadata: Account<'info, Metadata>,
     #[account(
@@ -42,6 +42,17 @@ pub struct AppendMetadataCollection<'info> {
         bump = collection_metadata_key.bump,
     )]
     pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -51,23 +62,26 @@ pub fn handler(
 ) -> Result<()> {
     let metadata = &mut ctx.accounts.metadata;
     let collection_metadata_key = &ctx.accounts.collection_metadata_key;
+    let collection_path = collection_path(&args.parent_path, collection_metadata_key.id);
+
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &collection_path,
+        MetadataDelegate::MANAGE_COLLECTIONS,
+    )?;
 
-    match metadata
-        .collections
-        .binary_search_by_key(&collection_metadata_key.id, |collection| {
-            collection.metadata_key_id
-        }) {
-        Ok(_) => return err!(MythicMetadataError::MetadataCollectionAlreadyExists),
-        Err(collection_index) => metadata.collections.insert(
-            collection_index,
-            MetadataCollection {
-                metadata_key_id: collection_metadata_key.id,
-                update_authority: args.update_authority,
-                update_slot: Clock::get()?.slot,
-                items: vec![],
-            },
-        ),
-    };
+    metadata.append_collection(MetadataCollection {
+        metadata_key_id: collection_metadata_key.id,
+        parent_path: args.parent_path,
+        update_authority: args.update_authority,
+        timelock_slots: 0,
+        frozen: false,
+        update_slot: Clock::get()?.slot,
+        items: vec![],
+        rent_payer: ctx.accounts.payer.key(),
+    })?;
 
     metadata.validate()?;
     let new_account_size = Metadata::size(&metadata.items, &metadata.collections);
@@ -84,4 +98,6 @@ pub fn handler(
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct AppendMetadataCollectionArgs {
     pub update_authority: Option<Pubkey>,
+    /// The MetadataKey Ids of the ancestor collections, empty for a top level collection
+    pub parent_path: Vec<u64>,
 }
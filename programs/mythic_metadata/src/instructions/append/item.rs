@@ -9,7 +9,8 @@ use crate::utils::*;
 pub struct AppendMetadataItem<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    pub issuing_authority: Signer<'info>,
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
     #[account(
         mut,
         constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
@@ -17,11 +18,10 @@ pub struct AppendMetadataItem<'info> {
             PREFIX,
             METADATA,
             metadata_metadata_key.key().as_ref(),
-            issuing_authority.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
             metadata.subject.as_ref()
         ],
         bump = metadata.bump,
-        has_one = issuing_authority
     )]
     pub metadata: Account<'info, Metadata>,
     #[account(
@@ -51,68 +51,46 @@ pub struct AppendMetadataItem<'info> {
         bump = item_metadata_key.bump,
     )]
     pub item_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<AppendMetadataItem>, args: AppendMetadataItemArgs) -> Result<()> {
     let metadata = &mut ctx.accounts.metadata;
-    let metadata_metadata_key = &ctx.accounts.metadata_metadata_key;
     let collection_metadata_key = &ctx.accounts.collection_metadata_key;
+    let collection_path = collection_path(&args.parent_path, collection_metadata_key.id);
     let item_metadata_key = &ctx.accounts.item_metadata_key;
 
-    // Check if metadata item is to be appended in main metadata
-    if check_collection_metadata_equality(metadata_metadata_key, collection_metadata_key) {
-        match metadata
-            .items
-            .binary_search_by_key(&item_metadata_key.id, |item| item.metadata_key_id)
-        {
-            Ok(_) => return err!(MythicMetadataError::MetadataItemAlreadyExists),
-            Err(item_index) => {
-                let slot = Clock::get()?.slot;
-                metadata.update_slot = slot;
-                metadata.items.insert(
-                    item_index,
-                    MetadataItem {
-                        metadata_key_id: item_metadata_key.id,
-                        update_slot: slot,
-                        value: args.value,
-                    },
-                );
-            }
-        };
-    } else {
-        match metadata
-            .collections
-            .binary_search_by_key(&collection_metadata_key.id, |collection| {
-                collection.metadata_key_id
-            }) {
-            Ok(collection_index) => {
-                let mut collection = metadata.collections.get_mut(collection_index).unwrap().clone();
-                
-                match collection
-                    .items
-                    .binary_search_by_key(&item_metadata_key.id, |item| item.metadata_key_id)
-                    {
-                        Ok(_) => return err!(MythicMetadataError::MetadataItemAlreadyExists),
-                        Err(item_index) => {
-                            let slot = Clock::get()?.slot;
-                            collection.update_slot = slot;
-                            collection.items.insert(
-                                item_index,
-                                MetadataItem {
-                                    metadata_key_id: item_metadata_key.id,
-                                    update_slot: slot,
-                                    value: args.value,
-                                },
-                            );
-                            metadata.collections.remove(collection_index);
-                            metadata.collections.insert(collection_index, collection);
-                        }
-                    }
-            }
-            Err(_) => return err!(MythicMetadataError::MetadataCollectionNonExistent)
-        };
-    }
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &collection_path,
+        MetadataDelegate::APPEND,
+    )?;
+
+    validate_item_value(item_metadata_key, &args.value)?;
+
+    metadata.append_item(
+        &collection_path,
+        MetadataItem {
+            metadata_key_id: item_metadata_key.id,
+            update_slot: Clock::get()?.slot,
+            value: args.value,
+            rent_payer: ctx.accounts.payer.key(),
+            growth_rent_shares: vec![],
+        },
+    )?;
 
     let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
     realloc_account(
@@ -130,4 +108,6 @@ pub fn handler(ctx: Context<AppendMetadataItem>, args: AppendMetadataItemArgs) -
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct AppendMetadataItemArgs {
     pub value: Vec<u8>,
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
 }
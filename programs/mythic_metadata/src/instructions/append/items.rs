@@ -10,7 +10,8 @@ use crate::ID;
 pub struct AppendMetadataItems<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    pub issuing_authority: Signer<'info>,
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
     #[account(
         mut,
         constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
@@ -18,11 +19,10 @@ pub struct AppendMetadataItems<'info> {
             PREFIX,
             METADATA,
             metadata_metadata_key.key().as_ref(),
-            issuing_authority.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
             metadata.subject.as_ref()
         ],
         bump = metadata.bump,
-        has_one = issuing_authority
     )]
     pub metadata: Account<'info, Metadata>,
     #[account(
@@ -43,16 +43,35 @@ pub struct AppendMetadataItems<'info> {
         bump = collection_metadata_key.bump,
     )]
     pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<AppendMetadataItems>, args: AppendMetadataItemsArgs) -> Result<()> {
     let metadata = &mut ctx.accounts.metadata;
-    let metadata_metadata_key = &ctx.accounts.metadata_metadata_key;
     let collection_metadata_key = &ctx.accounts.collection_metadata_key;
+    let collection_path = collection_path(&args.parent_path, collection_metadata_key.id);
+
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &collection_path,
+        MetadataDelegate::APPEND,
+    )?;
 
-    let mut index = 0;
-    for item_account_info in ctx.remaining_accounts {
+    let slot = Clock::get()?.slot;
+    for (index, item_account_info) in ctx.remaining_accounts.iter().enumerate() {
         if item_account_info.owner != &ID {
             return Err(anchor_lang::error!(
                 anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram
@@ -62,64 +81,20 @@ pub fn handler(ctx: Context<AppendMetadataItems>, args: AppendMetadataItemsArgs)
 
         let item_buf = &mut &**item_account_info.try_borrow_mut_data()?;
         let item_metadata_key = MetadataKey::try_deserialize(item_buf)?;
+        validate_item_value(&item_metadata_key, &args.value[index])?;
 
-        // Check if metadata item is to be appended in main metadata
-        if check_collection_metadata_equality(metadata_metadata_key, collection_metadata_key) {
-            match metadata
-                .items
-                .binary_search_by_key(&item_metadata_key.id, |item| item.metadata_key_id)
-            {
-                Ok(_) => return err!(MythicMetadataError::MetadataItemAlreadyExists),
-                Err(item_index) => {
-                    let slot = Clock::get()?.slot;
-                    metadata.update_slot = slot;
-                    metadata.items.insert(
-                        item_index,
-                        MetadataItem {
-                            metadata_key_id: item_metadata_key.id,
-                            update_slot: slot,
-                            value: args.value[index].clone(),
-                        },
-                    );
-                }
-            };
-        } else {
-            match metadata
-                .collections
-                .binary_search_by_key(&collection_metadata_key.id, |collection| {
-                    collection.metadata_key_id
-                }) {
-                Ok(collection_index) => {
-                    let mut collection = metadata.collections.get_mut(collection_index).unwrap().clone();
-                    
-                    match collection
-                        .items
-                        .binary_search_by_key(&item_metadata_key.id, |item| item.metadata_key_id)
-                        {
-                            Ok(_) => return err!(MythicMetadataError::MetadataItemAlreadyExists),
-                            Err(item_index) => {
-                                let slot = Clock::get()?.slot;
-                                collection.update_slot = slot;
-                                collection.items.insert(
-                                    item_index,
-                                    MetadataItem {
-                                        metadata_key_id: item_metadata_key.id,
-                                        update_slot: slot,
-                                        value: args.value[index].clone(),
-                                    },
-                                );
-                                metadata.collections.remove(collection_index);
-                                metadata.collections.insert(collection_index, collection);
-                            }
-                        }
-                }
-                Err(_) => return err!(MythicMetadataError::MetadataCollectionNonExistent)
-            };
-        }
-        index+=1;
+        metadata.append_item(
+            &collection_path,
+            MetadataItem {
+                metadata_key_id: item_metadata_key.id,
+                update_slot: slot,
+                value: args.value[index].clone(),
+                rent_payer: ctx.accounts.payer.key(),
+                growth_rent_shares: vec![],
+            },
+        )?;
     }
 
-    
     let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
     realloc_account(
         metadata.to_account_info(),
@@ -136,4 +111,6 @@ pub fn handler(ctx: Context<AppendMetadataItems>, args: AppendMetadataItemsArgs)
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct AppendMetadataItemsArgs {
     pub value: Vec<Vec<u8>>,
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
 }
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::signature::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Appends an item attested off-chain by the issuing authority, which doesn't sign the transaction
+///
+/// The instruction must be preceded by an ed25519 program instruction verifying the issuing
+/// authority's signature of the Attestation message. Replays are blocked by the Attestation's
+/// nonce, which must exceed the last nonce of the issuing authority, and its expiry slot
+#[derive(Accounts)]
+pub struct AttestMetadataItem<'info> {
+    /// The relayer or the subject posting the attestation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = collection_metadata_key.bump,
+    )]
+    pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &item_metadata_key.id.to_le_bytes()
+        ],
+        bump = item_metadata_key.bump,
+    )]
+    pub item_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SignerNonce::size(),
+        seeds = [
+            PREFIX,
+            NONCE,
+            ATTESTATION_DOMAIN,
+            metadata.key().as_ref(),
+            metadata.issuing_authority.as_ref()
+        ],
+        bump,
+    )]
+    pub signer_nonce: Account<'info, SignerNonce>,
+    /// CHECK: The instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AttestMetadataItem>, args: AttestMetadataItemArgs) -> Result<()> {
+    let signature = Ed25519Signature::load_preceding(&ctx.accounts.instructions_sysvar)?;
+
+    let metadata = &mut ctx.accounts.metadata;
+    let collection_path =
+        collection_path(&args.parent_path, ctx.accounts.collection_metadata_key.id);
+    let item_metadata_key = &ctx.accounts.item_metadata_key;
+
+    let attestation = Attestation {
+        subject: metadata.subject,
+        metadata_key_id: metadata.metadata_key_id,
+        collection_path,
+        item_metadata_key_id: item_metadata_key.id,
+        value: args.value,
+        nonce: args.nonce,
+        expiry_slot: args.expiry_slot,
+    };
+    let slot = Clock::get()?.slot;
+    verify_signed_message(
+        &signature,
+        &metadata.issuing_authority,
+        &attestation.message(),
+        attestation.expiry_slot,
+        slot,
+    )?;
+    consume_signer_nonce(
+        &mut ctx.accounts.signer_nonce,
+        ctx.bumps.signer_nonce,
+        metadata.key(),
+        signature.signer,
+        attestation.nonce,
+    )?;
+
+    validate_item_value(item_metadata_key, &attestation.value)?;
+
+    metadata.append_item(
+        &attestation.collection_path,
+        MetadataItem {
+            metadata_key_id: item_metadata_key.id,
+            update_slot: slot,
+            value: attestation.value,
+            rent_payer: ctx.accounts.payer.key(),
+            growth_rent_shares: vec![],
+        },
+    )?;
+
+    let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
+    realloc_account(
+        metadata.to_account_info(),
+        metadata_new_size,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    metadata.validate()?;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AttestMetadataItemArgs {
+    pub value: Vec<u8>,
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
+    pub nonce: u64,
+    pub expiry_slot: u64,
+}
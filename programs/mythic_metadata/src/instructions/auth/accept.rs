@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Sets the proposed update authority, which signs to prove it's in control of the key
+/// A proposed MultisigAuthority accepts when enough of its signers sign
+/// The rent of the AuthorityTransfer is refunded to its payer
+#[derive(Accounts)]
+pub struct AcceptUpdateAuthorityTransfer<'info> {
+    /// The proposed update authority or a signer of the proposed MultisigAuthority
+    pub new_update_authority: Signer<'info>,
+    #[account(mut)]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        mut,
+        close = payer,
+        has_one = metadata,
+        has_one = payer,
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+    /// CHECK: The payer of the AuthorityTransfer rent
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    /// The proposed MultisigAuthority the new update authority signs for
+    /// The other signers of the MultisigAuthority sign as remaining accounts
+    pub multisig_authority: Option<Account<'info, MultisigAuthority>>,
+}
+
+pub fn handler(ctx: Context<AcceptUpdateAuthorityTransfer>) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    let authority_transfer = &ctx.accounts.authority_transfer;
+
+    require!(
+        MultisigSigners::new(
+            ctx.accounts.new_update_authority.key(),
+            ctx.accounts.multisig_authority.as_ref(),
+            ctx.remaining_accounts,
+        )
+        .is(&authority_transfer.new_update_authority),
+        MythicMetadataError::Unauthorized
+    );
+
+    metadata.set_update_authority(
+        &authority_transfer.collection_path,
+        Some(authority_transfer.new_update_authority),
+    )?;
+
+    metadata.close_record();
+
+    metadata.validate()?;
+
+    Ok(())
+}
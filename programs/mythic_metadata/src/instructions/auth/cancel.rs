@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Cancels the proposed transfer, the proposed update authority can decline it as well
+/// The Metadata update authority can cancel it too, token Metadata transfers are proposed by it
+/// The rent of the AuthorityTransfer is refunded to its payer
+#[derive(Accounts)]
+pub struct CancelUpdateAuthorityTransfer<'info> {
+    /// The issuing authority, a delegate, the Metadata update authority or the proposed update authority
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        mut,
+        close = payer,
+        has_one = metadata,
+        has_one = payer,
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+    /// CHECK: The payer of the AuthorityTransfer rent
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+}
+
+pub fn handler(ctx: Context<CancelUpdateAuthorityTransfer>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let authority_transfer = &ctx.accounts.authority_transfer;
+
+    if authority.ne(&authority_transfer.new_update_authority)
+        && ctx.accounts.metadata.update_authority != Some(authority)
+    {
+        verify_issuing_authority(
+            &ctx.accounts.metadata,
+            &authority,
+            ctx.accounts.delegate_record.as_deref(),
+            &authority_transfer.collection_path,
+            MetadataDelegate::MANAGE_AUTHORITIES,
+        )?;
+    }
+
+    ctx.accounts.metadata.close_record();
+
+    Ok(())
+}
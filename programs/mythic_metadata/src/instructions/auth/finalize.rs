@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Finalizes the Metadata making it, its collections and items permanently immutable
+/// Revoking the update authority doesn't make the Metadata immutable, it must be finalized explicitly
+#[derive(Accounts)]
+pub struct FinalizeMetadata<'info> {
+    /// The issuing authority or the Metadata update authority, finalization can't be delegated
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    /// The MultisigAuthority the authority signs for as the Metadata update authority
+    /// The other signers of the MultisigAuthority sign as remaining accounts
+    pub multisig_authority: Option<Account<'info, MultisigAuthority>>,
+}
+
+pub fn handler(ctx: Context<FinalizeMetadata>, args: FinalizeMetadataArgs) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+
+    verify_permanent_change_authority(
+        metadata,
+        &MultisigSigners::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.multisig_authority.as_ref(),
+            ctx.remaining_accounts,
+        ),
+    )?;
+
+    require!(
+        metadata.state_hash()? == args.state_hash,
+        MythicMetadataError::MetadataStateHashMismatch
+    );
+
+    let slot = Clock::get()?.slot;
+    metadata.finalize(slot)?;
+
+    emit!(MetadataFinalized {
+        metadata: metadata.key(),
+        slot,
+    });
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FinalizeMetadataArgs {
+    /// The Metadata.state_hash of the Metadata being finalized
+    /// Confirms the finalization and that the Metadata didn't change since it was reviewed
+    pub state_hash: [u8; 32],
+}
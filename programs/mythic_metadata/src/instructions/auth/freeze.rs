@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Freezes the collection and its nested collections permanently
+/// No authority can change their items, authorities or timelocks afterwards
+#[derive(Accounts)]
+pub struct FreezeMetadataCollection<'info> {
+    /// The issuing authority or the Metadata update authority, freezing can't be delegated
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = collection_metadata_key.bump,
+    )]
+    pub collection_metadata_key: Account<'info, MetadataKey>,
+    /// The MultisigAuthority the authority signs for as the Metadata update authority
+    /// The other signers of the MultisigAuthority sign as remaining accounts
+    pub multisig_authority: Option<Account<'info, MultisigAuthority>>,
+}
+
+pub fn handler(
+    ctx: Context<FreezeMetadataCollection>,
+    args: FreezeMetadataCollectionArgs,
+) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    let collection_metadata_key = &ctx.accounts.collection_metadata_key;
+    let collection_path = collection_path(&args.parent_path, collection_metadata_key.id);
+
+    verify_permanent_change_authority(
+        metadata,
+        &MultisigSigners::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.multisig_authority.as_ref(),
+            ctx.remaining_accounts,
+        ),
+    )?;
+
+    metadata.freeze_collection(&collection_path)?;
+
+    metadata.validate()?;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FreezeMetadataCollectionArgs {
+    /// The MetadataKey Ids of the ancestor collections, empty for top level collections
+    pub parent_path: Vec<u64>,
+}
@@ -1,5 +1,15 @@
+pub mod accept;
+pub mod cancel;
+pub mod finalize;
+pub mod freeze;
+pub mod propose;
 pub mod revoke;
 pub mod set;
 
+pub use accept::*;
+pub use cancel::*;
+pub use finalize::*;
+pub use freeze::*;
+pub use propose::*;
 pub use revoke::*;
 pub use set::*;
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Proposes a new update authority for the Metadata or the collection
+/// The root path proposes the Metadata update authority
+/// A collection has at most one proposal, a pending one must be cancelled before proposing another
+#[derive(Accounts)]
+#[instruction(args: ProposeUpdateAuthorityTransferArgs)]
+pub struct ProposeUpdateAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = collection_metadata_key.bump,
+    )]
+    pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        init,
+        payer = payer,
+        space = AuthorityTransfer::size(args.parent_path.len() + 1),
+        seeds = [
+            PREFIX,
+            AUTHORITY_TRANSFER,
+            metadata.key().as_ref(),
+            &path_seed(&collection_path(&args.parent_path, collection_metadata_key.id))
+        ],
+        bump,
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeUpdateAuthorityTransfer>,
+    args: ProposeUpdateAuthorityTransferArgs,
+) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    let collection_path =
+        collection_path(&args.parent_path, ctx.accounts.collection_metadata_key.id);
+
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &collection_path,
+        MetadataDelegate::MANAGE_AUTHORITIES,
+    )?;
+
+    require!(
+        !metadata.is_frozen(&collection_path)?,
+        MythicMetadataError::MetadataCollectionFrozen
+    );
+
+    metadata.open_record();
+
+    let authority_transfer = &mut ctx.accounts.authority_transfer;
+    authority_transfer.metadata = metadata.key();
+    authority_transfer.collection_path = collection_path;
+    authority_transfer.new_update_authority = args.new_update_authority;
+    authority_transfer.payer = ctx.accounts.payer.key();
+    authority_transfer.bump = ctx.bumps.authority_transfer;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeUpdateAuthorityTransferArgs {
+    pub new_update_authority: Pubkey,
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
+}
@@ -5,9 +5,13 @@ use crate::errors::*;
 use crate::state::*;
 use crate::utils::*;
 
+/// Revokes the update authority of the collection, the parent authorities are used instead
+/// Revoking the Metadata update authority falls back to the issuing authority,
+/// use finalize_metadata to make the Metadata immutable
 #[derive(Accounts)]
 pub struct RevokeCollectionUpdateAuthority<'info> {
-    pub issuing_authority: Signer<'info>,
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
     #[account(
         mut,
         constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
@@ -15,11 +19,10 @@ pub struct RevokeCollectionUpdateAuthority<'info> {
             PREFIX,
             METADATA,
             metadata_metadata_key.key().as_ref(),
-            issuing_authority.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
             metadata.subject.as_ref()
         ],
         bump,
-        has_one = issuing_authority
     )]
     pub metadata: Account<'info, Metadata>,
     #[account(
@@ -40,33 +43,44 @@ pub struct RevokeCollectionUpdateAuthority<'info> {
         bump = collection_metadata_key.bump,
     )]
     pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
 }
 
-pub fn handler(ctx: Context<RevokeCollectionUpdateAuthority>) -> Result<()> {
+pub fn handler(
+    ctx: Context<RevokeCollectionUpdateAuthority>,
+    args: RevokeCollectionUpdateAuthorityArgs,
+) -> Result<()> {
     let metadata = &mut ctx.accounts.metadata;
-    let metadata_metadata_key = &ctx.accounts.metadata_metadata_key;
     let collection_metadata_key = &ctx.accounts.collection_metadata_key;
+    let collection_path = collection_path(&args.parent_path, collection_metadata_key.id);
+
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &collection_path,
+        MetadataDelegate::MANAGE_AUTHORITIES,
+    )?;
 
-    // Check if root collection and collection is same to update root_collection.update_authority
-    if check_collection_metadata_equality(metadata_metadata_key, collection_metadata_key) {
-        metadata.update_authority = None;
-    } else {
-        match metadata
-            .collections
-            .binary_search_by_key(&collection_metadata_key.id, |collection| {
-                collection.metadata_key_id
-            }) {
-            Ok(collection_index) => {
-                let mut collection = metadata.collections.get_mut(collection_index).unwrap().clone();
-                collection.update_authority = None;
-                metadata.collections.remove(collection_index);
-                metadata.collections.insert(collection_index, collection);
-            }
-            Err(_) => return err!(MythicMetadataError::MetadataCollectionNonExistent)
-        }
-    }
+    metadata.set_update_authority(&collection_path, None)?;
 
     metadata.validate()?;
 
     Ok(())
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevokeCollectionUpdateAuthorityArgs {
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
+}
@@ -7,7 +7,8 @@ use crate::utils::*;
 
 #[derive(Accounts)]
 pub struct SetCollectionUpdateAuthority<'info> {
-    pub issuing_authority: Signer<'info>,
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
     #[account(
         mut,
         constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
@@ -15,11 +16,10 @@ pub struct SetCollectionUpdateAuthority<'info> {
             PREFIX,
             METADATA,
             metadata_metadata_key.key().as_ref(),
-            issuing_authority.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
             metadata.subject.as_ref()
         ],
         bump = metadata.bump,
-        has_one = issuing_authority
     )]
     pub metadata: Account<'info, Metadata>,
     #[account(
@@ -40,6 +40,17 @@ pub struct SetCollectionUpdateAuthority<'info> {
         bump = collection_metadata_key.bump,
     )]
     pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
 }
 
 pub fn handler(
@@ -47,27 +58,23 @@ pub fn handler(
     args: SetCollectionUpdateAuthorityArgs,
 ) -> Result<()> {
     let metadata = &mut ctx.accounts.metadata;
-    let metadata_metadata_key = &ctx.accounts.metadata_metadata_key;
     let collection_metadata_key = &ctx.accounts.collection_metadata_key;
+    let collection_path = collection_path(&args.parent_path, collection_metadata_key.id);
+
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &collection_path,
+        MetadataDelegate::MANAGE_AUTHORITIES,
+    )?;
+
+    require!(
+        args.unsafe_skip_acceptance || args.new_update_authority.is_none(),
+        MythicMetadataError::UnsafeAuthorityChange
+    );
 
-    // Check if root collection and collection is same to update root_collection.update_authority
-    if check_collection_metadata_equality(metadata_metadata_key, collection_metadata_key) {
-        metadata.update_authority = args.new_update_authority;
-    } else {
-        match metadata
-            .collections
-            .binary_search_by_key(&collection_metadata_key.id, |collection| {
-                collection.metadata_key_id
-            }) {
-            Ok(collection_index) => {
-                let mut collection = metadata.collections.get_mut(collection_index).unwrap().clone();
-                collection.update_authority = args.new_update_authority;
-                metadata.collections.remove(collection_index);
-                metadata.collections.insert(collection_index, collection);
-            }
-            Err(_) => return err!(MythicMetadataError::MetadataCollectionNonExistent)
-        }
-    }
+    metadata.set_update_authority(&collection_path, args.new_update_authority)?;
 
     metadata.validate()?;
 
@@ -77,4 +84,9 @@ pub fn handler(
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct SetCollectionUpdateAuthorityArgs {
     pub new_update_authority: Option<Pubkey>,
+    /// Must be set to replace the update authority without the new authority accepting it
+    /// A mistyped authority locks everyone out, use propose_update_authority_transfer instead
+    pub unsafe_skip_acceptance: bool,
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
 }
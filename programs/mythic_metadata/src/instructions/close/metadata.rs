@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Closes the Metadata and removes it from the registry page of its subject
+/// Finalized Metadata can't be closed
+///
+/// The rent of the items and collections is refunded to their rent payers, which are passed
+/// as writable remaining accounts, the rest of the rent goes to the issuing authority
+///
+/// The delegate records, pending changes and authority transfers of the Metadata must be closed first
+#[derive(Accounts)]
+pub struct CloseMetadata<'info> {
+    /// Receives the rent of the closed accounts
+    #[account(mut)]
+    pub issuing_authority: Signer<'info>,
+    #[account(
+        mut,
+        close = issuing_authority,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            issuing_authority.key().as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+        has_one = issuing_authority
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    /// The registry page listing the Metadata, required if the Metadata is registered
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            SUBJECT_REGISTRY,
+            metadata.subject.as_ref(),
+            &metadata.registry_page_index.unwrap_or_default().to_le_bytes()
+        ],
+        bump = subject_registry_page.bump,
+    )]
+    pub subject_registry_page: Option<Account<'info, SubjectRegistryPage>>,
+}
+
+pub fn handler(ctx: Context<CloseMetadata>) -> Result<()> {
+    let metadata = &ctx.accounts.metadata;
+    metadata.verify_closable()?;
+
+    let metadata_info = metadata.to_account_info();
+    transfer_rent_shares(
+        &metadata_info,
+        metadata_info.lamports(),
+        metadata
+            .rent_shares()
+            .filter(|rent_share| rent_share.rent_payer != metadata.issuing_authority),
+        ctx.remaining_accounts,
+    )?;
+
+    if metadata.registry_page_index.is_some() {
+        ctx.accounts
+            .subject_registry_page
+            .as_mut()
+            .ok_or(MythicMetadataError::SubjectRegistryPageMissing)?
+            .remove(&SubjectRegistryEntry {
+                metadata_key_id: metadata.metadata_key_id,
+                issuing_authority: metadata.issuing_authority,
+            });
+    }
+
+    Ok(())
+}
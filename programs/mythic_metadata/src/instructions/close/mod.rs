@@ -0,0 +1,3 @@
+pub mod metadata;
+
+pub use metadata::*;
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Creates a Metadata and copies the selected root items and collections of an existing Metadata
+/// The new Metadata differs from the source by its subject, MetadataKey or issuing authority
+/// Delegates, collection update authorities, the verified subject flag and EVM address links
+/// aren't copied, the links' proofs are bound to the source's subject
+#[derive(Accounts)]
+#[instruction(args: CloneMetadataArgs)]
+pub struct CloneMetadata<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub issuing_authority: Signer<'info>,
+    pub source_metadata: Account<'info, Metadata>,
+    #[account(
+        init,
+        payer = payer,
+        space = Metadata::size(&[], &[]),
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            issuing_authority.key().as_ref(),
+            args.subject.as_ref()
+        ],
+        bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    pub system_program: Program<'info, System>,
+    /// The registry accounts are optional so the accounts of the form without registries stay
+    /// valid, the Metadata is listed only when both are passed, otherwise with register_metadata
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SubjectRegistry::size(),
+        seeds = [PREFIX, SUBJECT_REGISTRY, args.subject.as_ref()],
+        bump,
+    )]
+    pub subject_registry: Option<Account<'info, SubjectRegistry>>,
+    /// The page the Metadata is listed in, see SubjectRegistry::next_page_index
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SubjectRegistryPage::size(0),
+        seeds = [
+            PREFIX,
+            SUBJECT_REGISTRY,
+            args.subject.as_ref(),
+            &subject_registry
+                .as_ref()
+                .map_or(0, |subject_registry| subject_registry.next_page_index())
+                .to_le_bytes()
+        ],
+        bump,
+    )]
+    pub subject_registry_page: Option<Account<'info, SubjectRegistryPage>>,
+}
+
+pub fn handler(ctx: Context<CloneMetadata>, args: CloneMetadataArgs) -> Result<()> {
+    let CloneMetadataArgs {
+        subject,
+        update_authority,
+        collection_paths,
+    } = args;
+
+    let metadata = &mut ctx.accounts.metadata;
+    let slot = Clock::get()?.slot;
+    metadata.set_inner(Metadata {
+        bump: ctx.bumps.metadata,
+        collections: vec![],
+        items: vec![],
+        metadata_key_id: ctx.accounts.metadata_metadata_key.id,
+        update_authority,
+        update_slot: slot,
+        issuing_authority: ctx.accounts.issuing_authority.key(),
+        subject,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    });
+    metadata.copy_from(
+        &ctx.accounts.source_metadata,
+        &collection_paths,
+        slot,
+        ctx.accounts.payer.key(),
+    )?;
+
+    let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
+    realloc_account(
+        metadata.to_account_info(),
+        metadata_new_size,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    metadata.validate()?;
+
+    register_optional_subject_metadata(
+        &mut ctx.accounts.subject_registry,
+        ctx.bumps.subject_registry,
+        &mut ctx.accounts.subject_registry_page,
+        ctx.bumps.subject_registry_page,
+        metadata,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CloneMetadataArgs {
+    pub subject: Pubkey,
+    pub update_authority: Option<Pubkey>,
+    /// The collection filter, the paths of the source collections to copy with their nested collections
+    /// The source's root path copies its root items, an empty filter copies everything
+    pub collection_paths: Vec<Vec<u64>>,
+}
@@ -0,0 +1,149 @@
+use std::vec;
+
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::governance::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Creates the canonical Metadata of a DAO whose issuing authority and subject are the realm
+/// The Metadata is self-issued by the realm and therefore has a verified subject
+///
+/// The realm can't sign, so the issuing permissions are granted to the governance authority
+/// with a metadata wide MetadataDelegate and the governance authority becomes the update authority.
+/// Mutations therefore require the governance, or its native treasury, to sign by executing a proposal
+/// When the realm's authority changes, sync_governance_delegate moves both to the new authority
+#[derive(Accounts)]
+pub struct CreateGovernanceMetadata<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The governance of the realm's authority or its native treasury
+    pub governance_authority: Signer<'info>,
+    /// CHECK: Verified by verify_governance_authority
+    pub governance: UncheckedAccount<'info>,
+    /// CHECK: Verified by verify_governance_authority
+    pub realm: UncheckedAccount<'info>,
+    /// CHECK: The owner of the governance and realm accounts
+    #[account(address = SPL_GOVERNANCE_PROGRAM_ID @ MythicMetadataError::InvalidGovernanceProgram)]
+    pub governance_program: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = Metadata::size(&[], &[]),
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            realm.key().as_ref(),
+            realm.key().as_ref()
+        ],
+        bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        init,
+        payer = payer,
+        space = MetadataDelegate::size(1),
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&[metadata_metadata_key.id]),
+            governance_authority.key().as_ref()
+        ],
+        bump,
+    )]
+    pub delegate_record: Account<'info, MetadataDelegate>,
+    pub system_program: Program<'info, System>,
+    /// The registry accounts are optional so the accounts of the form without registries stay
+    /// valid, the Metadata is listed only when both are passed, otherwise with register_metadata
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SubjectRegistry::size(),
+        seeds = [PREFIX, SUBJECT_REGISTRY, realm.key().as_ref()],
+        bump,
+    )]
+    pub subject_registry: Option<Account<'info, SubjectRegistry>>,
+    /// The page the Metadata is listed in, see SubjectRegistry::next_page_index
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SubjectRegistryPage::size(0),
+        seeds = [
+            PREFIX,
+            SUBJECT_REGISTRY,
+            realm.key().as_ref(),
+            &subject_registry
+                .as_ref()
+                .map_or(0, |subject_registry| subject_registry.next_page_index())
+                .to_le_bytes()
+        ],
+        bump,
+    )]
+    pub subject_registry_page: Option<Account<'info, SubjectRegistryPage>>,
+}
+
+pub fn handler(ctx: Context<CreateGovernanceMetadata>) -> Result<()> {
+    let governance_authority = ctx.accounts.governance_authority.key();
+    let realm = ctx.accounts.realm.key();
+
+    verify_governance_authority(
+        &governance_authority,
+        &ctx.accounts.governance,
+        &ctx.accounts.realm,
+        ctx.accounts.governance_program.key,
+    )?;
+
+    let metadata = &mut ctx.accounts.metadata;
+    metadata.set_inner(Metadata {
+        bump: ctx.bumps.metadata,
+        collections: vec![],
+        items: vec![],
+        metadata_key_id: ctx.accounts.metadata_metadata_key.id,
+        update_authority: Some(governance_authority),
+        update_slot: Clock::get()?.slot,
+        issuing_authority: realm,
+        subject: realm,
+        // The realm authority is the realm's own governance
+        verified_subject: true,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    });
+
+    metadata.validate()?;
+
+    register_optional_subject_metadata(
+        &mut ctx.accounts.subject_registry,
+        ctx.bumps.subject_registry,
+        &mut ctx.accounts.subject_registry_page,
+        ctx.bumps.subject_registry_page,
+        metadata,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let delegate_record = &mut ctx.accounts.delegate_record;
+    delegate_record.set_inner(MetadataDelegate {
+        metadata: metadata.key(),
+        collection_path: vec![metadata.metadata_key_id],
+        delegate: governance_authority,
+        permissions: MetadataDelegate::ALL_PERMISSIONS,
+        bump: ctx.bumps.delegate_record,
+    });
+    metadata.open_record();
+
+    Ok(())
+}
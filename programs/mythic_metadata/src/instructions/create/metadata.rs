@@ -4,6 +4,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::state::*;
+use crate::utils::*;
 
 #[derive(Accounts)]
 #[instruction(args: CreateMetadataArgs)]
@@ -35,6 +36,39 @@ pub struct CreateMetadata<'info> {
     )]
     pub metadata_metadata_key: Account<'info, MetadataKey>,
     pub system_program: Program<'info, System>,
+    /// The subject, its mint authority or its program upgrade authority
+    /// If present the subject is verified and the Metadata is marked as self-issued
+    pub subject_authority: Option<Signer<'info>>,
+    /// CHECK: The subject mint or the subject program's ProgramData, verified by verify_subject_authority
+    /// Not required if the subject itself is the subject_authority
+    pub subject_account: Option<UncheckedAccount<'info>>,
+    /// The registry accounts are optional so the accounts of the form without registries stay
+    /// valid, the Metadata is listed only when both are passed, otherwise with register_metadata
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SubjectRegistry::size(),
+        seeds = [PREFIX, SUBJECT_REGISTRY, args.subject.as_ref()],
+        bump,
+    )]
+    pub subject_registry: Option<Account<'info, SubjectRegistry>>,
+    /// The page the Metadata is listed in, see SubjectRegistry::next_page_index
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SubjectRegistryPage::size(0),
+        seeds = [
+            PREFIX,
+            SUBJECT_REGISTRY,
+            args.subject.as_ref(),
+            &subject_registry
+                .as_ref()
+                .map_or(0, |subject_registry| subject_registry.next_page_index())
+                .to_le_bytes()
+        ],
+        bump,
+    )]
+    pub subject_registry_page: Option<Account<'info, SubjectRegistryPage>>,
 }
 
 pub fn handler(ctx: Context<CreateMetadata>, args: CreateMetadataArgs) -> Result<()> {
@@ -43,6 +77,18 @@ pub fn handler(ctx: Context<CreateMetadata>, args: CreateMetadataArgs) -> Result
         update_authority,
     } = args;
 
+    let verified_subject = match &ctx.accounts.subject_authority {
+        Some(subject_authority) => {
+            verify_subject_authority(
+                &subject,
+                subject_authority.key,
+                ctx.accounts.subject_account.as_deref(),
+            )?;
+            true
+        }
+        None => false,
+    };
+
     let metadata = &mut ctx.accounts.metadata;
     metadata.set_inner(Metadata {
         bump: ctx.bumps.metadata,
@@ -53,10 +99,24 @@ pub fn handler(ctx: Context<CreateMetadata>, args: CreateMetadataArgs) -> Result
         update_slot: Clock::get()?.slot,
         issuing_authority: ctx.accounts.issuing_authority.key(),
         subject,
+        verified_subject,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
     });
 
     metadata.validate()?;
 
+    register_optional_subject_metadata(
+        &mut ctx.accounts.subject_registry,
+        ctx.bumps.subject_registry,
+        &mut ctx.accounts.subject_registry_page,
+        ctx.bumps.subject_registry_page,
+        metadata,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
     Ok(())
 }
 
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 
 use crate::constants::*;
+use crate::errors::*;
 use crate::state::*;
+use crate::utils::*;
 
 #[derive(Accounts)]
 #[instruction(args: CreateMetadataKeyArgs)]
@@ -27,6 +29,37 @@ pub struct CreateMetadataKey<'info> {
     )]
     pub metadata_key: Account<'info, MetadataKey>,
     pub system_program: Program<'info, System>,
+    /// The registry accounts are optional so the accounts of the form without registries stay
+    /// valid, the MetadataKey is listed only when both are passed
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = NamespaceRegistry::size(),
+        seeds = [
+            PREFIX,
+            NAMESPACE_REGISTRY,
+            namespace_authority.key().as_ref()
+        ],
+        bump,
+    )]
+    pub namespace_registry: Option<Account<'info, NamespaceRegistry>>,
+    /// The page the MetadataKey is listed in, see NamespaceRegistry::next_page_index
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = NamespaceRegistryPage::size(&[]),
+        seeds = [
+            PREFIX,
+            NAMESPACE_REGISTRY,
+            namespace_authority.key().as_ref(),
+            &namespace_registry
+                .as_ref()
+                .map_or(0, |namespace_registry| namespace_registry.next_page_index())
+                .to_le_bytes()
+        ],
+        bump,
+    )]
+    pub namespace_registry_page: Option<Account<'info, NamespaceRegistryPage>>,
 }
 
 pub fn handler(ctx: Context<CreateMetadataKey>, args: CreateMetadataKeyArgs) -> Result<()> {
@@ -36,29 +69,95 @@ pub fn handler(ctx: Context<CreateMetadataKey>, args: CreateMetadataKeyArgs) ->
         label,
         description,
         content_type,
+        multi_valued,
     } = args;
     MetadataKey::validate(&name, &label, &description, &content_type)?;
+    require!(
+        id < MIN_RESERVED_METADATA_KEY_ID,
+        MythicMetadataError::InvalidMetadataKey
+    );
+
+    let namespace_authority = ctx.accounts.namespace_authority.key();
+
+    match (
+        &mut ctx.accounts.namespace_registry,
+        &mut ctx.accounts.namespace_registry_page,
+    ) {
+        (Some(namespace_registry), Some(namespace_registry_page)) => {
+            let page_index = namespace_registry.next_page_index();
+            namespace_registry.namespace_authority = namespace_authority;
+            namespace_registry.keys_count += 1;
+            namespace_registry.bump = ctx.bumps.namespace_registry.unwrap();
+
+            namespace_registry_page.namespace_authority = namespace_authority;
+            namespace_registry_page.page_index = page_index;
+            namespace_registry_page.bump = ctx.bumps.namespace_registry_page.unwrap();
+            namespace_registry_page
+                .entries
+                .push(NamespaceRegistryEntry {
+                    id,
+                    name: name.clone(),
+                    content_type: content_type.clone(),
+                });
+            realloc_account(
+                namespace_registry_page.to_account_info(),
+                NamespaceRegistryPage::size(&namespace_registry_page.entries),
+                ctx.accounts.payer.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        (None, None) => {}
+        _ => return err!(ErrorCode::ConstraintAccountIsNone),
+    }
 
     let metadata_key = &mut ctx.accounts.metadata_key;
 
     metadata_key.set_inner(MetadataKey {
         bump: ctx.bumps.metadata_key,
         id,
-        namespace_authority: ctx.accounts.namespace_authority.key(),
+        namespace_authority,
         name,
         description,
         label,
         content_type,
+        multi_valued,
     });
 
     Ok(())
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize)]
 pub struct CreateMetadataKeyArgs {
     pub id: u64,
     pub name: String,
     pub label: String,
     pub description: String,
     pub content_type: String,
+    /// Items of the key hold a list of values, see MetadataKey.multi_valued
+    pub multi_valued: bool,
+}
+
+/// Clients built before multi-valued keys don't send multi_valued, their keys are single valued
+impl AnchorDeserialize for CreateMetadataKeyArgs {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let id = u64::deserialize_reader(reader)?;
+        let name = String::deserialize_reader(reader)?;
+        let label = String::deserialize_reader(reader)?;
+        let description = String::deserialize_reader(reader)?;
+        let content_type = String::deserialize_reader(reader)?;
+        let mut multi_valued = [0];
+        let multi_valued = match reader.read(&mut multi_valued)? {
+            0 => false,
+            _ => bool::try_from_slice(&multi_valued)?,
+        };
+
+        Ok(Self {
+            id,
+            name,
+            label,
+            description,
+            content_type,
+            multi_valued,
+        })
+    }
 }
@@ -1,5 +1,11 @@
+pub mod clone_metadata;
+pub mod governance_metadata;
 pub mod metadata;
 pub mod metadata_key;
+pub mod multisig_authority;
 
+pub use clone_metadata::*;
+pub use governance_metadata::*;
 pub use metadata::*;
 pub use metadata_key::*;
+pub use multisig_authority::*;
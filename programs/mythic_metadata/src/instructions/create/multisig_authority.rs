@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::*;
+
+/// Creates an M-of-N update authority
+#[derive(Accounts)]
+#[instruction(args: CreateMultisigAuthorityArgs)]
+pub struct CreateMultisigAuthority<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = MultisigAuthority::size(args.signers.len()),
+        seeds = [
+            PREFIX,
+            MULTISIG,
+            args.create_key.as_ref()
+        ],
+        bump,
+    )]
+    pub multisig_authority: Account<'info, MultisigAuthority>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateMultisigAuthority>,
+    args: CreateMultisigAuthorityArgs,
+) -> Result<()> {
+    let mut signers = args.signers;
+    signers.sort();
+
+    let multisig_authority = &mut ctx.accounts.multisig_authority;
+    multisig_authority.create_key = args.create_key;
+    multisig_authority.threshold = args.threshold;
+    multisig_authority.signers = signers;
+    multisig_authority.bump = ctx.bumps.multisig_authority;
+
+    multisig_authority.validate()?;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateMultisigAuthorityArgs {
+    pub create_key: Pubkey,
+    pub threshold: u8,
+    pub signers: Vec<Pubkey>,
+}
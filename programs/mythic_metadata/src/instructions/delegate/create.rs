@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(args: CreateMetadataDelegateArgs)]
+pub struct CreateMetadataDelegate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub issuing_authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            issuing_authority.key().as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+        has_one = issuing_authority
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    /// The collection the permissions are scoped to, nested in the args.parent_path
+    /// Pass the metadata_metadata_key with an empty parent_path to scope the permissions to the whole Metadata
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = collection_metadata_key.bump,
+    )]
+    pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        init,
+        payer = payer,
+        space = MetadataDelegate::size(args.parent_path.len() + 1),
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&collection_path(&args.parent_path, collection_metadata_key.id)),
+            args.delegate.as_ref()
+        ],
+        bump,
+    )]
+    pub delegate_record: Account<'info, MetadataDelegate>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateMetadataDelegate>,
+    args: CreateMetadataDelegateArgs,
+) -> Result<()> {
+    let CreateMetadataDelegateArgs {
+        delegate,
+        permissions,
+        parent_path,
+    } = args;
+
+    let delegate_record = &mut ctx.accounts.delegate_record;
+    delegate_record.set_inner(MetadataDelegate {
+        metadata: ctx.accounts.metadata.key(),
+        collection_path: collection_path(&parent_path, ctx.accounts.collection_metadata_key.id),
+        delegate,
+        permissions,
+        bump: ctx.bumps.delegate_record,
+    });
+
+    delegate_record.validate()?;
+
+    ctx.accounts.metadata.open_record();
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateMetadataDelegateArgs {
+    pub delegate: Pubkey,
+    /// Bitmask of MetadataDelegate permissions
+    pub permissions: u8,
+    /// The MetadataKey Ids of the ancestor collections, empty for top level collections and the whole Metadata
+    pub parent_path: Vec<u64>,
+}
@@ -0,0 +1,7 @@
+pub mod create;
+pub mod revoke;
+pub mod sync_governance;
+
+pub use create::*;
+pub use revoke::*;
+pub use sync_governance::*;
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RevokeMetadataDelegate<'info> {
+    /// Receives the rent of the closed delegate record
+    #[account(mut)]
+    pub issuing_authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            issuing_authority.key().as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+        has_one = issuing_authority
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        mut,
+        close = issuing_authority,
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            delegate_record.delegate.as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Account<'info, MetadataDelegate>,
+}
+
+pub fn handler(ctx: Context<RevokeMetadataDelegate>) -> Result<()> {
+    ctx.accounts.metadata.close_record();
+
+    Ok(())
+}
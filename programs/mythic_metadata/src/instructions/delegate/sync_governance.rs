@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::governance::*;
+use crate::state::*;
+
+/// Moves the MetadataDelegate of a governance Metadata to the realm's current authority
+/// The realm can't sign to revoke the delegate record of a governance which isn't the realm's
+/// authority anymore, so anyone can sync it: the stale record is closed and, if the realm still
+/// has an authority, a record with all the permissions is granted to it. An update authority held
+/// by the stale governance moves to the realm's authority as well
+#[derive(Accounts)]
+pub struct SyncGovernanceDelegate<'info> {
+    /// Receives the rent of the stale delegate record and pays for the new one
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Verified by verify_stale_governance_delegate
+    pub realm: UncheckedAccount<'info>,
+    /// CHECK: The owner of the governance and realm accounts
+    #[account(address = SPL_GOVERNANCE_PROGRAM_ID @ MythicMetadataError::InvalidGovernanceProgram)]
+    pub governance_program: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            realm.key().as_ref(),
+            realm.key().as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    /// CHECK: The governance the stale delegate record was granted to, or to whose native treasury
+    /// Verified by verify_stale_governance_delegate
+    pub stale_governance: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&[metadata_metadata_key.id]),
+            stale_delegate_record.delegate.as_ref()
+        ],
+        bump = stale_delegate_record.bump,
+    )]
+    pub stale_delegate_record: Account<'info, MetadataDelegate>,
+    /// CHECK: The realm's authority, not read if the realm has no authority
+    pub governance: UncheckedAccount<'info>,
+    /// The delegate record of the realm's authority, omitted if the realm has no authority
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = MetadataDelegate::size(1),
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&[metadata_metadata_key.id]),
+            governance.key().as_ref()
+        ],
+        bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SyncGovernanceDelegate>) -> Result<()> {
+    let stale_governance = ctx.accounts.stale_governance.key();
+    let governance_program = ctx.accounts.governance_program.key();
+    let realm_authority = verify_stale_governance_delegate(
+        &ctx.accounts.stale_delegate_record.delegate,
+        &ctx.accounts.stale_governance,
+        &ctx.accounts.realm,
+        &governance_program,
+    )?;
+
+    let metadata = &mut ctx.accounts.metadata;
+    metadata.close_record();
+
+    if metadata.update_authority.is_some_and(|update_authority| {
+        update_authority == stale_governance
+            || update_authority
+                == find_native_treasury_address(&stale_governance, &governance_program)
+    }) {
+        metadata.update_authority = realm_authority;
+    }
+
+    match (realm_authority, &mut ctx.accounts.delegate_record) {
+        (Some(realm_authority), Some(delegate_record)) => {
+            require_keys_eq!(
+                ctx.accounts.governance.key(),
+                realm_authority,
+                MythicMetadataError::InvalidGovernanceAccount
+            );
+
+            // The realm's authority may still hold a record from before it lost the authority
+            if delegate_record.delegate == Pubkey::default() {
+                metadata.open_record();
+            }
+            delegate_record.set_inner(MetadataDelegate {
+                metadata: metadata.key(),
+                collection_path: vec![metadata.metadata_key_id],
+                delegate: realm_authority,
+                permissions: MetadataDelegate::ALL_PERMISSIONS,
+                bump: ctx.bumps.delegate_record.unwrap(),
+            });
+        }
+        (None, None) => {}
+        _ => return err!(ErrorCode::ConstraintAccountIsNone),
+    }
+
+    Ok(())
+}
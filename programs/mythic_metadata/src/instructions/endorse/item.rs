@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+/// Co-signs the current version of an item of the Metadata
+/// Endorsing again after the item was updated refreshes the endorsement
+#[derive(Accounts)]
+#[instruction(args: EndorseMetadataItemArgs)]
+pub struct EndorseMetadataItem<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub endorser: Signer<'info>,
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Endorsement::size(&args.collection_path),
+        seeds = [
+            PREFIX,
+            ENDORSEMENT,
+            metadata.key().as_ref(),
+            &item_seed(&args.collection_path, args.item_metadata_key_id),
+            endorser.key().as_ref()
+        ],
+        bump,
+    )]
+    pub endorsement: Account<'info, Endorsement>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<EndorseMetadataItem>, args: EndorseMetadataItemArgs) -> Result<()> {
+    let item = ctx
+        .accounts
+        .metadata
+        .item(&args.collection_path, args.item_metadata_key_id)?;
+
+    // The endorser commits to the value it reviewed, an update landing first fails the endorsement
+    require!(
+        Endorsement::value_hash(&item.value) == args.value_hash,
+        MythicMetadataError::EndorsedValueMismatch
+    );
+
+    let endorsement = &mut ctx.accounts.endorsement;
+    endorsement.metadata = ctx.accounts.metadata.key();
+    endorsement.endorser = ctx.accounts.endorser.key();
+    endorsement.item_metadata_key_id = args.item_metadata_key_id;
+    endorsement.value_hash = args.value_hash;
+    endorsement.item_update_slot = item.update_slot;
+    endorsement.collection_path = args.collection_path;
+    endorsement.bump = ctx.bumps.endorsement;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EndorseMetadataItemArgs {
+    /// The path of the collection holding the item, the Metadata's MetadataKey Id for the root items
+    pub collection_path: Vec<u64>,
+    pub item_metadata_key_id: u64,
+    /// The sha256 hash of the item value being endorsed
+    pub value_hash: [u8; 32],
+}
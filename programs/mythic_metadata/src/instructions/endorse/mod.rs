@@ -0,0 +1,5 @@
+pub mod item;
+pub mod revoke;
+
+pub use item::*;
+pub use revoke::*;
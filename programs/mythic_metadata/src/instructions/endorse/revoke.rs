@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::*;
+
+/// Closes the endorsement, the rent goes to the endorser
+#[derive(Accounts)]
+pub struct RevokeEndorsement<'info> {
+    #[account(mut)]
+    pub endorser: Signer<'info>,
+    #[account(
+        mut,
+        close = endorser,
+        has_one = endorser,
+        seeds = [
+            PREFIX,
+            ENDORSEMENT,
+            endorsement.metadata.as_ref(),
+            &item_seed(&endorsement.collection_path, endorsement.item_metadata_key_id),
+            endorser.key().as_ref()
+        ],
+        bump = endorsement.bump,
+    )]
+    pub endorsement: Account<'info, Endorsement>,
+}
+
+pub fn handler(_ctx: Context<RevokeEndorsement>) -> Result<()> {
+    Ok(())
+}
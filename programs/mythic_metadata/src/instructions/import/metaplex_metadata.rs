@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::metaplex::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Imports the name, symbol and uri of a Metaplex Metadata as root items
+/// of the Metadata issued by the Metaplex update authority about the mint
+/// Importing again refreshes the items, the rent payers of items which shrink can be passed
+/// as writable remaining accounts to refund them
+#[derive(Accounts)]
+pub struct ImportMetaplexMetadata<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The Metaplex update authority and the Metadata issuing authority
+    pub update_authority: Signer<'info>,
+    /// CHECK: The mint described by both Metadata accounts
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: Verified by MetaplexMetadata::from_account_info
+    pub metaplex_metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            update_authority.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ImportMetaplexMetadata>) -> Result<()> {
+    let metaplex_metadata = MetaplexMetadata::from_account_info(
+        &ctx.accounts.metaplex_metadata,
+        ctx.accounts.mint.key,
+    )?;
+    require_keys_eq!(
+        metaplex_metadata.update_authority,
+        ctx.accounts.update_authority.key(),
+        MythicMetadataError::Unauthorized
+    );
+
+    let metadata = &mut ctx.accounts.metadata;
+    let root_metadata_key_id = metadata.metadata_key_id;
+    let slot = Clock::get()?.slot;
+    let mut rent_shares = vec![];
+    for (metadata_key_id, value) in [
+        (METAPLEX_NAME_KEY_ID, metaplex_metadata.name),
+        (METAPLEX_SYMBOL_KEY_ID, metaplex_metadata.symbol),
+        (METAPLEX_URI_KEY_ID, metaplex_metadata.uri),
+    ] {
+        rent_shares.extend(metadata.set_item(
+            &[root_metadata_key_id],
+            MetadataItem {
+                metadata_key_id,
+                update_slot: slot,
+                value: value.into_bytes(),
+                rent_payer: ctx.accounts.payer.key(),
+                growth_rent_shares: vec![],
+            },
+        )?);
+    }
+
+    let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
+    realloc_account(
+        metadata.to_account_info(),
+        metadata_new_size,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    refund_rent(
+        &metadata.to_account_info(),
+        metadata_new_size,
+        rent_shares,
+        ctx.remaining_accounts,
+    )?;
+
+    metadata.validate()?;
+
+    Ok(())
+}
@@ -0,0 +1,3 @@
+pub mod metaplex_metadata;
+
+pub use metaplex_metadata::*;
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Appends an EVM address to the Metadata with the proof its key holder linked it to the subject
+/// The address is pushed to the list of a multi-valued MetadataKey
+#[derive(Accounts)]
+pub struct LinkEvmAddress<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = collection_metadata_key.bump,
+    )]
+    pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        constraint = item_metadata_key.content_type == EVM_ADDRESS_CONTENT_TYPE @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &item_metadata_key.id.to_le_bytes()
+        ],
+        bump = item_metadata_key.bump,
+    )]
+    pub item_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<LinkEvmAddress>, args: LinkEvmAddressArgs) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    let collection_path =
+        collection_path(&args.parent_path, ctx.accounts.collection_metadata_key.id);
+    let item_metadata_key = &ctx.accounts.item_metadata_key;
+
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &collection_path,
+        MetadataDelegate::APPEND,
+    )?;
+
+    let link = EvmAddressLink {
+        address: args.address,
+        signature: args.signature,
+        recovery_id: args.recovery_id,
+    };
+    link.verify(&metadata.subject)?;
+
+    let slot = Clock::get()?.slot;
+    if item_metadata_key.multi_valued {
+        verify_not_timelocked(metadata, &collection_path)?;
+        metadata.push_list_value(
+            &collection_path,
+            item_metadata_key.id,
+            link.try_to_vec()?,
+            slot,
+            ctx.accounts.payer.key(),
+        )?;
+    } else {
+        metadata.append_item(
+            &collection_path,
+            MetadataItem {
+                metadata_key_id: item_metadata_key.id,
+                update_slot: slot,
+                value: link.try_to_vec()?,
+                rent_payer: ctx.accounts.payer.key(),
+                growth_rent_shares: vec![],
+            },
+        )?;
+    }
+
+    let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
+    realloc_account(
+        metadata.to_account_info(),
+        metadata_new_size,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    metadata.validate()?;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LinkEvmAddressArgs {
+    pub address: [u8; 20],
+    /// The r and s values of the personal_sign signature of EvmAddressLink::message
+    pub signature: [u8; 64],
+    /// The signature's v value minus 27
+    pub recovery_id: u8,
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
+}
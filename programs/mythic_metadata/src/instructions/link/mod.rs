@@ -0,0 +1,3 @@
+pub mod evm_address;
+
+pub use evm_address::*;
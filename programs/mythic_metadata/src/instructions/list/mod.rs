@@ -0,0 +1,11 @@
+//! Instructions changing the values of multi-valued items, see MetadataKey.multi_valued
+
+pub mod clear;
+pub mod push;
+pub mod remove;
+pub mod replace;
+
+pub use clear::*;
+pub use push::*;
+pub use remove::*;
+pub use replace::*;
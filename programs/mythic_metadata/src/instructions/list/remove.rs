@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// The rent of the bytes the list shrinks by is refunded to the rent payers of the item,
+/// passed as writable remaining accounts
+#[derive(Accounts)]
+pub struct RemoveMetadataListValue<'info> {
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = collection_metadata_key.bump,
+    )]
+    pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &item_metadata_key.id.to_le_bytes()
+        ],
+        bump = item_metadata_key.bump,
+        constraint = item_metadata_key.multi_valued @ MythicMetadataError::MetadataKeyNotMultiValued,
+    )]
+    pub item_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+}
+
+pub fn handler(
+    ctx: Context<RemoveMetadataListValue>,
+    args: RemoveMetadataListValueArgs,
+) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    let collection_metadata_key = &ctx.accounts.collection_metadata_key;
+    let collection_path = collection_path(&args.parent_path, collection_metadata_key.id);
+    let item_metadata_key = &ctx.accounts.item_metadata_key;
+
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &collection_path,
+        MetadataDelegate::REMOVE,
+    )?;
+
+    verify_not_timelocked(metadata, &collection_path)?;
+
+    let rent_shares = metadata.remove_list_value(
+        &collection_path,
+        item_metadata_key.id,
+        args.index,
+        Clock::get()?.slot,
+    )?;
+
+    metadata.validate()?;
+
+    // The rent of the bytes the list shrunk by is refunded
+    let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
+    refund_rent(
+        &metadata.to_account_info(),
+        metadata_new_size,
+        rent_shares,
+        ctx.remaining_accounts,
+    )?;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveMetadataListValueArgs {
+    pub index: u16,
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// The update authority funds the growth of the list, the rent payers of the item passed as
+/// writable remaining accounts are refunded when it shrinks
+#[derive(Accounts)]
+pub struct ReplaceMetadataListValue<'info> {
+    /// The collection update authority, the Metadata update authority or a delegate
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = collection_metadata_key.bump,
+    )]
+    pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &item_metadata_key.id.to_le_bytes()
+        ],
+        bump = item_metadata_key.bump,
+        constraint = item_metadata_key.multi_valued @ MythicMetadataError::MetadataKeyNotMultiValued,
+    )]
+    pub item_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            update_authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+    /// The MultisigAuthority the update authority signs for
+    /// The other signers of the MultisigAuthority sign as remaining accounts
+    pub multisig_authority: Option<Account<'info, MultisigAuthority>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ReplaceMetadataListValue>,
+    args: ReplaceMetadataListValueArgs,
+) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    let collection_metadata_key = &ctx.accounts.collection_metadata_key;
+    let collection_path = collection_path(&args.parent_path, collection_metadata_key.id);
+    let item_metadata_key = &ctx.accounts.item_metadata_key;
+
+    verify_item_update_authority(
+        metadata,
+        &collection_path,
+        &MultisigSigners::new(
+            ctx.accounts.update_authority.key(),
+            ctx.accounts.multisig_authority.as_ref(),
+            ctx.remaining_accounts,
+        ),
+        ctx.accounts.delegate_record.as_deref(),
+    )?;
+
+    verify_not_timelocked(metadata, &collection_path)?;
+
+    validate_value(&item_metadata_key.content_type, &args.new_value)?;
+
+    let rent_shares = metadata.replace_list_value(
+        &collection_path,
+        item_metadata_key.id,
+        args.index,
+        args.new_value,
+        Clock::get()?.slot,
+        ctx.accounts.update_authority.key(),
+    )?;
+
+    let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
+    realloc_account(
+        metadata.to_account_info(),
+        metadata_new_size,
+        ctx.accounts.update_authority.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    refund_rent(
+        &metadata.to_account_info(),
+        metadata_new_size,
+        rent_shares,
+        ctx.remaining_accounts,
+    )?;
+
+    metadata.validate()?;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ReplaceMetadataListValueArgs {
+    pub index: u16,
+    pub new_value: Vec<u8>,
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::utils::*;
+
+/// Rewrites a Metadata account created in the legacy layout in the current layout, see [LegacyMetadata]
+/// Anyone can migrate a Metadata, the payer funds the growth of the account
+#[derive(Accounts)]
+pub struct MigrateMetadata<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Deserialized by LegacyMetadata::try_from_account_data
+    #[account(mut, owner = crate::ID)]
+    pub metadata: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateMetadata>) -> Result<()> {
+    let metadata_info = ctx.accounts.metadata.to_account_info();
+    let metadata =
+        LegacyMetadata::try_from_account_data(&metadata_info.try_borrow_data()?)?.into_metadata();
+    metadata.validate()?;
+
+    realloc_account(
+        metadata_info.clone(),
+        Metadata::size(&metadata.items, &metadata.collections),
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let mut data = metadata_info.try_borrow_mut_data()?;
+    metadata.try_serialize(&mut &mut data[..])
+}
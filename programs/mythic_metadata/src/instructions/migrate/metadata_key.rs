@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::utils::*;
+
+/// Rewrites a MetadataKey account created in the legacy layout in the current layout,
+/// see [LegacyMetadataKey]
+/// Anyone can migrate a MetadataKey, the payer funds the growth of the account
+#[derive(Accounts)]
+pub struct MigrateMetadataKey<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Deserialized by LegacyMetadataKey::try_from_account_data
+    #[account(mut, owner = crate::ID)]
+    pub metadata_key: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateMetadataKey>) -> Result<()> {
+    let metadata_key_info = ctx.accounts.metadata_key.to_account_info();
+    let metadata_key =
+        LegacyMetadataKey::try_from_account_data(&metadata_key_info.try_borrow_data()?)?
+            .into_metadata_key();
+
+    realloc_account(
+        metadata_key_info.clone(),
+        MetadataKey::size(
+            &metadata_key.name,
+            &metadata_key.label,
+            &metadata_key.description,
+            &metadata_key.content_type,
+        ),
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let mut data = metadata_key_info.try_borrow_mut_data()?;
+    metadata_key.try_serialize(&mut &mut data[..])
+}
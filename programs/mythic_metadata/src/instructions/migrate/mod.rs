@@ -0,0 +1,5 @@
+pub mod metadata;
+pub mod metadata_key;
+
+pub use metadata::*;
+pub use metadata_key::*;
@@ -1,11 +1,33 @@
 pub mod append;
+pub mod attest;
 pub mod auth;
+pub mod close;
 pub mod create;
+pub mod delegate;
+pub mod endorse;
+pub mod import;
+pub mod link;
+pub mod list;
+pub mod migrate;
+pub mod registry;
 pub mod remove;
+pub mod restructure;
+pub mod timelock;
 pub mod update;
 
 pub use append::*;
+pub use attest::*;
 pub use auth::*;
+pub use close::*;
 pub use create::*;
+pub use delegate::*;
+pub use endorse::*;
+pub use import::*;
+pub use link::*;
+pub use list::*;
+pub use migrate::*;
+pub use registry::*;
 pub use remove::*;
+pub use restructure::*;
+pub use timelock::*;
 pub use update::*;
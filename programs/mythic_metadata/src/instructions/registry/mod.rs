@@ -0,0 +1,3 @@
+pub mod register_metadata;
+
+pub use register_metadata::*;
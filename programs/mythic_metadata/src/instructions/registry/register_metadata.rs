@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Lists an existing Metadata in the registry of its subject
+/// Anyone can register a Metadata, e.g. one created by the token metadata interface
+#[derive(Accounts)]
+pub struct RegisterMetadata<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SubjectRegistry::size(),
+        seeds = [PREFIX, SUBJECT_REGISTRY, metadata.subject.as_ref()],
+        bump,
+    )]
+    pub subject_registry: Account<'info, SubjectRegistry>,
+    /// The page the Metadata is listed in, see SubjectRegistry::next_page_index
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SubjectRegistryPage::size(0),
+        seeds = [
+            PREFIX,
+            SUBJECT_REGISTRY,
+            metadata.subject.as_ref(),
+            &subject_registry.next_page_index().to_le_bytes()
+        ],
+        bump,
+    )]
+    pub subject_registry_page: Account<'info, SubjectRegistryPage>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterMetadata>) -> Result<()> {
+    register_subject_metadata(
+        &mut ctx.accounts.subject_registry,
+        ctx.bumps.subject_registry,
+        &mut ctx.accounts.subject_registry_page,
+        ctx.bumps.subject_registry_page,
+        &mut ctx.accounts.metadata,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )
+}
@@ -3,10 +3,12 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::*;
 use crate::state::*;
+use crate::utils::*;
 
 #[derive(Accounts)]
 pub struct RemoveMetadataCollection<'info> {
-    pub issuing_authority: Signer<'info>,
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
     #[account(
         mut,
         constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
@@ -14,11 +16,10 @@ pub struct RemoveMetadataCollection<'info> {
             PREFIX,
             METADATA,
             metadata_metadata_key.key().as_ref(),
-            issuing_authority.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
             metadata.subject.as_ref()
         ],
         bump = metadata.bump,
-        has_one = issuing_authority
     )]
     pub metadata: Account<'info, Metadata>,
     #[account(
@@ -39,20 +40,52 @@ pub struct RemoveMetadataCollection<'info> {
         bump = collection_metadata_key.bump,
     )]
     pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
 }
 
-pub fn handler(ctx: Context<RemoveMetadataCollection>) -> Result<()> {
+pub fn handler(
+    ctx: Context<RemoveMetadataCollection>,
+    args: RemoveMetadataCollectionArgs,
+) -> Result<()> {
     let metadata = &mut ctx.accounts.metadata;
     let collection_metadata_key = &ctx.accounts.collection_metadata_key;
+    let collection_path = collection_path(&args.parent_path, collection_metadata_key.id);
 
-    match metadata
-        .collections
-        .binary_search_by_key(&collection_metadata_key.id, |collection| {
-            collection.metadata_key_id
-        }) {
-        Ok(collection_index) => metadata.collections.remove(collection_index),
-        Err(_) => return err!(MythicMetadataError::MetadataCollectionNonExistent),
-    };
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &collection_path,
+        MetadataDelegate::MANAGE_COLLECTIONS,
+    )?;
+
+    let removed_collections = metadata.remove_collection(&collection_path)?;
+
+    let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
+    refund_rent(
+        &metadata.to_account_info(),
+        metadata_new_size,
+        removed_collections
+            .iter()
+            .flat_map(MetadataCollection::rent_shares),
+        ctx.remaining_accounts,
+    )?;
 
     Ok(())
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveMetadataCollectionArgs {
+    /// The MetadataKey Ids of the ancestor collections, empty for a top level collection
+    pub parent_path: Vec<u64>,
+}
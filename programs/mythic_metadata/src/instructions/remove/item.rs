@@ -5,9 +5,11 @@ use crate::errors::*;
 use crate::state::*;
 use crate::utils::*;
 
+/// The rent of the item is refunded to its rent payers, passed as writable remaining accounts
 #[derive(Accounts)]
 pub struct RemoveMetadataItem<'info> {
-    pub issuing_authority: Signer<'info>,
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
     #[account(
         mut,
         constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
@@ -15,11 +17,10 @@ pub struct RemoveMetadataItem<'info> {
             PREFIX,
             METADATA,
             metadata_metadata_key.key().as_ref(),
-            issuing_authority.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
             metadata.subject.as_ref()
         ],
         bump = metadata.bump,
-        has_one = issuing_authority
     )]
     pub metadata: Account<'info, Metadata>,
     #[account(
@@ -49,52 +50,50 @@ pub struct RemoveMetadataItem<'info> {
         bump = item_metadata_key.bump,
     )]
     pub item_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
 }
 
-pub fn handler(ctx: Context<RemoveMetadataItem>) -> Result<()> {
+pub fn handler(ctx: Context<RemoveMetadataItem>, args: RemoveMetadataItemArgs) -> Result<()> {
     let metadata = &mut ctx.accounts.metadata;
-    let metadata_metadata_key = &ctx.accounts.metadata_metadata_key;
     let collection_metadata_key = &ctx.accounts.collection_metadata_key;
+    let collection_path = collection_path(&args.parent_path, collection_metadata_key.id);
     let item_metadata_key = &ctx.accounts.item_metadata_key;
 
-    // Check if metadata item is to be removed in root collection
-    if check_collection_metadata_equality(metadata_metadata_key, collection_metadata_key) {
-        match metadata
-            .items
-            .binary_search_by_key(&item_metadata_key.id, |item| item.metadata_key_id)
-        {
-            Ok(item_index) => metadata.items.remove(item_index),
-            Err(_) => return err!(MythicMetadataError::MetadataItemNonExistent),
-        };
-    } else {
-        match metadata
-            .collections
-            .binary_search_by_key(&collection_metadata_key.id, |collection| {
-                collection.metadata_key_id
-            }) {
-            Ok(collection_index) => {
-                let mut collection = metadata.collections.get_mut(collection_index).unwrap().clone();
-                
-                match collection
-                    .items
-                    .binary_search_by_key(&item_metadata_key.id, |item| item.metadata_key_id)
-                {
-                    Ok(item_index) => {
-                        collection.items.remove(item_index);
-                        metadata.collections.remove(collection_index);
-                        metadata.collections.insert(collection_index, collection);
-                    }
-                    Err(_) => return err!(MythicMetadataError::MetadataItemNonExistent),
-                };
-            }
-            Err(_) => return err!(MythicMetadataError::MetadataCollectionNonExistent)
-        }
-    }
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &collection_path,
+        MetadataDelegate::REMOVE,
+    )?;
+
+    verify_not_timelocked(metadata, &collection_path)?;
+
+    let item = metadata.remove_item(&collection_path, item_metadata_key.id)?;
+
+    let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
+    refund_rent(
+        &metadata.to_account_info(),
+        metadata_new_size,
+        item.rent_shares(),
+        ctx.remaining_accounts,
+    )?;
 
     Ok(())
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RemoveMetadataItemArgs {
-    pub value: Vec<u8>,
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
 }
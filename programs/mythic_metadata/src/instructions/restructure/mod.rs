@@ -0,0 +1,7 @@
+//! Instructions restructuring the collections and items of a Metadata in place
+
+pub mod move_item;
+pub mod rekey_collection;
+
+pub use move_item::*;
+pub use rekey_collection::*;
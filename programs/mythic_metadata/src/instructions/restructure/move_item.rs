@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct MoveMetadataItem<'info> {
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    /// The collection the item is moved from
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &source_collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = source_collection_metadata_key.bump,
+    )]
+    pub source_collection_metadata_key: Account<'info, MetadataKey>,
+    /// The collection the item is moved to
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &destination_collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = destination_collection_metadata_key.bump,
+    )]
+    pub destination_collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &item_metadata_key.id.to_le_bytes()
+        ],
+        bump = item_metadata_key.bump,
+    )]
+    pub item_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+}
+
+pub fn handler(ctx: Context<MoveMetadataItem>, args: MoveMetadataItemArgs) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    let source_collection_path = collection_path(
+        &args.source_parent_path,
+        ctx.accounts.source_collection_metadata_key.id,
+    );
+    let destination_collection_path = collection_path(
+        &args.destination_parent_path,
+        ctx.accounts.destination_collection_metadata_key.id,
+    );
+
+    // Moving removes the item from the source and appends it to the destination
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &source_collection_path,
+        MetadataDelegate::REMOVE,
+    )?;
+    verify_issuing_authority(
+        metadata,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.delegate_record.as_deref(),
+        &destination_collection_path,
+        MetadataDelegate::APPEND,
+    )?;
+
+    verify_not_timelocked(metadata, &source_collection_path)?;
+    verify_not_timelocked(metadata, &destination_collection_path)?;
+
+    metadata.move_item(
+        &source_collection_path,
+        &destination_collection_path,
+        ctx.accounts.item_metadata_key.id,
+        Clock::get()?.slot,
+    )?;
+
+    metadata.validate()?;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MoveMetadataItemArgs {
+    /// The MetadataKey Ids of the ancestor collections of the source collection
+    pub source_parent_path: Vec<u64>,
+    /// The MetadataKey Ids of the ancestor collections of the destination collection
+    pub destination_parent_path: Vec<u64>,
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct RekeyMetadataCollection<'info> {
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = collection_metadata_key.bump,
+    )]
+    pub collection_metadata_key: Account<'info, MetadataKey>,
+    /// The new MetadataKey of the collection
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &new_collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = new_collection_metadata_key.bump,
+    )]
+    pub new_collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+}
+
+/// Delegate records, pending changes and authority transfers are keyed by collection paths and
+/// would be left keyed by the previous path, so they must be closed before rekeying
+pub fn handler(
+    ctx: Context<RekeyMetadataCollection>,
+    args: RekeyMetadataCollectionArgs,
+) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    let authority = ctx.accounts.authority.key();
+
+    // A delegate authorizing both paths is scoped to an ancestor and stays valid
+    metadata.verify_records_closed(u32::from(
+        ctx.accounts.delegate_record.is_some() && metadata.issuing_authority != authority,
+    ))?;
+
+    let collection_path =
+        collection_path(&args.parent_path, ctx.accounts.collection_metadata_key.id);
+    let new_collection_metadata_key_id = ctx.accounts.new_collection_metadata_key.id;
+
+    for collection_path in [
+        collection_path.clone(),
+        [
+            args.parent_path.as_slice(),
+            &[new_collection_metadata_key_id],
+        ]
+        .concat(),
+    ] {
+        verify_issuing_authority(
+            metadata,
+            &authority,
+            ctx.accounts.delegate_record.as_deref(),
+            &collection_path,
+            MetadataDelegate::MANAGE_COLLECTIONS,
+        )?;
+    }
+
+    metadata.rekey_collection(
+        &collection_path,
+        new_collection_metadata_key_id,
+        Clock::get()?.slot,
+    )?;
+
+    metadata.validate()?;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RekeyMetadataCollectionArgs {
+    /// The MetadataKey Ids of the ancestor collections, empty for a top level collection
+    pub parent_path: Vec<u64>,
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Cancels the PendingChange, the rent is refunded to its payer
+#[derive(Accounts)]
+pub struct CancelMetadataChange<'info> {
+    /// The Metadata update authority
+    pub update_authority: Signer<'info>,
+    #[account(mut)]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        mut,
+        close = payer,
+        has_one = metadata,
+        has_one = payer,
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+    /// CHECK: The payer of the PendingChange rent
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    /// The MultisigAuthority the update authority signs for
+    /// The other signers of the MultisigAuthority sign as remaining accounts
+    pub multisig_authority: Option<Account<'info, MultisigAuthority>>,
+}
+
+pub fn handler(ctx: Context<CancelMetadataChange>) -> Result<()> {
+    require!(
+        verify_metadata_update_authority(
+            &ctx.accounts.metadata,
+            &MultisigSigners::new(
+                ctx.accounts.update_authority.key(),
+                ctx.accounts.multisig_authority.as_ref(),
+                ctx.remaining_accounts,
+            ),
+        )?,
+        MythicMetadataError::Unauthorized
+    );
+
+    ctx.accounts.metadata.close_record();
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Applies the PendingChange once its timelock elapsed, anyone can crank it
+/// The rent of the PendingChange is refunded to its payer, the rent of the removed or shrunk item
+/// to its rent payers passed as writable remaining accounts
+#[derive(Accounts)]
+pub struct ExecuteMetadataChange<'info> {
+    /// Pays for the growth of the Metadata account the lamports prepaid with the PendingChange
+    /// don't cover, i.e. when the Metadata changed since the change was queued
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(mut)]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        mut,
+        close = payer,
+        has_one = metadata,
+        has_one = payer,
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+    /// CHECK: The payer of the PendingChange rent
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExecuteMetadataChange>) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    let pending_change = &ctx.accounts.pending_change;
+    let slot = Clock::get()?.slot;
+
+    require!(
+        slot >= pending_change.executable_slot,
+        MythicMetadataError::TimelockNotElapsed
+    );
+
+    let rent_shares = match &pending_change.change {
+        MetadataChange::Update { new_value } => metadata.update_item(
+            &pending_change.collection_path,
+            MetadataItem {
+                metadata_key_id: pending_change.item_metadata_key_id,
+                update_slot: slot,
+                value: new_value.clone(),
+                // The growth is funded by the lamports prepaid with the PendingChange
+                rent_payer: pending_change.payer,
+                growth_rent_shares: vec![],
+            },
+        )?,
+        MetadataChange::Remove => metadata
+            .remove_item(
+                &pending_change.collection_path,
+                pending_change.item_metadata_key_id,
+            )?
+            .rent_shares()
+            .collect(),
+    };
+
+    let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
+    let metadata_info = metadata.to_account_info();
+    let prepaid_lamports = Rent::get()?
+        .minimum_balance(metadata_new_size)
+        .saturating_sub(metadata_info.lamports())
+        .min(pending_change.prepaid_lamports);
+    if prepaid_lamports > 0 {
+        **pending_change.to_account_info().try_borrow_mut_lamports()? -= prepaid_lamports;
+        **metadata_info.try_borrow_mut_lamports()? += prepaid_lamports;
+    }
+    realloc_account(
+        metadata.to_account_info(),
+        metadata_new_size,
+        ctx.accounts.cranker.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+    refund_rent(
+        &metadata.to_account_info(),
+        metadata_new_size,
+        rent_shares,
+        ctx.remaining_accounts,
+    )?;
+
+    metadata.close_record();
+
+    metadata.validate()?;
+
+    Ok(())
+}
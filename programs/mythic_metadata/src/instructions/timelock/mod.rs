@@ -0,0 +1,9 @@
+pub mod cancel;
+pub mod execute;
+pub mod queue;
+pub mod set;
+
+pub use cancel::*;
+pub use execute::*;
+pub use queue::*;
+pub use set::*;
@@ -0,0 +1,180 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Queues an update or a removal of an item of a timelocked collection
+/// Updates are authorized like update_metadata_item and removals like remove_metadata_item
+/// The payer prepays the growth of the Metadata account by an update on top of the rent
+/// Pushing list values and moving items are rejected for timelocked collections,
+/// a list is changed by queueing an update of the whole list instead
+#[derive(Accounts)]
+#[instruction(args: QueueMetadataChangeArgs)]
+pub struct QueueMetadataChange<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// An update authority or a delegate for updates, the issuing authority or a delegate for removals
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = collection_metadata_key.bump,
+    )]
+    pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &item_metadata_key.id.to_le_bytes()
+        ],
+        bump = item_metadata_key.bump,
+    )]
+    pub item_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        init,
+        payer = payer,
+        space = PendingChange::size(args.parent_path.len() + 1, &args.change),
+        seeds = [
+            PREFIX,
+            PENDING_CHANGE,
+            metadata.key().as_ref(),
+            &item_seed(
+                &collection_path(&args.parent_path, collection_metadata_key.id),
+                item_metadata_key.id
+            )
+        ],
+        bump,
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+    /// The MultisigAuthority the authority signs for updates for
+    /// The other signers of the MultisigAuthority sign as remaining accounts
+    pub multisig_authority: Option<Account<'info, MultisigAuthority>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<QueueMetadataChange>, args: QueueMetadataChangeArgs) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    let collection_path =
+        collection_path(&args.parent_path, ctx.accounts.collection_metadata_key.id);
+    let item_metadata_key = &ctx.accounts.item_metadata_key;
+
+    let item = metadata.item(&collection_path, item_metadata_key.id)?;
+    require!(
+        !metadata.is_frozen(&collection_path)?,
+        MythicMetadataError::MetadataCollectionFrozen
+    );
+
+    match &args.change {
+        MetadataChange::Update { new_value } => {
+            verify_item_update_authority(
+                metadata,
+                &collection_path,
+                &MultisigSigners::new(
+                    ctx.accounts.authority.key(),
+                    ctx.accounts.multisig_authority.as_ref(),
+                    ctx.remaining_accounts,
+                ),
+                ctx.accounts.delegate_record.as_deref(),
+            )?;
+
+            validate_item_value(item_metadata_key, new_value)?;
+        }
+        MetadataChange::Remove => verify_issuing_authority(
+            metadata,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.delegate_record.as_deref(),
+            &collection_path,
+            MetadataDelegate::REMOVE,
+        )?,
+    }
+
+    let executable_slot = Clock::get()?
+        .slot
+        .checked_add(metadata.timelock_slots(&collection_path)?)
+        .ok_or(MythicMetadataError::TimelockOverflow)?;
+
+    // The permissionless cranker mustn't pay for the growth of the Metadata account
+    let prepaid_lamports = match &args.change {
+        MetadataChange::Update { new_value } => {
+            let metadata_size = metadata.to_account_info().data_len();
+            let rent = Rent::get()?;
+            rent.minimum_balance(metadata_size + new_value.len().saturating_sub(item.value.len()))
+                .saturating_sub(rent.minimum_balance(metadata_size))
+        }
+        MetadataChange::Remove => 0,
+    };
+    if prepaid_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.pending_change.to_account_info(),
+                },
+            ),
+            prepaid_lamports,
+        )?;
+    }
+
+    metadata.open_record();
+
+    let pending_change = &mut ctx.accounts.pending_change;
+    pending_change.metadata = metadata.key();
+    pending_change.collection_path = collection_path;
+    pending_change.item_metadata_key_id = item_metadata_key.id;
+    pending_change.change = args.change;
+    pending_change.payer = ctx.accounts.payer.key();
+    pending_change.prepaid_lamports = prepaid_lamports;
+    pending_change.executable_slot = executable_slot;
+    pending_change.bump = ctx.bumps.pending_change;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QueueMetadataChangeArgs {
+    pub change: MetadataChange,
+    /// The MetadataKey Ids of the ancestor collections, empty for top level collections
+    pub parent_path: Vec<u64>,
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Sets the number of slots updates and removals of the collection items are queued for
+/// Delegates can only lengthen the timelock, shortening it takes the issuing authority
+#[derive(Accounts)]
+pub struct SetCollectionTimelock<'info> {
+    /// The issuing authority or a delegate
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = collection_metadata_key.bump,
+    )]
+    pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+}
+
+pub fn handler(ctx: Context<SetCollectionTimelock>, args: SetCollectionTimelockArgs) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    let collection_path =
+        collection_path(&args.parent_path, ctx.accounts.collection_metadata_key.id);
+    let authority = ctx.accounts.authority.key();
+
+    verify_issuing_authority(
+        metadata,
+        &authority,
+        ctx.accounts.delegate_record.as_deref(),
+        &collection_path,
+        MetadataDelegate::MANAGE_AUTHORITIES,
+    )?;
+
+    // A compromised delegate mustn't be able to lift the timelock protecting against it
+    let collection_index = metadata.collection_index(&collection_path)?;
+    require!(
+        metadata.issuing_authority.eq(&authority)
+            || args.timelock_slots >= metadata.collections[collection_index].timelock_slots,
+        MythicMetadataError::Unauthorized
+    );
+
+    metadata.set_timelock(&collection_path, args.timelock_slots)?;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetCollectionTimelockArgs {
+    pub timelock_slots: u64,
+    /// The MetadataKey Ids of the ancestor collections, empty for top level collections
+    pub parent_path: Vec<u64>,
+}
@@ -5,8 +5,11 @@ use crate::errors::*;
 use crate::state::*;
 use crate::utils::*;
 
+/// The update authority funds the growth of the item, when it shrinks the rent is refunded to
+/// the rent payers of the item passed as writable remaining accounts, next to any multisig signers
 #[derive(Accounts)]
 pub struct UpdateMetadataItem<'info> {
+    /// The collection update authority, the Metadata update authority or a delegate
     #[account(mut)]
     pub update_authority: Signer<'info>,
     #[account(
@@ -49,68 +52,54 @@ pub struct UpdateMetadataItem<'info> {
         bump = item_metadata_key.bump,
     )]
     pub item_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            update_authority.key().as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+    /// The MultisigAuthority the update authority signs for
+    /// The other signers of the MultisigAuthority sign as remaining accounts
+    pub multisig_authority: Option<Account<'info, MultisigAuthority>>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<UpdateMetadataItem>, args: UpdateMetadataItemArgs) -> Result<()> {
     let metadata = &mut ctx.accounts.metadata;
-    let metadata_metadata_key = &ctx.accounts.metadata_metadata_key;
     let collection_metadata_key = &ctx.accounts.collection_metadata_key;
+    let collection_path = collection_path(&args.parent_path, collection_metadata_key.id);
     let item_metadata_key = &ctx.accounts.item_metadata_key;
-    let update_authority = &ctx.accounts.update_authority;
 
-    // Check if metadata item is to be updated in main metadata
-    if check_collection_metadata_equality(metadata_metadata_key, collection_metadata_key) {
-        verify_metadata_update_authority(&metadata, &update_authority.key())?;
+    verify_item_update_authority(
+        metadata,
+        &collection_path,
+        &MultisigSigners::new(
+            ctx.accounts.update_authority.key(),
+            ctx.accounts.multisig_authority.as_ref(),
+            ctx.remaining_accounts,
+        ),
+        ctx.accounts.delegate_record.as_deref(),
+    )?;
 
-        match metadata
-            .items
-            .binary_search_by_key(&item_metadata_key.id, |item| item.metadata_key_id)
-        {
-            Ok(item_index) => {
-                let slot = Clock::get()?.slot;
-                metadata.items.remove(item_index);
-                metadata.update_slot = slot;
-                metadata.items.insert(
-                    item_index,
-                    MetadataItem {
-                        metadata_key_id: item_metadata_key.id,
-                        update_slot: slot,
-                        value: args.new_value,
-                    },
-                );
-            }
-            Err(_) => return err!(MythicMetadataError::MetadataItemNonExistent),
-        };
-    } else {
-        let (collection_index, mut collection) = verify_collection_update_authority(
-            &metadata,
-            collection_metadata_key.id,
-            &update_authority.key(),
-        )?;
+    verify_not_timelocked(metadata, &collection_path)?;
 
-        match collection
-            .items
-            .binary_search_by_key(&item_metadata_key.id, |item| item.metadata_key_id)
-        {
-            Ok(item_index) => {
-                let slot = Clock::get()?.slot;
-                collection.update_slot = slot;
-                collection.items.remove(item_index);
-                collection.items.insert(
-                    item_index,
-                    MetadataItem {
-                        metadata_key_id: item_metadata_key.id,
-                        update_slot: slot,
-                        value: args.new_value,
-                    },
-                );
-                metadata.collections.remove(collection_index);
-                metadata.collections.insert(collection_index, collection);
-            }
-            Err(_) => return err!(MythicMetadataError::MetadataItemNonExistent),
-        };
-    }
+    validate_item_value(item_metadata_key, &args.new_value)?;
+
+    let rent_shares = metadata.update_item(
+        &collection_path,
+        MetadataItem {
+            metadata_key_id: item_metadata_key.id,
+            update_slot: Clock::get()?.slot,
+            value: args.new_value,
+            rent_payer: ctx.accounts.update_authority.key(),
+            growth_rent_shares: vec![],
+        },
+    )?;
 
     let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
     realloc_account(
@@ -120,6 +109,13 @@ pub fn handler(ctx: Context<UpdateMetadataItem>, args: UpdateMetadataItemArgs) -
         ctx.accounts.system_program.to_account_info(),
     )?;
 
+    refund_rent(
+        &metadata.to_account_info(),
+        metadata_new_size,
+        rent_shares,
+        ctx.remaining_accounts,
+    )?;
+
     metadata.validate()?;
 
     Ok(())
@@ -128,4 +124,6 @@ pub fn handler(ctx: Context<UpdateMetadataItem>, args: UpdateMetadataItemArgs) -
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct UpdateMetadataItemArgs {
     pub new_value: Vec<u8>,
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
 }
@@ -1,3 +1,5 @@
 pub mod item;
+pub mod relayed_item;
 
 pub use item::*;
+pub use relayed_item::*;
@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::signature::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// Updates an item with the new value signed off-chain by an update authority of the item,
+/// which doesn't sign the transaction. A relayer pays the fees and the rent
+///
+/// The instruction must be preceded by an ed25519 program instruction verifying the update
+/// authority's signature of the SignedItemUpdate message. A MultisigAuthority signs with the
+/// signatures of enough of its signers in the same ed25519 instruction. Replays are blocked by
+/// the message's nonce, which must exceed the last nonce of the update authority, and its expiry slot
+///
+/// The relayer funds the growth of the item, the rent payers of the item passed as writable
+/// remaining accounts are refunded when it shrinks
+#[derive(Accounts)]
+#[instruction(args: RelayUpdateMetadataItemArgs)]
+pub struct RelayUpdateMetadataItem<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = metadata.metadata_key_id.eq(&metadata_metadata_key.id) @ MythicMetadataError::InvalidMetadataKey,
+        seeds = [
+            PREFIX,
+            METADATA,
+            metadata_metadata_key.key().as_ref(),
+            metadata.issuing_authority.as_ref(),
+            metadata.subject.as_ref()
+        ],
+        bump = metadata.bump,
+    )]
+    pub metadata: Account<'info, Metadata>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &metadata_metadata_key.id.to_le_bytes()
+        ],
+        bump = metadata_metadata_key.bump,
+    )]
+    pub metadata_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &collection_metadata_key.id.to_le_bytes()
+        ],
+        bump = collection_metadata_key.bump,
+    )]
+    pub collection_metadata_key: Account<'info, MetadataKey>,
+    #[account(
+        seeds = [
+            PREFIX,
+            METADATA_KEY,
+            &item_metadata_key.id.to_le_bytes()
+        ],
+        bump = item_metadata_key.bump,
+    )]
+    pub item_metadata_key: Account<'info, MetadataKey>,
+    /// The delegate record of the signer if it updates the item as a delegate
+    #[account(
+        seeds = [
+            PREFIX,
+            DELEGATE,
+            metadata.key().as_ref(),
+            &path_seed(&delegate_record.collection_path),
+            delegate_record.delegate.as_ref()
+        ],
+        bump = delegate_record.bump,
+    )]
+    pub delegate_record: Option<Account<'info, MetadataDelegate>>,
+    /// The MultisigAuthority the signers of the message sign for
+    pub multisig_authority: Option<Account<'info, MultisigAuthority>>,
+    /// The nonce record of the update authority
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = SignerNonce::size(),
+        seeds = [
+            PREFIX,
+            NONCE,
+            ITEM_UPDATE_DOMAIN,
+            metadata.key().as_ref(),
+            args.update_authority.as_ref()
+        ],
+        bump,
+    )]
+    pub signer_nonce: Account<'info, SignerNonce>,
+    /// CHECK: The instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<RelayUpdateMetadataItem>,
+    args: RelayUpdateMetadataItemArgs,
+) -> Result<()> {
+    let signatures = Ed25519Signature::load_preceding_all(&ctx.accounts.instructions_sysvar)?;
+
+    let metadata = &mut ctx.accounts.metadata;
+    let collection_path =
+        collection_path(&args.parent_path, ctx.accounts.collection_metadata_key.id);
+    let item_metadata_key = &ctx.accounts.item_metadata_key;
+
+    let update = SignedItemUpdate {
+        metadata: metadata.key(),
+        update_authority: args.update_authority,
+        collection_path,
+        item_metadata_key_id: item_metadata_key.id,
+        new_value: args.new_value,
+        nonce: args.nonce,
+        expiry_slot: args.expiry_slot,
+    };
+    let slot = Clock::get()?.slot;
+    let update_authority = MultisigSigners::from_message_signers(
+        verify_signed_messages(&signatures, &update.message(), update.expiry_slot, slot)?,
+        ctx.accounts.multisig_authority.as_ref(),
+    )?;
+    require!(
+        update_authority.is(&args.update_authority),
+        MythicMetadataError::Unauthorized
+    );
+    verify_item_update_authority(
+        metadata,
+        &update.collection_path,
+        &update_authority,
+        ctx.accounts.delegate_record.as_deref(),
+    )?;
+
+    verify_not_timelocked(metadata, &update.collection_path)?;
+    consume_signer_nonce(
+        &mut ctx.accounts.signer_nonce,
+        ctx.bumps.signer_nonce,
+        metadata.key(),
+        args.update_authority,
+        update.nonce,
+    )?;
+
+    validate_item_value(item_metadata_key, &update.new_value)?;
+
+    let rent_shares = metadata.update_item(
+        &update.collection_path,
+        MetadataItem {
+            metadata_key_id: item_metadata_key.id,
+            update_slot: slot,
+            value: update.new_value,
+            rent_payer: ctx.accounts.relayer.key(),
+            growth_rent_shares: vec![],
+        },
+    )?;
+
+    let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
+    realloc_account(
+        metadata.to_account_info(),
+        metadata_new_size,
+        ctx.accounts.relayer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    refund_rent(
+        &metadata.to_account_info(),
+        metadata_new_size,
+        rent_shares,
+        ctx.remaining_accounts,
+    )?;
+
+    metadata.validate()?;
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RelayUpdateMetadataItemArgs {
+    /// The collection update authority, the Metadata update authority or a delegate which signed the update
+    /// or a MultisigAuthority whose signers signed it
+    pub update_authority: Pubkey,
+    pub new_value: Vec<u8>,
+    /// The MetadataKey Ids of the ancestor collections, empty for the root items and top level collections
+    pub parent_path: Vec<u64>,
+    pub nonce: u64,
+    pub expiry_slot: u64,
+}
@@ -5,8 +5,13 @@ declare_id!("metaThtkusoWYDvHBFXfvc93Z3d8iBeDZ4DVyq8SYVR");
 
 pub mod constants;
 pub mod errors;
+pub mod events;
+pub mod governance;
 pub mod instructions;
+pub mod metaplex;
+pub mod signature;
 pub mod state;
+pub mod token_metadata;
 pub mod utils;
 
 pub use instructions::*;
@@ -26,6 +31,37 @@ pub mod mythic_metadata {
         create::metadata::handler(ctx, args)
     }
 
+    pub fn create_governance_metadata(ctx: Context<CreateGovernanceMetadata>) -> Result<()> {
+        create::governance_metadata::handler(ctx)
+    }
+
+    pub fn create_multisig_authority(
+        ctx: Context<CreateMultisigAuthority>,
+        args: CreateMultisigAuthorityArgs,
+    ) -> Result<()> {
+        create::multisig_authority::handler(ctx, args)
+    }
+
+    pub fn clone_metadata(ctx: Context<CloneMetadata>, args: CloneMetadataArgs) -> Result<()> {
+        create::clone_metadata::handler(ctx, args)
+    }
+
+    pub fn register_metadata(ctx: Context<RegisterMetadata>) -> Result<()> {
+        registry::register_metadata::handler(ctx)
+    }
+
+    pub fn close_metadata(ctx: Context<CloseMetadata>) -> Result<()> {
+        close::metadata::handler(ctx)
+    }
+
+    pub fn migrate_metadata(ctx: Context<MigrateMetadata>) -> Result<()> {
+        migrate::metadata::handler(ctx)
+    }
+
+    pub fn migrate_metadata_key(ctx: Context<MigrateMetadataKey>) -> Result<()> {
+        migrate::metadata_key::handler(ctx)
+    }
+
     pub fn append_metadata_collection(
         ctx: Context<AppendMetadataCollection>,
         args: AppendMetadataCollectionArgs,
@@ -33,8 +69,11 @@ pub mod mythic_metadata {
         append::collection::handler(ctx, args)
     }
 
-    pub fn remove_metadata_collection(ctx: Context<RemoveMetadataCollection>) -> Result<()> {
-        remove::collection::handler(ctx)
+    pub fn remove_metadata_collection(
+        ctx: Context<RemoveMetadataCollection>,
+        args: RemoveMetadataCollectionArgs,
+    ) -> Result<()> {
+        remove::collection::handler(ctx, args)
     }
 
     pub fn set_collection_update_authority(
@@ -46,8 +85,42 @@ pub mod mythic_metadata {
 
     pub fn revoke_collection_update_authority(
         ctx: Context<RevokeCollectionUpdateAuthority>,
+        args: RevokeCollectionUpdateAuthorityArgs,
+    ) -> Result<()> {
+        auth::revoke::handler(ctx, args)
+    }
+
+    pub fn propose_update_authority_transfer(
+        ctx: Context<ProposeUpdateAuthorityTransfer>,
+        args: ProposeUpdateAuthorityTransferArgs,
+    ) -> Result<()> {
+        auth::propose::handler(ctx, args)
+    }
+
+    pub fn accept_update_authority_transfer(
+        ctx: Context<AcceptUpdateAuthorityTransfer>,
     ) -> Result<()> {
-        auth::revoke::handler(ctx)
+        auth::accept::handler(ctx)
+    }
+
+    pub fn cancel_update_authority_transfer(
+        ctx: Context<CancelUpdateAuthorityTransfer>,
+    ) -> Result<()> {
+        auth::cancel::handler(ctx)
+    }
+
+    pub fn freeze_metadata_collection(
+        ctx: Context<FreezeMetadataCollection>,
+        args: FreezeMetadataCollectionArgs,
+    ) -> Result<()> {
+        auth::freeze::handler(ctx, args)
+    }
+
+    pub fn finalize_metadata(
+        ctx: Context<FinalizeMetadata>,
+        args: FinalizeMetadataArgs,
+    ) -> Result<()> {
+        auth::finalize::handler(ctx, args)
     }
 
     pub fn append_metadata_item(
@@ -71,8 +144,132 @@ pub mod mythic_metadata {
         update::item::handler(ctx, args)
     }
 
-    pub fn remove_metadata_item(ctx: Context<RemoveMetadataItem>) -> Result<()> {
-        remove::item::handler(ctx)
+    pub fn remove_metadata_item(
+        ctx: Context<RemoveMetadataItem>,
+        args: RemoveMetadataItemArgs,
+    ) -> Result<()> {
+        remove::item::handler(ctx, args)
+    }
+
+    pub fn relay_update_metadata_item(
+        ctx: Context<RelayUpdateMetadataItem>,
+        args: RelayUpdateMetadataItemArgs,
+    ) -> Result<()> {
+        update::relayed_item::handler(ctx, args)
+    }
+
+    pub fn attest_metadata_item(
+        ctx: Context<AttestMetadataItem>,
+        args: AttestMetadataItemArgs,
+    ) -> Result<()> {
+        attest::item::handler(ctx, args)
+    }
+
+    pub fn endorse_metadata_item(
+        ctx: Context<EndorseMetadataItem>,
+        args: EndorseMetadataItemArgs,
+    ) -> Result<()> {
+        endorse::item::handler(ctx, args)
+    }
+
+    pub fn revoke_endorsement(ctx: Context<RevokeEndorsement>) -> Result<()> {
+        endorse::revoke::handler(ctx)
+    }
+
+    pub fn link_evm_address(ctx: Context<LinkEvmAddress>, args: LinkEvmAddressArgs) -> Result<()> {
+        link::evm_address::handler(ctx, args)
+    }
+
+    pub fn move_metadata_item(
+        ctx: Context<MoveMetadataItem>,
+        args: MoveMetadataItemArgs,
+    ) -> Result<()> {
+        restructure::move_item::handler(ctx, args)
+    }
+
+    pub fn rekey_metadata_collection(
+        ctx: Context<RekeyMetadataCollection>,
+        args: RekeyMetadataCollectionArgs,
+    ) -> Result<()> {
+        restructure::rekey_collection::handler(ctx, args)
+    }
+
+    pub fn push_metadata_list_value(
+        ctx: Context<PushMetadataListValue>,
+        args: PushMetadataListValueArgs,
+    ) -> Result<()> {
+        list::push::handler(ctx, args)
+    }
+
+    pub fn replace_metadata_list_value(
+        ctx: Context<ReplaceMetadataListValue>,
+        args: ReplaceMetadataListValueArgs,
+    ) -> Result<()> {
+        list::replace::handler(ctx, args)
+    }
+
+    pub fn remove_metadata_list_value(
+        ctx: Context<RemoveMetadataListValue>,
+        args: RemoveMetadataListValueArgs,
+    ) -> Result<()> {
+        list::remove::handler(ctx, args)
+    }
+
+    pub fn clear_metadata_list(
+        ctx: Context<ClearMetadataList>,
+        args: ClearMetadataListArgs,
+    ) -> Result<()> {
+        list::clear::handler(ctx, args)
+    }
+
+    pub fn set_collection_timelock(
+        ctx: Context<SetCollectionTimelock>,
+        args: SetCollectionTimelockArgs,
+    ) -> Result<()> {
+        timelock::set::handler(ctx, args)
+    }
+
+    pub fn queue_metadata_change(
+        ctx: Context<QueueMetadataChange>,
+        args: QueueMetadataChangeArgs,
+    ) -> Result<()> {
+        timelock::queue::handler(ctx, args)
+    }
+
+    pub fn cancel_metadata_change(ctx: Context<CancelMetadataChange>) -> Result<()> {
+        timelock::cancel::handler(ctx)
+    }
+
+    pub fn execute_metadata_change(ctx: Context<ExecuteMetadataChange>) -> Result<()> {
+        timelock::execute::handler(ctx)
+    }
+
+    pub fn create_metadata_delegate(
+        ctx: Context<CreateMetadataDelegate>,
+        args: CreateMetadataDelegateArgs,
+    ) -> Result<()> {
+        delegate::create::handler(ctx, args)
+    }
+
+    pub fn revoke_metadata_delegate(ctx: Context<RevokeMetadataDelegate>) -> Result<()> {
+        delegate::revoke::handler(ctx)
+    }
+
+    pub fn sync_governance_delegate(ctx: Context<SyncGovernanceDelegate>) -> Result<()> {
+        delegate::sync_governance::handler(ctx)
+    }
+
+    pub fn import_metaplex_metadata(ctx: Context<ImportMetaplexMetadata>) -> Result<()> {
+        import::metaplex_metadata::handler(ctx)
+    }
+
+    /// Dispatches the spl-token-metadata-interface instructions
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        token_metadata::processor::process(program_id, accounts, data)
     }
 }
 
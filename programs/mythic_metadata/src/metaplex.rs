@@ -0,0 +1,64 @@
+//! Minimal reader for Metaplex Token Metadata accounts
+//!
+//! Only the leading fields are read so the program doesn't depend on the mpl-token-metadata crate
+
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+
+pub const MPL_TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Key discriminator of a Metaplex MetadataV1 account
+const METADATA_V1_KEY: u8 = 4;
+
+/// The leading fields of a Metaplex Metadata account
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetaplexMetadata {
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+impl MetaplexMetadata {
+    /// Parses the account data, Metaplex pads the strings with NUL characters which are trimmed
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        fn parse_fields(mut data: &[u8]) -> std::io::Result<MetaplexMetadata> {
+            let trimmed = |value: String| value.trim_end_matches('\0').to_string();
+
+            Ok(MetaplexMetadata {
+                update_authority: Pubkey::deserialize(&mut data)?,
+                mint: Pubkey::deserialize(&mut data)?,
+                name: trimmed(String::deserialize(&mut data)?),
+                symbol: trimmed(String::deserialize(&mut data)?),
+                uri: trimmed(String::deserialize(&mut data)?),
+            })
+        }
+
+        match data.split_first() {
+            Some((&METADATA_V1_KEY, fields)) => parse_fields(fields)
+                .map_err(|_| error!(MythicMetadataError::InvalidMetaplexMetadata)),
+            _ => err!(MythicMetadataError::InvalidMetaplexMetadata),
+        }
+    }
+
+    /// Reads the Metaplex Metadata of the mint from an account owned by the Metaplex program
+    pub fn from_account_info(account: &AccountInfo, mint: &Pubkey) -> Result<Self> {
+        require_keys_eq!(
+            *account.owner,
+            MPL_TOKEN_METADATA_PROGRAM_ID,
+            MythicMetadataError::InvalidAccountOwner
+        );
+
+        let metaplex_metadata = Self::parse(&account.try_borrow_data()?)?;
+        require_keys_eq!(
+            metaplex_metadata.mint,
+            *mint,
+            MythicMetadataError::InvalidMetaplexMetadata
+        );
+
+        Ok(metaplex_metadata)
+    }
+}
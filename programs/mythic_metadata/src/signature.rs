@@ -0,0 +1,132 @@
+//! Readers of the native signature verification instruction preceding the current instruction
+//!
+//! The ed25519 program fails the transaction if a signature is invalid, so a program can trust
+//! the public key and message of an ed25519 instruction in the same transaction. Only the
+//! instruction immediately before the current one is read, and only if it carries its own
+//! signature, public key and message, so the verified message can't be swapped for another
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::errors::*;
+
+/// The size of the signature count and padding preceding the offsets
+const ED25519_HEADER_LEN: usize = 2;
+/// The size of the Ed25519SignatureOffsets of a signature
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_PUBKEY_LEN: usize = 32;
+const ED25519_SIGNATURE_LEN: usize = 64;
+/// The instruction index referring to the ed25519 instruction itself
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Locates the signature, public key and message in the instruction data
+/// The instruction indexes are CURRENT_INSTRUCTION when they're part of the ed25519 instruction
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct Ed25519SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u16,
+    pub public_key_offset: u16,
+    pub public_key_instruction_index: u16,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u16,
+}
+
+/// A signature verified by the ed25519 program
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ed25519Signature {
+    pub signer: Pubkey,
+    pub message: Vec<u8>,
+}
+
+impl Ed25519Signature {
+    /// Parses the data of an ed25519 program instruction verifying a single signature
+    /// whose signature, public key and message are all part of the instruction data
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let [signature] = <[Self; 1]>::try_from(Self::parse_all(data)?)
+            .map_err(|_| error!(MythicMetadataError::InvalidSignatureInstruction))?;
+
+        Ok(signature)
+    }
+
+    /// Parses the data of an ed25519 program instruction verifying one or more signatures
+    /// whose signatures, public keys and messages are all part of the instruction data
+    pub fn parse_all(data: &[u8]) -> Result<Vec<Self>> {
+        let invalid = || error!(MythicMetadataError::InvalidSignatureInstruction);
+
+        let [signatures_count, _] = data.get(..ED25519_HEADER_LEN).ok_or_else(invalid)? else {
+            return Err(invalid());
+        };
+        require!(
+            *signatures_count > 0,
+            MythicMetadataError::InvalidSignatureInstruction
+        );
+
+        (0..usize::from(*signatures_count))
+            .map(|index| {
+                let offsets_start = ED25519_HEADER_LEN + index * ED25519_OFFSETS_LEN;
+                let offsets = data
+                    .get(offsets_start..offsets_start + ED25519_OFFSETS_LEN)
+                    .and_then(|offsets| Ed25519SignatureOffsets::try_from_slice(offsets).ok())
+                    .ok_or_else(invalid)?;
+
+                require!(
+                    [
+                        offsets.signature_instruction_index,
+                        offsets.public_key_instruction_index,
+                        offsets.message_instruction_index
+                    ]
+                    .iter()
+                    .all(|index| *index == CURRENT_INSTRUCTION),
+                    MythicMetadataError::InvalidSignatureInstruction
+                );
+
+                let slice = |offset: u16, len: usize| {
+                    data.get(offset as usize..offset as usize + len)
+                        .ok_or_else(invalid)
+                };
+                slice(offsets.signature_offset, ED25519_SIGNATURE_LEN)?;
+
+                Ok(Self {
+                    signer: Pubkey::try_from(slice(offsets.public_key_offset, ED25519_PUBKEY_LEN)?)
+                        .map_err(|_| invalid())?,
+                    message: slice(
+                        offsets.message_data_offset,
+                        offsets.message_data_size as usize,
+                    )?
+                    .to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Loads the signature verified by the ed25519 instruction preceding the current instruction
+    pub fn load_preceding(instructions_sysvar: &AccountInfo) -> Result<Self> {
+        Self::parse(&load_preceding_ed25519_data(instructions_sysvar)?)
+    }
+
+    /// Loads the signatures verified by the ed25519 instruction preceding the current instruction
+    pub fn load_preceding_all(instructions_sysvar: &AccountInfo) -> Result<Vec<Self>> {
+        Self::parse_all(&load_preceding_ed25519_data(instructions_sysvar)?)
+    }
+}
+
+/// Loads the data of the ed25519 instruction preceding the current instruction
+fn load_preceding_ed25519_data(instructions_sysvar: &AccountInfo) -> Result<Vec<u8>> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let preceding_index = current_index
+        .checked_sub(1)
+        .ok_or(MythicMetadataError::InvalidSignatureInstruction)?;
+    let instruction = load_instruction_at_checked(preceding_index as usize, instructions_sysvar)?;
+
+    require_keys_eq!(
+        instruction.program_id,
+        ed25519_program::ID,
+        MythicMetadataError::InvalidSignatureInstruction
+    );
+
+    Ok(instruction.data)
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+/// Prefix of the signed attestation messages, so they can't be mistaken for other signed data
+pub const ATTESTATION_DOMAIN: &[u8] = b"mythic_metadata:attestation:v1";
+
+/// A claim about a subject signed off-chain by the issuing authority of a Metadata
+/// Anyone holding the signature can post the claim with attest_metadata_item
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Attestation {
+    pub subject: Pubkey,
+    /// The MetadataKey Id of the Metadata
+    pub metadata_key_id: u64,
+    /// The path of the collection holding the item, the Metadata's MetadataKey Id for the root items
+    pub collection_path: Vec<u64>,
+    pub item_metadata_key_id: u64,
+    pub value: Vec<u8>,
+    /// Must exceed the nonce of the last message of the issuing authority applied to the Metadata
+    pub nonce: u64,
+    /// The last slot the attestation can be posted in
+    pub expiry_slot: u64,
+}
+
+impl Attestation {
+    /// The canonical encoding signed by the issuing authority
+    pub fn message(&self) -> Vec<u8> {
+        [ATTESTATION_DOMAIN, &self.try_to_vec().unwrap()].concat()
+    }
+}
+
+#[derive(Debug)]
+#[account]
+/// SignerNonce records the nonce of the last signed message of a signer applied to a Metadata
+/// Messages must carry a greater nonce, which blocks replays
+/// Each message domain has its own SignerNonce, so attestations and relayed updates signed
+/// concurrently don't consume each other's nonces
+pub struct SignerNonce {
+    pub metadata: Pubkey,
+
+    pub signer: Pubkey,
+
+    pub nonce: u64,
+
+    /// Bump
+    pub bump: u8,
+}
+
+impl SignerNonce {
+    pub fn size() -> usize {
+        8 + // Anchor discriminator
+        32 + // metadata
+        32 + // signer
+        8 + // nonce
+        1 // bump
+    }
+}
+
+/// Prefix of the signed item update messages
+pub const ITEM_UPDATE_DOMAIN: &[u8] = b"mythic_metadata:item_update:v1";
+
+/// An item update signed off-chain by an update authority of the item
+/// A relayer pays the fees and the rent and posts it with relay_update_metadata_item
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SignedItemUpdate {
+    /// The address of the Metadata
+    pub metadata: Pubkey,
+    /// The update authority the signers act as, the signer or a MultisigAuthority of the signers
+    /// Its nonce is consumed, so the message can't be replayed as another update authority
+    pub update_authority: Pubkey,
+    /// The path of the collection holding the item, the Metadata's MetadataKey Id for the root items
+    pub collection_path: Vec<u64>,
+    pub item_metadata_key_id: u64,
+    pub new_value: Vec<u8>,
+    /// Must exceed the nonce of the last message of the update authority applied to the Metadata
+    pub nonce: u64,
+    /// The last slot the update can be posted in
+    pub expiry_slot: u64,
+}
+
+impl SignedItemUpdate {
+    /// The canonical encoding signed by the update authority
+    pub fn message(&self) -> Vec<u8> {
+        [ITEM_UPDATE_DOMAIN, &self.try_to_vec().unwrap()].concat()
+    }
+}
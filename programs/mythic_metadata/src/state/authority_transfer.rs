@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+#[derive(Debug)]
+#[account]
+/// AuthorityTransfer proposes a new update authority for a Metadata or one of its collections
+/// The update authority only changes once the new update authority accepts it, so a mistyped
+/// authority can't lock everyone out. A collection has at most one proposed transfer
+pub struct AuthorityTransfer {
+    pub metadata: Pubkey,
+
+    /// The path of the collection, the Metadata's MetadataKey Id for the Metadata update authority
+    pub collection_path: Vec<u64>,
+
+    pub new_update_authority: Pubkey,
+
+    /// The payer of the rent, refunded when the transfer is accepted or cancelled
+    pub payer: Pubkey,
+
+    /// Bump
+    pub bump: u8,
+}
+
+impl AuthorityTransfer {
+    pub fn size(collection_path_len: usize) -> usize {
+        8 + // Anchor discriminator
+        32 + // metadata
+        4 + collection_path_len * 8 + // collection_path
+        32 + // new_update_authority
+        32 + // payer
+        1 // bump
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::state::*;
+
+#[derive(Debug)]
+#[account]
+/// Endorsement records an endorser co-signing one version of an item of a Metadata
+/// The version is identified by the value hash and the item's update_slot, any later update
+/// of the item invalidates the endorsement
+pub struct Endorsement {
+    pub metadata: Pubkey,
+
+    pub endorser: Pubkey,
+
+    /// The path of the collection holding the item, the Metadata's MetadataKey Id for the root items
+    pub collection_path: Vec<u64>,
+
+    pub item_metadata_key_id: u64,
+
+    /// The sha256 hash of the endorsed value
+    pub value_hash: [u8; 32],
+
+    /// The update_slot of the item when it was endorsed
+    pub item_update_slot: u64,
+
+    /// Bump
+    pub bump: u8,
+}
+
+impl Endorsement {
+    pub fn size(collection_path: &[u64]) -> usize {
+        8 + // Anchor discriminator
+        32 + // metadata
+        32 + // endorser
+        4 + collection_path.len() * 8 + // collection_path
+        8 + // item_metadata_key_id
+        32 + // value_hash
+        8 + // item_update_slot
+        1 // bump
+    }
+
+    pub fn value_hash(value: &[u8]) -> [u8; 32] {
+        hashv(&[value]).to_bytes()
+    }
+
+    /// Whether the endorsement covers the current version of the item
+    pub fn is_valid_for(&self, item: &MetadataItem) -> bool {
+        self.item_metadata_key_id == item.metadata_key_id
+            && self.item_update_slot == item.update_slot
+            && self.value_hash == Self::value_hash(&item.value)
+    }
+}
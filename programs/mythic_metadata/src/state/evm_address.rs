@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::secp256k1_recover::secp256k1_recover;
+
+use crate::errors::*;
+use crate::state::*;
+
+pub const EVM_ADDRESS_LEN: usize = 20;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+/// EvmAddressLink is the value of items whose MetadataKey content_type is EVM_ADDRESS_CONTENT_TYPE
+/// It holds the address with the proof its key holder signed the EIP-191 link message of the subject,
+/// so readers can verify the link against the Metadata's subject themselves
+pub struct EvmAddressLink {
+    /// The Ethereum address, the last 20 bytes of the keccak256 hash of the public key
+    pub address: [u8; EVM_ADDRESS_LEN],
+
+    /// The r and s values of the personal_sign signature
+    pub signature: [u8; 64],
+
+    /// The recovery id, 0 or 1, i.e. the signature's v value minus 27
+    pub recovery_id: u8,
+}
+
+impl EvmAddressLink {
+    /// The message the address signs with personal_sign to link itself to the subject
+    pub fn message(address: &[u8; EVM_ADDRESS_LEN], subject: &Pubkey) -> String {
+        let address = address.iter().fold(String::new(), |mut hex, byte| {
+            use std::fmt::Write;
+            write!(hex, "{byte:02x}").unwrap();
+            hex
+        });
+        format!("Link EVM address 0x{address} to Mythic Metadata subject {subject}")
+    }
+
+    /// The EIP-191 hash of a personal_sign message
+    pub fn eip191_hash(message: &[u8]) -> [u8; 32] {
+        keccak::hashv(&[
+            b"\x19Ethereum Signed Message:\n",
+            message.len().to_string().as_bytes(),
+            message,
+        ])
+        .to_bytes()
+    }
+
+    /// Deserializes an item value, the link isn't verified
+    pub fn from_value(value: &[u8]) -> Result<Self> {
+        Self::try_from_slice(value).map_err(|_| error!(MythicMetadataError::InvalidEvmAddressLink))
+    }
+
+    /// Returns true if the item value is a link, or a list holding a link, proving an address
+    /// linked itself to the subject. The proof is bound to the subject so it isn't copied
+    /// by Metadata::copy_from
+    pub fn is_proof_for(value: &[u8], subject: &Pubkey) -> bool {
+        let is_link =
+            |value: &[u8]| Self::from_value(value).is_ok_and(|link| link.verify(subject).is_ok());
+
+        is_link(value)
+            || decode_list(value).is_ok_and(|values| values.iter().any(|value| is_link(value)))
+    }
+
+    /// Verifies the signature of the subject's link message recovers the address
+    pub fn verify(&self, subject: &Pubkey) -> Result<()> {
+        let hash = Self::eip191_hash(Self::message(&self.address, subject).as_bytes());
+        let public_key = secp256k1_recover(&hash, self.recovery_id, &self.signature)
+            .map_err(|_| error!(MythicMetadataError::InvalidEvmAddressLink))?;
+
+        require!(
+            keccak::hash(&public_key.to_bytes()).to_bytes()[12..] == self.address,
+            MythicMetadataError::InvalidEvmAddressLink
+        );
+
+        Ok(())
+    }
+}
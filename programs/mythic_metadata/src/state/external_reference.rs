@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{blake3, hash, keccak};
+
+use crate::constants::*;
+use crate::errors::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha256,
+    Keccak256,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    pub fn digest(&self, content: &[u8]) -> [u8; 32] {
+        match self {
+            HashAlgorithm::Sha256 => hash::hash(content).to_bytes(),
+            HashAlgorithm::Keccak256 => keccak::hash(content).to_bytes(),
+            HashAlgorithm::Blake3 => blake3::hash(content).to_bytes(),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+/// ExternalReference is the value of items whose MetadataKey content_type is EXTERNAL_REFERENCE_CONTENT_TYPE
+/// It anchors an off-chain document which readers verify against the digest
+pub struct ExternalReference {
+    /// Location of the referenced content
+    pub uri: String,
+
+    /// The algorithm the digest was computed with
+    pub hash_algorithm: HashAlgorithm,
+
+    /// Digest of the referenced content
+    pub digest: [u8; 32],
+
+    /// Size of the referenced content in bytes, if known
+    pub size: Option<u64>,
+}
+
+impl ExternalReference {
+    pub fn new(uri: String, hash_algorithm: HashAlgorithm, content: &[u8]) -> Self {
+        Self {
+            uri,
+            hash_algorithm,
+            digest: hash_algorithm.digest(content),
+            size: Some(content.len() as u64),
+        }
+    }
+
+    /// Deserializes and validates an item value
+    pub fn from_value(value: &[u8]) -> Result<Self> {
+        let external_reference = Self::try_from_slice(value)
+            .map_err(|_| error!(MythicMetadataError::InvalidExternalReference))?;
+        external_reference.validate()?;
+
+        Ok(external_reference)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.uri.is_empty() || self.uri.len() > MAX_URI_LEN {
+            return err!(MythicMetadataError::InvalidExternalReference);
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the content matches the size and digest
+    pub fn matches(&self, content: &[u8]) -> bool {
+        self.size.map_or(true, |size| size == content.len() as u64)
+            && self.hash_algorithm.digest(content) == self.digest
+    }
+}
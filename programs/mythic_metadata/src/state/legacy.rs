@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::errors::*;
+use crate::state::*;
+
+/// The discriminator of Metadata accounts in the legacy layout, derived from "account:Metadata"
+pub const LEGACY_METADATA_DISCRIMINATOR: [u8; 8] = [72, 11, 121, 26, 111, 181, 85, 93];
+
+/// The discriminator of MetadataKey accounts in the legacy layout, derived from "account:MetadataKey"
+pub const LEGACY_METADATA_KEY_DISCRIMINATOR: [u8; 8] = [108, 103, 195, 88, 73, 100, 213, 199];
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyMetadataItem {
+    pub metadata_key_id: u64,
+    pub update_slot: u64,
+    pub value: Vec<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyMetadataCollection {
+    pub metadata_key_id: u64,
+    pub update_slot: u64,
+    pub update_authority: Option<Pubkey>,
+    pub items: Vec<LegacyMetadataItem>,
+}
+
+/// The layout of the Metadata accounts created before nested collections,
+/// multi-valued keys, timelocks, freezing, finalization and rent payers were added
+///
+/// migrate_metadata and migrate_metadata_key rewrite such accounts in the current layout.
+/// The layouts are told apart by the discriminators of the accounts, not by whether the data
+/// deserializes: the legacy program never shrank accounts and the stale bytes it left after the
+/// data may well deserialize in the current layout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyMetadata {
+    pub subject: Pubkey,
+    pub metadata_key_id: u64,
+    pub issuing_authority: Pubkey,
+    pub update_slot: u64,
+    pub update_authority: Option<Pubkey>,
+    pub items: Vec<LegacyMetadataItem>,
+    pub collections: Vec<LegacyMetadataCollection>,
+    pub bump: u8,
+}
+
+impl LegacyMetadataItem {
+    fn into_item(self, rent_payer: Pubkey) -> MetadataItem {
+        MetadataItem {
+            metadata_key_id: self.metadata_key_id,
+            update_slot: self.update_slot,
+            value: self.value,
+            rent_payer,
+            growth_rent_shares: vec![],
+        }
+    }
+}
+
+impl LegacyMetadata {
+    /// Deserializes the data of a Metadata account in the legacy layout
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            !data.starts_with(&Metadata::DISCRIMINATOR),
+            MythicMetadataError::AccountAlreadyMigrated
+        );
+        require!(
+            data.starts_with(&LEGACY_METADATA_DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+
+    /// Converts the Metadata to the current layout
+    /// The legacy collections are top level collections and the issuing authority is the rent payer
+    /// of the items and collections, it receives the whole rent when the Metadata is closed
+    /// The legacy program couldn't make a Metadata immutable, its issuing authority could always set
+    /// a new update authority, so the migrated Metadata isn't finalized
+    pub fn into_metadata(self) -> Metadata {
+        let rent_payer = self.issuing_authority;
+
+        Metadata {
+            subject: self.subject,
+            metadata_key_id: self.metadata_key_id,
+            issuing_authority: self.issuing_authority,
+            update_slot: self.update_slot,
+            update_authority: self.update_authority,
+            items: self
+                .items
+                .into_iter()
+                .map(|item| item.into_item(rent_payer))
+                .collect(),
+            collections: self
+                .collections
+                .into_iter()
+                .map(|collection| MetadataCollection {
+                    metadata_key_id: collection.metadata_key_id,
+                    parent_path: vec![],
+                    update_slot: collection.update_slot,
+                    update_authority: collection.update_authority,
+                    timelock_slots: 0,
+                    frozen: false,
+                    items: collection
+                        .items
+                        .into_iter()
+                        .map(|item| item.into_item(rent_payer))
+                        .collect(),
+                    rent_payer,
+                })
+                .collect(),
+            bump: self.bump,
+            verified_subject: false,
+            finalized_slot: None,
+            registry_page_index: None,
+            records_count: 0,
+        }
+    }
+}
+
+/// The layout of the MetadataKey accounts created before multi-valued keys, see [LegacyMetadata]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyMetadataKey {
+    pub id: u64,
+    pub namespace_authority: Pubkey,
+    pub name: String,
+    pub label: String,
+    pub description: String,
+    pub content_type: String,
+    pub bump: u8,
+}
+
+impl LegacyMetadataKey {
+    /// Deserializes the data of a MetadataKey account in the legacy layout
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            !data.starts_with(&MetadataKey::DISCRIMINATOR),
+            MythicMetadataError::AccountAlreadyMigrated
+        );
+        require!(
+            data.starts_with(&LEGACY_METADATA_KEY_DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+
+    /// Converts the MetadataKey to the current layout, legacy keys are single valued
+    pub fn into_metadata_key(self) -> MetadataKey {
+        MetadataKey {
+            id: self.id,
+            namespace_authority: self.namespace_authority,
+            name: self.name,
+            label: self.label,
+            description: self.description,
+            content_type: self.content_type,
+            bump: self.bump,
+            multi_valued: false,
+        }
+    }
+}
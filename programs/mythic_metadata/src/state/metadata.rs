@@ -1,7 +1,11 @@
+use std::iter::once;
+
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 
 use crate::constants::*;
 use crate::errors::*;
+use crate::state::EvmAddressLink;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 /// MetadataItem defines a single metadata item identified by its MetadataKey
@@ -14,13 +18,110 @@ pub struct MetadataItem {
 
     /// Serialized metadata item value
     pub value: Vec<u8>,
+
+    /// The payer which funded the rent of the item when it was appended
+    /// The rent of the item, less the growth_rent_shares, is refunded to it when the item is removed or shrinks
+    pub rent_payer: Pubkey,
+
+    /// The payers which funded the growth of the value by updates, in the order they grew it
+    /// The rent_payer funds its own growth until another payer grows the value, shrinks refund
+    /// the latest growth first
+    pub growth_rent_shares: Vec<GrowthRentShare>,
 }
 
 impl MetadataItem {
-    pub fn size(value: &[u8]) -> usize {
+    pub fn size(&self) -> usize {
         8 + // metadata_key_id
         8 + // update_slot
-        4 + value.len() // value
+        4 + self.value.len() + // value
+        32 + // rent_payer
+        4 + GrowthRentShare::SIZE * self.growth_rent_shares.len() // growth_rent_shares
+    }
+
+    /// The rent shares of the item, the rent_payer funds the bytes the growth_rent_shares don't
+    pub fn rent_shares(&self) -> impl Iterator<Item = RentShare> + '_ {
+        let growth_size = self
+            .growth_rent_shares
+            .iter()
+            .map(|growth_rent_share| growth_rent_share.size as usize)
+            .sum::<usize>();
+
+        once(RentShare {
+            rent_payer: self.rent_payer,
+            size: self.size() - growth_size,
+        })
+        .chain(
+            self.growth_rent_shares
+                .iter()
+                .map(|growth_rent_share| RentShare {
+                    rent_payer: growth_rent_share.rent_payer,
+                    size: growth_rent_share.size as usize,
+                }),
+        )
+    }
+
+    /// Replaces the value with the value of the item, the rent payer of the item funds the growth
+    /// Returns the rent shares of the bytes the item shrunk by, the latest growth is refunded first
+    fn update(&mut self, item: MetadataItem) -> Vec<RentShare> {
+        let MetadataItem {
+            update_slot,
+            value,
+            rent_payer,
+            ..
+        } = item;
+        let current_size = self.value.len();
+        self.update_slot = update_slot;
+        self.value = value;
+
+        if self.value.len() > current_size {
+            let growth = (self.value.len() - current_size) as u32;
+            // Each growth is stacked on the latest one so shrinks refund them in reverse order,
+            // consecutive growths of the same payer share an entry
+            match self.growth_rent_shares.last_mut() {
+                Some(growth_rent_share) if growth_rent_share.rent_payer == rent_payer => {
+                    growth_rent_share.size += growth
+                }
+                None if rent_payer == self.rent_payer => {}
+                _ => self.growth_rent_shares.push(GrowthRentShare {
+                    rent_payer,
+                    // The payer funds its share entry as well
+                    size: growth + GrowthRentShare::SIZE as u32,
+                }),
+            }
+            return vec![];
+        }
+
+        let mut shrink = (current_size - self.value.len()) as u32;
+        let mut rent_shares = vec![];
+        while shrink > 0 {
+            let Some(growth_rent_share) = self.growth_rent_shares.last_mut() else {
+                rent_shares.push(RentShare {
+                    rent_payer: self.rent_payer,
+                    size: shrink as usize,
+                });
+                break;
+            };
+
+            let growth = growth_rent_share.size - GrowthRentShare::SIZE as u32;
+            if shrink < growth {
+                growth_rent_share.size -= shrink;
+                rent_shares.push(RentShare {
+                    rent_payer: growth_rent_share.rent_payer,
+                    size: shrink as usize,
+                });
+                break;
+            }
+
+            // The share entry is removed with the growth it funded
+            rent_shares.push(RentShare {
+                rent_payer: growth_rent_share.rent_payer,
+                size: growth_rent_share.size as usize,
+            });
+            self.growth_rent_shares.pop();
+            shrink -= growth;
+        }
+
+        rent_shares
     }
 
     pub fn validate(&self) -> Result<()> {
@@ -32,38 +133,129 @@ impl MetadataItem {
     }
 }
 
+/// The bytes of an item value funded by a payer other than the item rent_payer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GrowthRentShare {
+    pub rent_payer: Pubkey,
+    /// The bytes the payer funded, including this share
+    pub size: u32,
+}
+
+impl GrowthRentShare {
+    pub const SIZE: usize = 32 + // rent_payer
+        4; // size
+}
+
+/// The bytes of a Metadata account funded by a rent payer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RentShare {
+    pub rent_payer: Pubkey,
+    pub size: usize,
+}
+
+/// Returns the path of the collection identified by metadata_key_id nested in the parent_path
+pub fn collection_path(parent_path: &[u64], metadata_key_id: u64) -> Vec<u64> {
+    parent_path
+        .iter()
+        .copied()
+        .chain(once(metadata_key_id))
+        .collect()
+}
+
+/// The PDA seed identifying a collection of a Metadata, collection paths don't fit in seeds
+pub fn path_seed(path: &[u64]) -> [u8; 32] {
+    let path = path
+        .iter()
+        .flat_map(|metadata_key_id| metadata_key_id.to_le_bytes())
+        .collect::<Vec<u8>>();
+    hashv(&[&path]).to_bytes()
+}
+
+/// The PDA seed identifying an item of a Metadata
+pub fn item_seed(collection_path: &[u64], item_metadata_key_id: u64) -> [u8; 32] {
+    path_seed(&self::collection_path(
+        collection_path,
+        item_metadata_key_id,
+    ))
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct MetadataCollection {
     /// The Metadata Key  Id
     pub metadata_key_id: u64,
 
+    /// The MetadataKey Ids of the ancestor collections from the top level collection down to the parent
+    /// Empty for top level collections
+    /// A collection is addressed by its path, the parent_path followed by its metadata_key_id
+    /// e.g. socials/twitter is nested in the top level socials collection
+    pub parent_path: Vec<u64>,
+
     /// The slot when the collection was last updated
     /// The collection update slot is max(update_slot) for all its metadata items
     pub update_slot: u64,
 
     /// The authority that can update the collection metadata items
     /// Separate update instructions can be invoked to add/revoke specific collection's update_authority
-    /// If the collection level update authority is None then parent Metadata update_authority is used
+    /// The update authorities of the ancestor collections and the Metadata update_authority
+    /// can update the collection metadata items as well
     pub update_authority: Option<Pubkey>,
 
+    /// The number of slots updates and removals of the collection items wait in a PendingChange
+    /// Nested collections are locked for at least as long as their ancestors, 0 applies them instantly
+    pub timelock_slots: u64,
+
+    /// Frozen collections and their nested collections can't be changed by any authority
+    /// Freezing is permanent, unlike revoking the update_authority
+    pub frozen: bool,
+
     pub items: Vec<MetadataItem>,
+
+    /// The payer which funded the rent of the collection, excluding its items
+    pub rent_payer: Pubkey,
 }
 
 impl MetadataCollection {
-    pub fn size(collection_items: &[MetadataItem]) -> usize {
+    pub fn size(parent_path: &[u64], collection_items: &[MetadataItem]) -> usize {
         let collection_items_size = collection_items.iter().fold(0, |mut acc, collection_item| {
-            let metadata_item_size = MetadataItem::size(&collection_item.value);
+            let metadata_item_size = collection_item.size();
             acc += metadata_item_size;
             acc
         });
 
         8 + // metadata_key_id
+        4 + 8 * parent_path.len() + // parent_path
         8 + // update_slot
         1 + 32 + // update_authority
-        4 + collection_items_size // items
+        8 + // timelock_slots
+        1 + // frozen
+        4 + collection_items_size + // items
+        32 // rent_payer
+    }
+
+    /// The rent shares of the collection and of its items
+    pub fn rent_shares(&self) -> impl Iterator<Item = RentShare> + '_ {
+        once(RentShare {
+            rent_payer: self.rent_payer,
+            size: Self::size(&self.parent_path, &[]),
+        })
+        .chain(self.items.iter().flat_map(MetadataItem::rent_shares))
+    }
+
+    /// Returns the MetadataKey Ids of the collection path
+    pub fn path(&self) -> impl Iterator<Item = &u64> {
+        self.parent_path.iter().chain(once(&self.metadata_key_id))
+    }
+
+    /// Returns true if the collection is nested, at any depth, in the collection identified by collection_path
+    pub fn is_descendant_of(&self, collection_path: &[u64]) -> bool {
+        self.parent_path.starts_with(collection_path)
     }
 
     pub fn validate(&self) -> Result<()> {
+        if self.parent_path.len() >= MAX_COLLECTION_DEPTH {
+            return err!(MythicMetadataError::MetadataCollectionDepthExceeded);
+        }
+
         if self.items.len() > MAX_ITEMS_PER_COLLECTION {
             return err!(MythicMetadataError::MetadataItemFull);
         }
@@ -74,9 +266,56 @@ impl MetadataCollection {
 
         Ok(())
     }
+
+    pub fn append_item(&mut self, item: MetadataItem) -> Result<()> {
+        let slot = item.update_slot;
+        insert_item(&mut self.items, item)?;
+        self.update_slot = slot;
+        Ok(())
+    }
+
+    pub fn update_item(&mut self, item: MetadataItem) -> Result<Vec<RentShare>> {
+        let slot = item.update_slot;
+        let rent_shares = replace_item(&mut self.items, item)?;
+        self.update_slot = slot;
+        Ok(rent_shares)
+    }
+
+    pub fn remove_item(&mut self, item_metadata_key_id: u64) -> Result<MetadataItem> {
+        remove_item(&mut self.items, item_metadata_key_id)
+    }
+}
+
+/// Inserts the item keeping the items sorted by metadata_key_id
+fn insert_item(items: &mut Vec<MetadataItem>, item: MetadataItem) -> Result<()> {
+    match items.binary_search_by_key(&item.metadata_key_id, |item| item.metadata_key_id) {
+        Ok(_) => err!(MythicMetadataError::MetadataItemAlreadyExists),
+        Err(item_index) => {
+            items.insert(item_index, item);
+            Ok(())
+        }
+    }
+}
+
+/// Replaces the value of the existing item with the same metadata_key_id
+/// Returns the rent shares of the bytes the item shrunk by, see [MetadataItem::update]
+fn replace_item(items: &mut [MetadataItem], item: MetadataItem) -> Result<Vec<RentShare>> {
+    match items.binary_search_by_key(&item.metadata_key_id, |item| item.metadata_key_id) {
+        Ok(item_index) => Ok(items[item_index].update(item)),
+        Err(_) => err!(MythicMetadataError::MetadataItemNonExistent),
+    }
+}
+
+fn remove_item(items: &mut Vec<MetadataItem>, item_metadata_key_id: u64) -> Result<MetadataItem> {
+    match items.binary_search_by_key(&item_metadata_key_id, |item| item.metadata_key_id) {
+        Ok(item_index) => Ok(items.remove(item_index)),
+        Err(_) => err!(MythicMetadataError::MetadataItemNonExistent),
+    }
 }
 
-#[account]
+/// The discriminator is namespaced by the layout version, so accounts in the legacy layout,
+/// see [LegacyMetadata], can't be read as accounts in the current layout whatever their data
+#[account("account_v2")]
 pub struct Metadata {
     /// The subject described by the metadata (e.g. a DAO, NFT, a program etc.)
     pub subject: Pubkey,
@@ -92,8 +331,10 @@ pub struct Metadata {
     /// For example a DAO can issue metadata about itself using the DAO's authority
     /// Or external authority can issue claims, certifications etc. about the DAO
     ///
-    /// TODO:
-    /// - Should it also be allowed to close the account?
+    /// The canonical Metadata of a DAO is issued by its SPL Governance realm about itself,
+    /// see create_governance_metadata
+    ///
+    /// The issuing authority can close the Metadata with close_metadata
     pub issuing_authority: Pubkey,
 
     /// The slot when the collection was last updated
@@ -102,7 +343,8 @@ pub struct Metadata {
 
     /// The default update authority for all the collections
     /// Note: The authority can be overridden at the collection level
-    /// Setting the authority to None makes the Metadata immutable
+    /// When the authority is None the issuing authority acts as the update authority,
+    /// the Metadata can only be made immutable with finalize_metadata
     pub update_authority: Option<Pubkey>,
 
     pub items: Vec<MetadataItem>,
@@ -111,18 +353,42 @@ pub struct Metadata {
 
     /// Bump
     pub bump: u8,
+
+    /// True if the subject proved control when the Metadata was created, i.e. the subject,
+    /// its mint authority or its program upgrade authority signed
+    /// Readers can use it to tell the official self-issued Metadata from third party claims
+    pub verified_subject: bool,
+
+    /// The slot when the Metadata was finalized with finalize_metadata
+    /// Finalized Metadata is permanently immutable
+    pub finalized_slot: Option<u64>,
+
+    /// The index of the SubjectRegistryPage listing the Metadata, None if it isn't registered
+    pub registry_page_index: Option<u32>,
+
+    /// The number of open MetadataDelegate, PendingChange and AuthorityTransfer accounts of the Metadata
+    /// The Metadata can't be closed while it has any, they would apply to Metadata recreated at its address
+    pub records_count: u32,
+}
+
+// Namespaced accounts don't implement Owner
+impl Owner for Metadata {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
 }
 
 impl Metadata {
     pub fn size(items: &[MetadataItem], collections: &[MetadataCollection]) -> usize {
         let items_size = items.iter().fold(0, |mut acc, collection_item| {
-            let metadata_item_size = MetadataItem::size(&collection_item.value);
+            let metadata_item_size = collection_item.size();
             acc += metadata_item_size;
             acc
         });
 
         let collections_size = collections.iter().fold(0, |mut acc, child_collection| {
-            let child_collection_size = MetadataCollection::size(&child_collection.items);
+            let child_collection_size =
+                MetadataCollection::size(&child_collection.parent_path, &child_collection.items);
             acc += child_collection_size;
             acc
         });
@@ -135,7 +401,11 @@ impl Metadata {
         1 + 32 + // update_authority
         4 + items_size + // items
         4 + collections_size + // root_collection
-        1 // bump
+        1 + // bump
+        1 + // verified_subject
+        1 + 8 + // finalized_slot
+        1 + 4 + // registry_page_index
+        4 // records_count
     }
 
     pub fn validate(&self) -> Result<()> {
@@ -157,4 +427,477 @@ impl Metadata {
 
         Ok(())
     }
+
+    /// Returns true if the collection_path addresses the root items rather than a collection
+    /// The root items are addressed by the Metadata's own MetadataKey Id
+    pub fn is_root(&self, collection_path: &[u64]) -> bool {
+        collection_path == [self.metadata_key_id]
+    }
+
+    /// Collections are sorted by their paths, nested collections follow their parent
+    fn search_collection(&self, collection_path: &[u64]) -> std::result::Result<usize, usize> {
+        self.collections
+            .binary_search_by(|collection| collection.path().cmp(collection_path.iter()))
+    }
+
+    pub fn collection_index(&self, collection_path: &[u64]) -> Result<usize> {
+        self.search_collection(collection_path)
+            .map_err(|_| error!(MythicMetadataError::MetadataCollectionNonExistent))
+    }
+
+    /// The rent shares of the root items, the collections and their items
+    /// The rent of the rest of the account is the issuing authority's
+    pub fn rent_shares(&self) -> impl Iterator<Item = RentShare> + '_ {
+        self.items.iter().flat_map(MetadataItem::rent_shares).chain(
+            self.collections
+                .iter()
+                .flat_map(MetadataCollection::rent_shares),
+        )
+    }
+
+    /// The authority the Metadata update authority checks are made against,
+    /// the issuing authority when the update authority was revoked and None once finalized
+    pub fn effective_update_authority(&self) -> Option<Pubkey> {
+        match self.finalized_slot {
+            Some(_) => None,
+            None => Some(self.update_authority.unwrap_or(self.issuing_authority)),
+        }
+    }
+
+    /// The hash of the serialized Metadata, finalize_metadata is confirmed with it
+    /// It changes with every update, so finalization can't be confirmed against a stale state
+    pub fn state_hash(&self) -> Result<[u8; 32]> {
+        Ok(hashv(&[&self.try_to_vec()?]).to_bytes())
+    }
+
+    fn verify_not_finalized(&self) -> Result<()> {
+        require!(
+            self.finalized_slot.is_none(),
+            MythicMetadataError::ImmutableMetadata
+        );
+
+        Ok(())
+    }
+
+    /// Verifies the Metadata can be closed, finalized Metadata is permanent and can't be closed
+    /// and the delegate records, pending changes and authority transfers must be closed first
+    pub fn verify_closable(&self) -> Result<()> {
+        self.verify_not_finalized()?;
+        self.verify_records_closed(0)
+    }
+
+    /// Verifies the delegate records, pending changes and authority transfers of the Metadata are
+    /// closed except for the given number of records authorizing the instruction
+    pub fn verify_records_closed(&self, authorizing_records: u32) -> Result<()> {
+        require!(
+            self.records_count <= authorizing_records,
+            MythicMetadataError::MetadataRecordsOpen
+        );
+
+        Ok(())
+    }
+
+    /// Counts a MetadataDelegate, PendingChange or AuthorityTransfer opened for the Metadata
+    pub fn open_record(&mut self) {
+        self.records_count += 1;
+    }
+
+    /// Counts a MetadataDelegate, PendingChange or AuthorityTransfer of the Metadata closed
+    pub fn close_record(&mut self) {
+        self.records_count = self.records_count.saturating_sub(1);
+    }
+
+    /// Makes the Metadata, its collections and items permanently immutable
+    pub fn finalize(&mut self, slot: u64) -> Result<()> {
+        self.verify_not_finalized()?;
+        self.finalized_slot = Some(slot);
+
+        Ok(())
+    }
+
+    /// Returns true if the collection identified by collection_path or one of its ancestors is frozen
+    /// The root items can't be frozen
+    pub fn is_frozen(&self, collection_path: &[u64]) -> Result<bool> {
+        if self.is_root(collection_path) {
+            return Ok(false);
+        }
+
+        for depth in 1..=collection_path.len() {
+            let collection_index = self.collection_index(&collection_path[..depth])?;
+            if self.collections[collection_index].frozen {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Verifies the collection can be changed, i.e. neither it nor its ancestors are frozen
+    /// and the Metadata isn't finalized
+    fn verify_not_frozen(&self, collection_path: &[u64]) -> Result<()> {
+        self.verify_not_finalized()?;
+        require!(
+            !self.is_frozen(collection_path)?,
+            MythicMetadataError::MetadataCollectionFrozen
+        );
+
+        Ok(())
+    }
+
+    /// Verifies neither the collection, its ancestors nor its nested collections are frozen
+    fn verify_subtree_not_frozen(&self, collection_path: &[u64]) -> Result<()> {
+        self.verify_not_frozen(collection_path)?;
+
+        let collection_index = self.collection_index(collection_path)?;
+        require!(
+            !self.collections[collection_index + 1..]
+                .iter()
+                .take_while(|collection| collection.is_descendant_of(collection_path))
+                .any(|collection| collection.frozen),
+            MythicMetadataError::MetadataCollectionFrozen
+        );
+
+        Ok(())
+    }
+
+    /// Freezes the collection and its nested collections permanently
+    pub fn freeze_collection(&mut self, collection_path: &[u64]) -> Result<()> {
+        self.verify_not_frozen(collection_path)?;
+
+        let collection_index = self.collection_index(collection_path)?;
+        self.collections[collection_index].frozen = true;
+
+        Ok(())
+    }
+
+    /// Inserts the collection keeping the collections sorted by their paths
+    /// A nested collection can only be appended to an existing parent collection
+    pub fn append_collection(&mut self, collection: MetadataCollection) -> Result<()> {
+        self.verify_not_finalized()?;
+        if !collection.parent_path.is_empty() {
+            self.verify_not_frozen(&collection.parent_path)?;
+        }
+
+        let collection_path = collection_path(&collection.parent_path, collection.metadata_key_id);
+        match self.search_collection(&collection_path) {
+            Ok(_) => err!(MythicMetadataError::MetadataCollectionAlreadyExists),
+            Err(collection_index) => {
+                self.collections.insert(collection_index, collection);
+                Ok(())
+            }
+        }
+    }
+
+    /// Sets the update authority of the Metadata or of the collection identified by collection_path
+    pub fn set_update_authority(
+        &mut self,
+        collection_path: &[u64],
+        update_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        if self.is_root(collection_path) {
+            self.verify_not_finalized()?;
+            self.update_authority = update_authority;
+        } else {
+            self.verify_not_frozen(collection_path)?;
+            let collection_index = self.collection_index(collection_path)?;
+            self.collections[collection_index].update_authority = update_authority;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_timelock(&mut self, collection_path: &[u64], timelock_slots: u64) -> Result<()> {
+        self.verify_not_frozen(collection_path)?;
+        let collection_index = self.collection_index(collection_path)?;
+        self.collections[collection_index].timelock_slots = timelock_slots;
+
+        Ok(())
+    }
+
+    /// Returns the timelock of the items of the collection identified by collection_path,
+    /// the longest timelock of the collection and its ancestors. The root items aren't timelocked
+    pub fn timelock_slots(&self, collection_path: &[u64]) -> Result<u64> {
+        if self.is_root(collection_path) {
+            return Ok(0);
+        }
+
+        let mut timelock_slots = 0;
+        for depth in 1..=collection_path.len() {
+            let collection_index = self.collection_index(&collection_path[..depth])?;
+            timelock_slots = timelock_slots.max(self.collections[collection_index].timelock_slots);
+        }
+
+        Ok(timelock_slots)
+    }
+
+    /// Verifies neither the collection, its ancestors nor its nested collections are timelocked
+    /// Removing or rekeying a collection changes its nested collections as well, so an unlocked
+    /// parent mustn't bypass the timelock of its nested collections
+    fn verify_subtree_not_timelocked(&self, collection_path: &[u64]) -> Result<()> {
+        require!(
+            self.timelock_slots(collection_path)? == 0,
+            MythicMetadataError::MetadataCollectionTimelocked
+        );
+
+        let collection_index = self.collection_index(collection_path)?;
+        require!(
+            !self.collections[collection_index + 1..]
+                .iter()
+                .take_while(|collection| collection.is_descendant_of(collection_path))
+                .any(|collection| collection.timelock_slots > 0),
+            MythicMetadataError::MetadataCollectionTimelocked
+        );
+
+        Ok(())
+    }
+
+    /// Removes the collection together with its nested collections
+    /// Returns the removed collections, the collection first
+    pub fn remove_collection(
+        &mut self,
+        collection_path: &[u64],
+    ) -> Result<Vec<MetadataCollection>> {
+        self.verify_subtree_not_frozen(collection_path)?;
+        self.verify_subtree_not_timelocked(collection_path)?;
+        let collection_index = self.collection_index(collection_path)?;
+        let nested_collections_count = self.collections[collection_index + 1..]
+            .iter()
+            .take_while(|collection| collection.is_descendant_of(collection_path))
+            .count();
+
+        Ok(self
+            .collections
+            .drain(collection_index..=collection_index + nested_collections_count)
+            .collect())
+    }
+
+    /// Copies the root items and collections of the source selected by collection_paths
+    /// The source's root path selects its root items and a collection path selects the collection
+    /// with its nested collections, the ancestors of a selected nested collection are copied
+    /// without their items. Everything is copied if collection_paths is empty
+    /// The copies are stamped with the slot and funded by the rent_payer, the collections inherit
+    /// the update authority and aren't timelocked or frozen
+    /// EVM address links aren't copied, their proofs are bound to the source's subject
+    pub fn copy_from(
+        &mut self,
+        source: &Metadata,
+        collection_paths: &[Vec<u64>],
+        slot: u64,
+        rent_payer: Pubkey,
+    ) -> Result<()> {
+        for collection_path in collection_paths {
+            if !source.is_root(collection_path) {
+                source.collection_index(collection_path)?;
+            }
+        }
+
+        let copy_all = collection_paths.is_empty();
+        let root_collection_path = [self.metadata_key_id];
+        let copied_items = |items: &'_ [MetadataItem]| {
+            items
+                .iter()
+                .filter(|item| !EvmAddressLink::is_proof_for(&item.value, &source.subject))
+                .map(|item| MetadataItem {
+                    metadata_key_id: item.metadata_key_id,
+                    update_slot: slot,
+                    value: item.value.clone(),
+                    rent_payer,
+                    growth_rent_shares: vec![],
+                })
+                .collect::<Vec<_>>()
+        };
+
+        if copy_all
+            || collection_paths
+                .iter()
+                .any(|collection_path| source.is_root(collection_path))
+        {
+            for item in copied_items(&source.items) {
+                self.append_item(&root_collection_path, item)?;
+            }
+        }
+
+        for collection in &source.collections {
+            let path = collection.path().copied().collect::<Vec<_>>();
+            let selected = copy_all
+                || collection_paths.iter().any(|collection_path| {
+                    !source.is_root(collection_path) && path.starts_with(collection_path)
+                });
+            let ancestor = collection_paths.iter().any(|collection_path| {
+                collection_path.len() > path.len() && collection_path.starts_with(&path)
+            });
+            if !selected && !ancestor {
+                continue;
+            }
+
+            if self.is_root(&path) {
+                return err!(MythicMetadataError::InvalidMetadataKey);
+            }
+            self.append_collection(MetadataCollection {
+                metadata_key_id: collection.metadata_key_id,
+                parent_path: collection.parent_path.clone(),
+                update_slot: slot,
+                update_authority: None,
+                timelock_slots: 0,
+                frozen: false,
+                items: vec![],
+                rent_payer,
+            })?;
+            if selected {
+                for item in copied_items(&collection.items) {
+                    self.append_item(&path, item)?;
+                }
+            }
+        }
+
+        self.update_slot = slot;
+
+        Ok(())
+    }
+
+    /// Changes the MetadataKey Id of the collection identified by collection_path
+    /// The parent paths of its nested collections are changed accordingly and the sorted order is kept
+    pub fn rekey_collection(
+        &mut self,
+        collection_path: &[u64],
+        new_metadata_key_id: u64,
+        slot: u64,
+    ) -> Result<()> {
+        self.verify_subtree_not_frozen(collection_path)?;
+        self.verify_subtree_not_timelocked(collection_path)?;
+        let collection_index = self.collection_index(collection_path)?;
+        let depth = collection_path.len() - 1;
+
+        let new_collection_path = [&collection_path[..depth], &[new_metadata_key_id]].concat();
+        if self.is_root(&new_collection_path) {
+            return err!(MythicMetadataError::InvalidMetadataKey);
+        }
+        if self.search_collection(&new_collection_path).is_ok() {
+            return err!(MythicMetadataError::MetadataCollectionAlreadyExists);
+        }
+
+        for collection in &mut self.collections {
+            if collection.is_descendant_of(collection_path) {
+                collection.parent_path[depth] = new_metadata_key_id;
+            }
+        }
+
+        let collection = &mut self.collections[collection_index];
+        collection.metadata_key_id = new_metadata_key_id;
+        collection.update_slot = slot;
+
+        self.collections
+            .sort_by(|collection, other| collection.path().cmp(other.path()));
+
+        Ok(())
+    }
+
+    /// Appends the item to the root items or to the collection identified by collection_path
+    pub fn append_item(&mut self, collection_path: &[u64], item: MetadataItem) -> Result<()> {
+        self.verify_not_frozen(collection_path)?;
+        if self.is_root(collection_path) {
+            let slot = item.update_slot;
+            insert_item(&mut self.items, item)?;
+            self.update_slot = slot;
+            Ok(())
+        } else {
+            let collection_index = self.collection_index(collection_path)?;
+            self.collections[collection_index].append_item(item)
+        }
+    }
+
+    /// Replaces the value of an existing item in the root items or in the collection
+    /// The rent payer of the item funds the growth, the rent shares of the bytes it shrunk by are returned
+    pub fn update_item(
+        &mut self,
+        collection_path: &[u64],
+        item: MetadataItem,
+    ) -> Result<Vec<RentShare>> {
+        self.verify_not_frozen(collection_path)?;
+        if self.is_root(collection_path) {
+            let slot = item.update_slot;
+            let rent_shares = replace_item(&mut self.items, item)?;
+            self.update_slot = slot;
+            Ok(rent_shares)
+        } else {
+            let collection_index = self.collection_index(collection_path)?;
+            self.collections[collection_index].update_item(item)
+        }
+    }
+
+    /// Returns the root items or the items of the collection identified by collection_path
+    pub fn collection_items(&self, collection_path: &[u64]) -> Result<&[MetadataItem]> {
+        if self.is_root(collection_path) {
+            Ok(&self.items)
+        } else {
+            Ok(&self.collections[self.collection_index(collection_path)?].items)
+        }
+    }
+
+    /// Returns the item of the collection identified by collection_path
+    pub fn item(
+        &self,
+        collection_path: &[u64],
+        item_metadata_key_id: u64,
+    ) -> Result<&MetadataItem> {
+        let items = self.collection_items(collection_path)?;
+        match items.binary_search_by_key(&item_metadata_key_id, |item| item.metadata_key_id) {
+            Ok(item_index) => Ok(&items[item_index]),
+            Err(_) => err!(MythicMetadataError::MetadataItemNonExistent),
+        }
+    }
+
+    /// Appends the item or replaces the value of the existing item
+    /// Returns the rent shares of the bytes an existing item shrunk by
+    pub fn set_item(
+        &mut self,
+        collection_path: &[u64],
+        item: MetadataItem,
+    ) -> Result<Vec<RentShare>> {
+        match self
+            .collection_items(collection_path)?
+            .binary_search_by_key(&item.metadata_key_id, |item| item.metadata_key_id)
+        {
+            Ok(_) => self.update_item(collection_path, item),
+            Err(_) => self.append_item(collection_path, item).map(|_| vec![]),
+        }
+    }
+
+    /// Moves the item to the destination collection
+    /// The update_slot of the item and of both collections is set to the slot
+    pub fn move_item(
+        &mut self,
+        source_collection_path: &[u64],
+        destination_collection_path: &[u64],
+        item_metadata_key_id: u64,
+        slot: u64,
+    ) -> Result<()> {
+        self.verify_not_frozen(destination_collection_path)?;
+
+        let mut item = self.remove_item(source_collection_path, item_metadata_key_id)?;
+        item.update_slot = slot;
+
+        if self.is_root(source_collection_path) {
+            self.update_slot = slot;
+        } else {
+            let collection_index = self.collection_index(source_collection_path)?;
+            self.collections[collection_index].update_slot = slot;
+        }
+
+        self.append_item(destination_collection_path, item)
+    }
+
+    pub fn remove_item(
+        &mut self,
+        collection_path: &[u64],
+        item_metadata_key_id: u64,
+    ) -> Result<MetadataItem> {
+        self.verify_not_frozen(collection_path)?;
+        if self.is_root(collection_path) {
+            remove_item(&mut self.items, item_metadata_key_id)
+        } else {
+            let collection_index = self.collection_index(collection_path)?;
+            self.collections[collection_index].remove_item(item_metadata_key_id)
+        }
+    }
 }
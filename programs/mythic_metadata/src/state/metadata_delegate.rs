@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+use crate::state::*;
+
+#[account]
+/// MetadataDelegate grants a delegate a set of permissions over a Metadata or one of its collections
+pub struct MetadataDelegate {
+    /// The Metadata the permissions are granted for
+    pub metadata: Pubkey,
+
+    /// The path of the collection the permissions are scoped to
+    /// The permissions apply to the collection and to its nested collections
+    /// If it's the Metadata's own MetadataKey Id then the permissions apply to the root items
+    /// and to all the collections of the Metadata
+    pub collection_path: Vec<u64>,
+
+    /// The authority the permissions are granted to
+    pub delegate: Pubkey,
+
+    /// Bitmask of the granted permissions
+    pub permissions: u8,
+
+    /// Bump
+    pub bump: u8,
+}
+
+impl MetadataDelegate {
+    /// Append metadata items
+    pub const APPEND: u8 = 1 << 0;
+    /// Update metadata items
+    pub const UPDATE: u8 = 1 << 1;
+    /// Remove metadata items
+    pub const REMOVE: u8 = 1 << 2;
+    /// Append and remove metadata collections
+    pub const MANAGE_COLLECTIONS: u8 = 1 << 3;
+    /// Set and revoke update authorities
+    pub const MANAGE_AUTHORITIES: u8 = 1 << 4;
+
+    pub const ALL_PERMISSIONS: u8 = Self::APPEND
+        | Self::UPDATE
+        | Self::REMOVE
+        | Self::MANAGE_COLLECTIONS
+        | Self::MANAGE_AUTHORITIES;
+
+    pub fn size(collection_path_len: usize) -> usize {
+        8 + // Anchor discriminator
+        32 + // metadata
+        4 + collection_path_len * 8 + // collection_path
+        32 + // delegate
+        1 + // permissions
+        1 // bump
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.permissions == 0 || self.permissions & !Self::ALL_PERMISSIONS != 0 {
+            return err!(MythicMetadataError::InvalidDelegatePermissions);
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the permission is granted for the collection identified by collection_path
+    pub fn is_granted(&self, metadata: &Metadata, collection_path: &[u64], permission: u8) -> bool {
+        let in_scope = metadata.is_root(&self.collection_path)
+            || collection_path.starts_with(&self.collection_path);
+
+        in_scope && self.permissions & permission == permission
+    }
+}
@@ -4,8 +4,9 @@ use crate::constants::*;
 use crate::errors::*;
 
 #[derive(Debug)]
-#[account]
+#[account("account_v2")]
 /// MetadataKey account defines a single metadata value
+/// The discriminator is namespaced by the layout version, see [crate::state::LegacyMetadataKey]
 pub struct MetadataKey {
     /// Id
     pub id: u64,
@@ -29,6 +30,18 @@ pub struct MetadataKey {
 
     /// Bump
     pub bump: u8,
+
+    /// True if the items of the key hold a list of values rather than a single value
+    /// The value of a multi-valued item is a borsh serialized Vec<Vec<u8>> of at most MAX_LIST_LEN values,
+    /// each of the content_type
+    pub multi_valued: bool,
+}
+
+// Namespaced accounts don't implement Owner
+impl Owner for MetadataKey {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
 }
 
 impl MetadataKey {
@@ -40,7 +53,8 @@ impl MetadataKey {
         4 + label.len() + // Label
         4 + description.len() + // Description
         4 + content_type.len() + // Content Type
-        1 // bump
+        1 + // bump
+        1 // multi_valued
     }
 
     pub fn validate(name: &str, label: &str, description: &str, content_type: &str) -> Result<()> {
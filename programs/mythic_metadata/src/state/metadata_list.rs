@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+/// Deserializes the values of a multi-valued item
+pub fn decode_list(value: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let values = Vec::<Vec<u8>>::try_from_slice(value)
+        .map_err(|_| error!(MythicMetadataError::InvalidMetadataList))?;
+
+    if values.len() > MAX_LIST_LEN {
+        return err!(MythicMetadataError::MetadataListFull);
+    }
+
+    Ok(values)
+}
+
+impl Metadata {
+    /// Applies the change to the values of an existing multi-valued item
+    /// The rent_payer funds the growth, the item rent payer when None as the change can only shrink it
+    /// Returns the rent shares of the bytes the item shrunk by
+    fn change_list(
+        &mut self,
+        collection_path: &[u64],
+        item_metadata_key_id: u64,
+        slot: u64,
+        rent_payer: Option<Pubkey>,
+        change: impl FnOnce(&mut Vec<Vec<u8>>) -> Result<()>,
+    ) -> Result<Vec<RentShare>> {
+        let items = self.collection_items(collection_path)?;
+        let item_index = items
+            .binary_search_by_key(&item_metadata_key_id, |item| item.metadata_key_id)
+            .map_err(|_| error!(MythicMetadataError::MetadataItemNonExistent))?;
+
+        let rent_payer = rent_payer.unwrap_or(items[item_index].rent_payer);
+        let mut values = decode_list(&items[item_index].value)?;
+        change(&mut values)?;
+
+        if values.len() > MAX_LIST_LEN {
+            return err!(MythicMetadataError::MetadataListFull);
+        }
+
+        self.update_item(
+            collection_path,
+            MetadataItem {
+                metadata_key_id: item_metadata_key_id,
+                update_slot: slot,
+                value: values.try_to_vec()?,
+                rent_payer,
+                growth_rent_shares: vec![],
+            },
+        )
+    }
+
+    /// Appends the value to the end of the list, the item is appended if it doesn't exist
+    /// The rent_payer funds an appended item or the growth of the existing item
+    pub fn push_list_value(
+        &mut self,
+        collection_path: &[u64],
+        item_metadata_key_id: u64,
+        value: Vec<u8>,
+        slot: u64,
+        rent_payer: Pubkey,
+    ) -> Result<()> {
+        let item_exists = self
+            .collection_items(collection_path)?
+            .binary_search_by_key(&item_metadata_key_id, |item| item.metadata_key_id)
+            .is_ok();
+
+        if item_exists {
+            self.change_list(
+                collection_path,
+                item_metadata_key_id,
+                slot,
+                Some(rent_payer),
+                |values| {
+                    values.push(value);
+                    Ok(())
+                },
+            )?;
+            Ok(())
+        } else {
+            self.append_item(
+                collection_path,
+                MetadataItem {
+                    metadata_key_id: item_metadata_key_id,
+                    update_slot: slot,
+                    value: vec![value].try_to_vec()?,
+                    rent_payer,
+                    growth_rent_shares: vec![],
+                },
+            )
+        }
+    }
+
+    /// Removes the value at the index, the following values are shifted down
+    /// Returns the rent shares of the bytes the item shrunk by
+    pub fn remove_list_value(
+        &mut self,
+        collection_path: &[u64],
+        item_metadata_key_id: u64,
+        index: u16,
+        slot: u64,
+    ) -> Result<Vec<RentShare>> {
+        self.change_list(
+            collection_path,
+            item_metadata_key_id,
+            slot,
+            None,
+            |values| {
+                require!(
+                    usize::from(index) < values.len(),
+                    MythicMetadataError::MetadataListIndexOutOfBounds
+                );
+                values.remove(usize::from(index));
+                Ok(())
+            },
+        )
+    }
+
+    /// Replaces the value at the index, the rent_payer funds the growth of the item
+    /// Returns the rent shares of the bytes the item shrunk by
+    pub fn replace_list_value(
+        &mut self,
+        collection_path: &[u64],
+        item_metadata_key_id: u64,
+        index: u16,
+        value: Vec<u8>,
+        slot: u64,
+        rent_payer: Pubkey,
+    ) -> Result<Vec<RentShare>> {
+        self.change_list(
+            collection_path,
+            item_metadata_key_id,
+            slot,
+            Some(rent_payer),
+            |values| {
+                let current_value = values
+                    .get_mut(usize::from(index))
+                    .ok_or(MythicMetadataError::MetadataListIndexOutOfBounds)?;
+                *current_value = value;
+                Ok(())
+            },
+        )
+    }
+
+    /// Removes all the values, the item is kept with an empty list
+    /// Returns the rent shares of the bytes the item shrunk by
+    pub fn clear_list(
+        &mut self,
+        collection_path: &[u64],
+        item_metadata_key_id: u64,
+        slot: u64,
+    ) -> Result<Vec<RentShare>> {
+        self.change_list(
+            collection_path,
+            item_metadata_key_id,
+            slot,
+            None,
+            |values| {
+                values.clear();
+                Ok(())
+            },
+        )
+    }
+}
@@ -1,5 +1,29 @@
+pub mod attestation;
+pub mod authority_transfer;
+pub mod endorsement;
+pub mod evm_address;
+pub mod external_reference;
+pub mod legacy;
 pub mod metadata;
+pub mod metadata_delegate;
 pub mod metadata_key;
+pub mod metadata_list;
+pub mod multisig_authority;
+pub mod namespace_registry;
+pub mod pending_change;
+pub mod subject_registry;
 
+pub use attestation::*;
+pub use authority_transfer::*;
+pub use endorsement::*;
+pub use evm_address::*;
+pub use external_reference::*;
+pub use legacy::*;
 pub use metadata::*;
+pub use metadata_delegate::*;
 pub use metadata_key::*;
+pub use metadata_list::*;
+pub use multisig_authority::*;
+pub use namespace_registry::*;
+pub use pending_change::*;
+pub use subject_registry::*;
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+
+#[derive(Debug)]
+#[account]
+/// MultisigAuthority is an M-of-N update authority
+/// Its address is set as the update authority of a Metadata or a collection and the update
+/// instructions accept it when at least threshold of its signers sign the transaction
+/// The signers and the threshold can't change, a new MultisigAuthority is assigned instead
+pub struct MultisigAuthority {
+    /// The key the address is derived from, any unique key
+    pub create_key: Pubkey,
+
+    /// The number of signers required
+    pub threshold: u8,
+
+    /// The signers sorted by address
+    pub signers: Vec<Pubkey>,
+
+    /// Bump
+    pub bump: u8,
+}
+
+impl MultisigAuthority {
+    pub fn size(signers_len: usize) -> usize {
+        8 + // Anchor discriminator
+        32 + // create_key
+        1 + // threshold
+        4 + signers_len * 32 + // signers
+        1 // bump
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.threshold > 0
+                && usize::from(self.threshold) <= self.signers.len()
+                && self.signers.len() <= MAX_MULTISIG_SIGNERS
+                && self.signers.windows(2).all(|pair| pair[0] < pair[1]),
+            MythicMetadataError::InvalidMultisigAuthority
+        );
+
+        Ok(())
+    }
+
+    /// Whether at least threshold of the signers are among the transaction signers
+    pub fn is_approved_by(&self, transaction_signers: &[Pubkey]) -> bool {
+        let approvals = self
+            .signers
+            .iter()
+            .filter(|signer| transaction_signers.contains(signer))
+            .count();
+        approvals >= usize::from(self.threshold)
+    }
+}
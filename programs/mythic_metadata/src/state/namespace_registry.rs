@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+#[derive(Debug)]
+#[account]
+/// NamespaceRegistry counts the MetadataKeys created by a namespace authority
+/// The keys are listed in NamespaceRegistryPages of NAMESPACE_REGISTRY_PAGE_LEN keys each,
+/// so readers can walk the pages 0..page_count() instead of scanning the program accounts
+pub struct NamespaceRegistry {
+    /// The namespace authority of the listed MetadataKeys
+    pub namespace_authority: Pubkey,
+
+    /// The number of listed MetadataKeys
+    pub keys_count: u64,
+
+    /// Bump
+    pub bump: u8,
+}
+
+impl NamespaceRegistry {
+    pub fn size() -> usize {
+        8 + // Anchor discriminator
+        32 + // namespace_authority
+        8 + // keys_count
+        1 // bump
+    }
+
+    /// The index of the page the next MetadataKey is listed in
+    pub fn next_page_index(&self) -> u32 {
+        (self.keys_count / NAMESPACE_REGISTRY_PAGE_LEN as u64) as u32
+    }
+
+    /// The number of pages listing the MetadataKeys
+    pub fn page_count(&self) -> u32 {
+        self.keys_count.div_ceil(NAMESPACE_REGISTRY_PAGE_LEN as u64) as u32
+    }
+}
+
+/// A MetadataKey listed in a NamespaceRegistryPage
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct NamespaceRegistryEntry {
+    /// The MetadataKey Id
+    pub id: u64,
+    pub name: String,
+    pub content_type: String,
+}
+
+impl NamespaceRegistryEntry {
+    pub fn size(name: &str, content_type: &str) -> usize {
+        8 + // id
+        4 + name.len() + // name
+        4 + content_type.len() // content_type
+    }
+}
+
+#[derive(Debug)]
+#[account]
+/// A page of the MetadataKeys of a namespace in creation order
+pub struct NamespaceRegistryPage {
+    /// The namespace authority of the listed MetadataKeys
+    pub namespace_authority: Pubkey,
+
+    /// The index of the page
+    pub page_index: u32,
+
+    /// At most NAMESPACE_REGISTRY_PAGE_LEN MetadataKeys
+    pub entries: Vec<NamespaceRegistryEntry>,
+
+    /// Bump
+    pub bump: u8,
+}
+
+impl NamespaceRegistryPage {
+    pub fn size(entries: &[NamespaceRegistryEntry]) -> usize {
+        let entries_size = entries.iter().fold(0, |acc, entry| {
+            acc + NamespaceRegistryEntry::size(&entry.name, &entry.content_type)
+        });
+
+        8 + // Anchor discriminator
+        32 + // namespace_authority
+        4 + // page_index
+        4 + entries_size + // entries
+        1 // bump
+    }
+}
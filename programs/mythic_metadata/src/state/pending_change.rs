@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum MetadataChange {
+    Update { new_value: Vec<u8> },
+    Remove,
+}
+
+impl MetadataChange {
+    pub fn size(&self) -> usize {
+        1 + match self {
+            MetadataChange::Update { new_value } => 4 + new_value.len(),
+            MetadataChange::Remove => 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[account]
+/// PendingChange queues an update or a removal of an item of a timelocked collection
+/// The Metadata update authority can cancel it until it's executed after the executable_slot
+/// An item has at most one PendingChange
+pub struct PendingChange {
+    pub metadata: Pubkey,
+
+    /// The path of the collection holding the item
+    pub collection_path: Vec<u64>,
+
+    pub item_metadata_key_id: u64,
+
+    pub change: MetadataChange,
+
+    /// The payer of the rent, refunded when the change is executed or cancelled
+    pub payer: Pubkey,
+
+    /// The lamports the payer prepaid for the growth of the Metadata account by an update,
+    /// held by the PendingChange on top of its rent and refunded with it when not used
+    pub prepaid_lamports: u64,
+
+    /// The first slot the change can be executed in
+    pub executable_slot: u64,
+
+    /// Bump
+    pub bump: u8,
+}
+
+impl PendingChange {
+    pub fn size(collection_path_len: usize, change: &MetadataChange) -> usize {
+        8 + // Anchor discriminator
+        32 + // metadata
+        4 + collection_path_len * 8 + // collection_path
+        8 + // item_metadata_key_id
+        change.size() + // change
+        32 + // payer
+        8 + // prepaid_lamports
+        8 + // executable_slot
+        1 // bump
+    }
+}
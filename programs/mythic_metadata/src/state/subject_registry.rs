@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+/// Identifies a Metadata about the registry's subject, together with the subject they make up its seeds
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub struct SubjectRegistryEntry {
+    pub metadata_key_id: u64,
+    pub issuing_authority: Pubkey,
+}
+
+impl SubjectRegistryEntry {
+    pub fn size() -> usize {
+        8 + // metadata_key_id
+        32 // issuing_authority
+    }
+}
+
+#[derive(Debug)]
+#[account]
+/// SubjectRegistry counts the Metadata accounts registered for a subject
+/// The Metadata are listed in SubjectRegistryPages of SUBJECT_REGISTRY_PAGE_LEN entries each,
+/// so readers can walk the pages 0..page_count() instead of using getProgramAccounts
+///
+/// Metadata are registered when they're created and removed from their page when they're closed.
+/// A new page is started once the last one is full, so registering never fails.
+/// Metadata created by the token metadata interface can be registered by anyone with register_metadata
+pub struct SubjectRegistry {
+    /// The subject of the registered Metadata accounts
+    pub subject: Pubkey,
+
+    /// The number of Metadata accounts ever registered, including the closed ones
+    pub registered_count: u64,
+
+    /// Bump
+    pub bump: u8,
+}
+
+impl SubjectRegistry {
+    pub fn size() -> usize {
+        8 + // Anchor discriminator
+        32 + // subject
+        8 + // registered_count
+        1 // bump
+    }
+
+    /// The index of the page the next Metadata is listed in
+    pub fn next_page_index(&self) -> u32 {
+        (self.registered_count / SUBJECT_REGISTRY_PAGE_LEN as u64) as u32
+    }
+
+    /// The number of pages listing the Metadata
+    pub fn page_count(&self) -> u32 {
+        self.registered_count
+            .div_ceil(SUBJECT_REGISTRY_PAGE_LEN as u64) as u32
+    }
+}
+
+#[derive(Debug)]
+#[account]
+/// A page of the Metadata registered for a subject in registration order
+/// The entries of closed Metadata are removed, their places aren't reused
+pub struct SubjectRegistryPage {
+    /// The subject of the listed Metadata accounts
+    pub subject: Pubkey,
+
+    /// The index of the page
+    pub page_index: u32,
+
+    /// At most SUBJECT_REGISTRY_PAGE_LEN Metadata accounts
+    pub entries: Vec<SubjectRegistryEntry>,
+
+    /// Bump
+    pub bump: u8,
+}
+
+impl SubjectRegistryPage {
+    pub fn size(entries_count: usize) -> usize {
+        8 + // Anchor discriminator
+        32 + // subject
+        4 + // page_index
+        4 + entries_count * SubjectRegistryEntry::size() + // entries
+        1 // bump
+    }
+
+    /// Removes the entry if it's listed
+    pub fn remove(&mut self, entry: &SubjectRegistryEntry) {
+        self.entries.retain(|listed| listed != entry);
+    }
+}
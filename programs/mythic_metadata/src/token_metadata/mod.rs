@@ -0,0 +1,371 @@
+//! Adapter implementing the spl-token-metadata-interface over Metadata accounts
+//!
+//! A Token-2022 mint can point its metadata pointer extension at the mint's token Metadata,
+//! derived from the mint alone, see find_token_metadata_address. The name, symbol and uri are
+//! root items and the additional fields are items of a reserved collection whose ids are derived
+//! from the field keys. The interface instructions aren't Anchor instructions and are dispatched
+//! from the program's fallback by their interface discriminators
+
+pub mod processor;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+use crate::utils::*;
+
+/// A field of the token metadata
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum Field {
+    Name,
+    Symbol,
+    Uri,
+    /// An additional field
+    Key(String),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Initialize {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct UpdateField {
+    pub field: Field,
+    pub value: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct RemoveKey {
+    /// Succeed even if the key doesn't exist
+    pub idempotent: bool,
+    pub key: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct UpdateAuthority {
+    /// The proposed update authority, the default Pubkey makes the metadata immutable
+    pub new_authority: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Emit {
+    pub start: Option<u64>,
+    pub end: Option<u64>,
+}
+
+/// The spl-token-metadata-interface instructions
+///
+/// Accounts expected by each instruction:
+/// - Initialize: [writable] metadata, [] update authority, [] mint, [signer] mint authority,
+///   optionally [writable, signer] payer and [] system program, otherwise the metadata must
+///   already hold the rent exempt lamports and the system program must be in the transaction
+/// - UpdateField: [writable] metadata, [signer] update authority,
+///   optionally [writable, signer] payer and [] system program, without them the field can't grow
+/// - RemoveKey: [writable] metadata, [signer] update authority
+/// - UpdateAuthority: [writable] metadata, [signer] current update authority, followed by
+///   [writable] authority transfer, [writable, signer] payer and [] system program unless the
+///   update authority is cleared. Unlike Token-2022 the new update authority isn't set right away,
+///   it's proposed with an AuthorityTransfer and set once accepted by accept_update_authority_transfer
+/// - Emit: [] metadata
+///
+/// A MultisigAuthority update authority is passed without signing, followed by the accounts
+/// above and then by enough of its signers as [signer] accounts
+///
+/// The rent of fields shrunk by UpdateField or removed by RemoveKey is refunded to their rent
+/// payers passed as [writable] accounts after the accounts above, otherwise it stays in the metadata
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenMetadataInstruction {
+    Initialize(Initialize),
+    UpdateField(UpdateField),
+    RemoveKey(RemoveKey),
+    UpdateAuthority(UpdateAuthority),
+    Emit(Emit),
+}
+
+impl TokenMetadataInstruction {
+    /// sha256("spl_token_metadata_interface:initialize_account")[..8]
+    pub const INITIALIZE: [u8; 8] = [210, 225, 30, 162, 88, 184, 77, 141];
+    /// sha256("spl_token_metadata_interface:updating_field")[..8]
+    pub const UPDATE_FIELD: [u8; 8] = [221, 233, 49, 45, 181, 202, 220, 200];
+    /// sha256("spl_token_metadata_interface:remove_key_ix")[..8]
+    pub const REMOVE_KEY: [u8; 8] = [234, 18, 32, 56, 89, 141, 37, 181];
+    /// sha256("spl_token_metadata_interface:update_the_authority")[..8]
+    pub const UPDATE_AUTHORITY: [u8; 8] = [215, 228, 166, 228, 84, 100, 86, 123];
+    /// sha256("spl_token_metadata_interface:emitter")[..8]
+    pub const EMIT: [u8; 8] = [250, 166, 180, 250, 13, 12, 184, 70];
+
+    /// Returns None if the data isn't a token metadata interface instruction
+    pub fn unpack(data: &[u8]) -> Option<Result<Self>> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, mut args) = data.split_at(8);
+
+        fn deserialize<T: AnchorDeserialize>(args: &mut &[u8]) -> Result<T> {
+            T::deserialize(args).map_err(|_| ErrorCode::InstructionDidNotDeserialize.into())
+        }
+
+        let instruction = match discriminator {
+            d if d == Self::INITIALIZE => deserialize(&mut args).map(Self::Initialize),
+            d if d == Self::UPDATE_FIELD => deserialize(&mut args).map(Self::UpdateField),
+            d if d == Self::REMOVE_KEY => deserialize(&mut args).map(Self::RemoveKey),
+            d if d == Self::UPDATE_AUTHORITY => deserialize(&mut args).map(Self::UpdateAuthority),
+            d if d == Self::EMIT => deserialize(&mut args).map(Self::Emit),
+            _ => return None,
+        };
+
+        Some(instruction)
+    }
+
+    pub fn pack(&self) -> Vec<u8> {
+        let (discriminator, args) = match self {
+            Self::Initialize(args) => (Self::INITIALIZE, args.try_to_vec()),
+            Self::UpdateField(args) => (Self::UPDATE_FIELD, args.try_to_vec()),
+            Self::RemoveKey(args) => (Self::REMOVE_KEY, args.try_to_vec()),
+            Self::UpdateAuthority(args) => (Self::UPDATE_AUTHORITY, args.try_to_vec()),
+            Self::Emit(args) => (Self::EMIT, args.try_to_vec()),
+        };
+
+        [discriminator.as_ref(), &args.unwrap()].concat()
+    }
+}
+
+/// The token metadata as returned by the Emit instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct TokenMetadata {
+    /// The update authority, the default Pubkey if the metadata is immutable
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// The additional fields ordered by their item ids
+    pub additional_metadata: Vec<(String, String)>,
+}
+
+impl TokenMetadata {
+    pub fn from_metadata(metadata: &Metadata) -> Result<Self> {
+        let root_value = |metadata_key_id: u64| {
+            metadata
+                .items
+                .binary_search_by_key(&metadata_key_id, |item| item.metadata_key_id)
+                .ok()
+                .map_or(Ok(String::new()), |item_index| {
+                    string_value(&metadata.items[item_index].value)
+                })
+        };
+
+        let additional_metadata = match metadata.collection_index(&[TOKEN_METADATA_FIELDS_KEY_ID]) {
+            Ok(collection_index) => metadata.collections[collection_index]
+                .items
+                .iter()
+                .map(|item| field_value(&item.value))
+                .collect::<Result<Vec<_>>>()?,
+            Err(_) => vec![],
+        };
+
+        Ok(Self {
+            update_authority: metadata.effective_update_authority().unwrap_or_default(),
+            mint: metadata.subject,
+            name: root_value(TOKEN_METADATA_NAME_KEY_ID)?,
+            symbol: root_value(TOKEN_METADATA_SYMBOL_KEY_ID)?,
+            uri: root_value(TOKEN_METADATA_URI_KEY_ID)?,
+            additional_metadata,
+        })
+    }
+}
+
+/// Returns the item id of an additional field in the TOKEN_METADATA_FIELDS_KEY_ID collection
+pub fn field_item_id(key: &str) -> u64 {
+    let hash = hash(key.as_bytes());
+    u64::from_le_bytes(hash.to_bytes()[..8].try_into().unwrap())
+}
+
+fn string_value(value: &[u8]) -> Result<String> {
+    String::from_utf8(value.to_vec())
+        .map_err(|_| error!(MythicMetadataError::InvalidTokenMetadataField))
+}
+
+/// Additional field items hold the key, to detect id collisions, and the value
+fn field_value(value: &[u8]) -> Result<(String, String)> {
+    <(String, String)>::try_from_slice(value)
+        .map_err(|_| error!(MythicMetadataError::InvalidTokenMetadataField))
+}
+
+fn field_item_index(
+    items: &[MetadataItem],
+    key: &str,
+) -> Result<std::result::Result<usize, usize>> {
+    let item_index = items.binary_search_by_key(&field_item_id(key), |item| item.metadata_key_id);
+    if let Ok(item_index) = item_index {
+        require!(
+            field_value(&items[item_index].value)?.0 == key,
+            MythicMetadataError::InvalidTokenMetadataField
+        );
+    }
+
+    Ok(item_index)
+}
+
+/// Builds the token Metadata of the mint with the initial name, symbol and uri funded by the rent_payer
+pub fn new_token_metadata(
+    mint: Pubkey,
+    update_authority: Option<Pubkey>,
+    args: Initialize,
+    slot: u64,
+    bump: u8,
+    rent_payer: Pubkey,
+) -> Result<Metadata> {
+    let mut metadata = Metadata {
+        subject: mint,
+        metadata_key_id: TOKEN_METADATA_KEY_ID,
+        issuing_authority: mint,
+        update_slot: slot,
+        update_authority,
+        items: vec![],
+        collections: vec![],
+        bump,
+        // The mint authority signed the initialization
+        verified_subject: true,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    };
+
+    for (metadata_key_id, value) in [
+        (TOKEN_METADATA_NAME_KEY_ID, args.name),
+        (TOKEN_METADATA_SYMBOL_KEY_ID, args.symbol),
+        (TOKEN_METADATA_URI_KEY_ID, args.uri),
+    ] {
+        metadata.append_item(
+            &[TOKEN_METADATA_KEY_ID],
+            MetadataItem {
+                metadata_key_id,
+                update_slot: slot,
+                value: value.into_bytes(),
+                rent_payer,
+                growth_rent_shares: vec![],
+            },
+        )?;
+    }
+
+    metadata.append_collection(MetadataCollection {
+        metadata_key_id: TOKEN_METADATA_FIELDS_KEY_ID,
+        parent_path: vec![],
+        update_slot: slot,
+        update_authority: None,
+        timelock_slots: 0,
+        frozen: false,
+        items: vec![],
+        rent_payer,
+    })?;
+
+    // The interface initializes immutable metadata without an update authority,
+    // it must not fall back to the mint as the issuing authority
+    if metadata.update_authority.is_none() {
+        metadata.finalize(slot)?;
+    }
+
+    metadata.validate()?;
+
+    Ok(metadata)
+}
+
+/// Sets the value of the field, additional fields are appended if they don't exist
+/// The rent_payer funds appended fields and the growth of existing ones, without a rent_payer
+/// the field can't grow
+/// Returns the rent shares of the bytes the field shrunk by
+pub fn update_field(
+    metadata: &mut Metadata,
+    args: UpdateField,
+    slot: u64,
+    rent_payer: Option<Pubkey>,
+) -> Result<Vec<RentShare>> {
+    let size = Metadata::size(&metadata.items, &metadata.collections);
+    let rent_shares = set_field(metadata, args, slot, rent_payer.unwrap_or_default())?;
+
+    // The growth would be recorded as funded by an account which didn't pay for it
+    require!(
+        rent_payer.is_some() || Metadata::size(&metadata.items, &metadata.collections) <= size,
+        MythicMetadataError::RentPayerRequired
+    );
+
+    Ok(rent_shares)
+}
+
+fn set_field(
+    metadata: &mut Metadata,
+    args: UpdateField,
+    slot: u64,
+    rent_payer: Pubkey,
+) -> Result<Vec<RentShare>> {
+    let UpdateField { field, value } = args;
+
+    let metadata_key_id = match field {
+        Field::Name => TOKEN_METADATA_NAME_KEY_ID,
+        Field::Symbol => TOKEN_METADATA_SYMBOL_KEY_ID,
+        Field::Uri => TOKEN_METADATA_URI_KEY_ID,
+        Field::Key(key) => return update_additional_field(metadata, key, value, slot, rent_payer),
+    };
+
+    metadata.update_item(
+        &[TOKEN_METADATA_KEY_ID],
+        MetadataItem {
+            metadata_key_id,
+            update_slot: slot,
+            value: value.into_bytes(),
+            rent_payer,
+            growth_rent_shares: vec![],
+        },
+    )
+}
+
+fn update_additional_field(
+    metadata: &mut Metadata,
+    key: String,
+    value: String,
+    slot: u64,
+    rent_payer: Pubkey,
+) -> Result<Vec<RentShare>> {
+    verify_not_timelocked(metadata, &[TOKEN_METADATA_FIELDS_KEY_ID])?;
+
+    let collection_index = metadata.collection_index(&[TOKEN_METADATA_FIELDS_KEY_ID])?;
+    let item_index = field_item_index(&metadata.collections[collection_index].items, &key)?;
+    let item = MetadataItem {
+        metadata_key_id: field_item_id(&key),
+        update_slot: slot,
+        value: (key, value).try_to_vec()?,
+        rent_payer,
+        growth_rent_shares: vec![],
+    };
+
+    match item_index {
+        Ok(_) => metadata.update_item(&[TOKEN_METADATA_FIELDS_KEY_ID], item),
+        Err(_) => metadata
+            .append_item(&[TOKEN_METADATA_FIELDS_KEY_ID], item)
+            .map(|_| vec![]),
+    }
+}
+
+/// Removes an additional field
+/// Returns the rent shares of the removed field
+pub fn remove_key(metadata: &mut Metadata, args: RemoveKey) -> Result<Vec<RentShare>> {
+    let RemoveKey { idempotent, key } = args;
+
+    verify_not_timelocked(metadata, &[TOKEN_METADATA_FIELDS_KEY_ID])?;
+
+    let collection_index = metadata.collection_index(&[TOKEN_METADATA_FIELDS_KEY_ID])?;
+    match field_item_index(&metadata.collections[collection_index].items, &key)? {
+        Ok(_) => Ok(metadata
+            .remove_item(&[TOKEN_METADATA_FIELDS_KEY_ID], field_item_id(&key))?
+            .rent_shares()
+            .collect()),
+        Err(_) if idempotent => Ok(vec![]),
+        Err(_) => err!(MythicMetadataError::MetadataItemNonExistent),
+    }
+}
@@ -0,0 +1,349 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke_signed, set_return_data};
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::system_program::{create_account, CreateAccount};
+use anchor_lang::AccountsExit;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+use crate::token_metadata::*;
+use crate::utils::*;
+
+/// Returns the token Metadata address of the mint
+pub fn find_token_metadata_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    let (metadata_key, _) = Pubkey::find_program_address(
+        &[PREFIX, METADATA_KEY, &TOKEN_METADATA_KEY_ID.to_le_bytes()],
+        program_id,
+    );
+    Pubkey::find_program_address(
+        &[
+            PREFIX,
+            METADATA,
+            metadata_key.as_ref(),
+            mint.as_ref(),
+            mint.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+pub fn process<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    data: &[u8],
+) -> Result<()> {
+    let instruction =
+        TokenMetadataInstruction::unpack(data).ok_or(ErrorCode::InstructionFallbackNotFound)??;
+
+    match instruction {
+        TokenMetadataInstruction::Initialize(args) => initialize(program_id, accounts, args),
+        TokenMetadataInstruction::UpdateField(args) => {
+            let (metadata, remaining_accounts) = load_for_update(accounts)?;
+            let slot = Clock::get()?.slot;
+            // Without a payer the field can't grow, the update authority may be a
+            // MultisigAuthority which can't receive refunds
+            let rent_payer = payer_accounts(remaining_accounts).map(|(payer, _)| *payer.key);
+            update(program_id, metadata, remaining_accounts, |metadata| {
+                update_field(metadata, args, slot, rent_payer)
+            })
+        }
+        TokenMetadataInstruction::RemoveKey(args) => {
+            let (metadata, remaining_accounts) = load_for_update(accounts)?;
+            update(program_id, metadata, remaining_accounts, |metadata| {
+                remove_key(metadata, args)
+            })
+        }
+        TokenMetadataInstruction::UpdateAuthority(args) => {
+            let (metadata, remaining_accounts) = load_for_update(accounts)?;
+            if args.new_authority != Pubkey::default() {
+                return propose_update_authority(
+                    program_id,
+                    metadata,
+                    remaining_accounts,
+                    args.new_authority,
+                );
+            }
+
+            // The interface makes the metadata immutable by clearing the update authority
+            let metadata_address = metadata.key();
+            let slot = Clock::get()?.slot;
+            update(program_id, metadata, remaining_accounts, |metadata| {
+                metadata.update_authority = None;
+                metadata.finalize(slot)?;
+                emit!(MetadataFinalized {
+                    metadata: metadata_address,
+                    slot,
+                });
+                Ok(vec![])
+            })
+        }
+        TokenMetadataInstruction::Emit(args) => {
+            let metadata_info = accounts.first().ok_or(ErrorCode::AccountNotEnoughKeys)?;
+            let metadata = load(metadata_info)?;
+            let token_metadata = TokenMetadata::from_metadata(&metadata)?.try_to_vec()?;
+
+            let start = args.start.unwrap_or(0) as usize;
+            let end = args.end.map_or(token_metadata.len(), |end| end as usize);
+            // Like Token-2022 nothing is returned for an invalid range
+            if let Some(bytes) = token_metadata.get(start..end) {
+                set_return_data(bytes);
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn load<'info>(metadata_info: &'info AccountInfo<'info>) -> Result<Account<'info, Metadata>> {
+    let metadata = Account::<Metadata>::try_from(metadata_info)?;
+    require!(
+        metadata.metadata_key_id == TOKEN_METADATA_KEY_ID,
+        MythicMetadataError::InvalidMetadataKey
+    );
+
+    Ok(metadata)
+}
+
+/// Loads the metadata and verifies the update authority signed
+/// A MultisigAuthority update authority doesn't sign, enough of its signers sign among the
+/// accounts following it instead
+/// Returns the accounts following the update authority
+fn load_for_update<'info>(
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<(Account<'info, Metadata>, &'info [AccountInfo<'info>])> {
+    let [metadata_info, update_authority, remaining_accounts @ ..] = accounts else {
+        return Err(ErrorCode::AccountNotEnoughKeys.into());
+    };
+
+    let metadata = load(metadata_info)?;
+    let is_update_authority = if update_authority.is_signer {
+        verify_metadata_update_authority(&metadata, update_authority.key)?
+    } else {
+        let multisig_authority = Account::<MultisigAuthority>::try_from(update_authority)?;
+        let signer = remaining_accounts
+            .iter()
+            .find(|account| account.is_signer)
+            .ok_or(ErrorCode::AccountNotSigner)?;
+        verify_metadata_update_authority(
+            &metadata,
+            &MultisigSigners::new(signer.key(), Some(&multisig_authority), remaining_accounts),
+        )?
+    };
+    require!(is_update_authority, MythicMetadataError::Unauthorized);
+
+    Ok((metadata, remaining_accounts))
+}
+
+/// Returns the optional payer and system program following the update authority
+/// The signers of a MultisigAuthority update authority may follow them
+fn payer_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Option<(&'a AccountInfo<'info>, &'a AccountInfo<'info>)> {
+    match remaining_accounts {
+        [payer, system_program, ..] if system_program.key() == System::id() => {
+            Some((payer, system_program))
+        }
+        _ => None,
+    }
+}
+
+/// Applies the change, resizes the account and persists the metadata
+///
+/// The optional payer and system program pay for the growth, without them the metadata
+/// account must already hold the rent exempt lamports for the new size
+/// The rent shares returned by the change are refunded to the rent payers among the remaining accounts
+fn update<'info>(
+    program_id: &Pubkey,
+    mut metadata: Account<'info, Metadata>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    change: impl FnOnce(&mut Metadata) -> Result<Vec<RentShare>>,
+) -> Result<()> {
+    let rent_shares = change(&mut metadata)?;
+    metadata.validate()?;
+
+    let metadata_info = metadata.to_account_info();
+    let metadata_new_size = Metadata::size(&metadata.items, &metadata.collections);
+    match payer_accounts(remaining_accounts) {
+        Some((payer, system_program)) => realloc_account(
+            metadata_info,
+            metadata_new_size,
+            payer.clone(),
+            system_program.clone(),
+        )?,
+        _ if metadata_info.data_len() < metadata_new_size => {
+            require!(
+                Rent::get()?.is_exempt(metadata_info.lamports(), metadata_new_size),
+                ErrorCode::ConstraintRentExempt
+            );
+            metadata_info.realloc(metadata_new_size, false)?;
+        }
+        _ => {}
+    }
+    refund_rent(
+        &metadata.to_account_info(),
+        metadata_new_size,
+        rent_shares,
+        remaining_accounts,
+    )?;
+
+    metadata.exit(program_id)
+}
+
+/// Proposes the new update authority with an AuthorityTransfer like
+/// propose_update_authority_transfer, the new update authority accepts it with
+/// accept_update_authority_transfer so a mistyped authority can't lock the update authority out
+fn propose_update_authority<'info>(
+    program_id: &Pubkey,
+    mut metadata: Account<'info, Metadata>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    new_update_authority: Pubkey,
+) -> Result<()> {
+    let [authority_transfer_info, payer, system_program, ..] = remaining_accounts else {
+        return Err(ErrorCode::AccountNotEnoughKeys.into());
+    };
+    require!(payer.is_signer, ErrorCode::AccountNotSigner);
+
+    let collection_path = vec![TOKEN_METADATA_KEY_ID];
+    require!(
+        !metadata.is_frozen(&collection_path)?,
+        MythicMetadataError::MetadataCollectionFrozen
+    );
+
+    let metadata_address = metadata.key();
+    let path_seed = path_seed(&collection_path);
+    let (authority_transfer_address, bump) = Pubkey::find_program_address(
+        &[
+            PREFIX,
+            AUTHORITY_TRANSFER,
+            metadata_address.as_ref(),
+            &path_seed,
+        ],
+        program_id,
+    );
+    require_keys_eq!(
+        authority_transfer_info.key(),
+        authority_transfer_address,
+        ErrorCode::ConstraintSeeds
+    );
+
+    // Fails for a pending proposal, which must be cancelled first
+    let space = AuthorityTransfer::size(collection_path.len());
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            CreateAccount {
+                from: payer.clone(),
+                to: authority_transfer_info.clone(),
+            },
+            &[&[
+                PREFIX,
+                AUTHORITY_TRANSFER,
+                metadata_address.as_ref(),
+                &path_seed,
+                &[bump],
+            ]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        program_id,
+    )?;
+
+    AuthorityTransfer {
+        metadata: metadata_address,
+        collection_path,
+        new_update_authority,
+        payer: payer.key(),
+        bump,
+    }
+    .try_serialize(&mut &mut authority_transfer_info.try_borrow_mut_data()?[..])?;
+
+    metadata.open_record();
+    metadata.exit(program_id)
+}
+
+fn initialize<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    args: Initialize,
+) -> Result<()> {
+    let [metadata_info, update_authority, mint, mint_authority, remaining_accounts @ ..] = accounts
+    else {
+        return Err(ErrorCode::AccountNotEnoughKeys.into());
+    };
+    require!(mint_authority.is_signer, ErrorCode::AccountNotSigner);
+
+    // The mint authority proves control of the mint
+    verify_subject_authority(mint.key, mint_authority.key, Some(mint))?;
+
+    let (metadata_address, bump) = find_token_metadata_address(mint.key, program_id);
+    require_keys_eq!(
+        metadata_info.key(),
+        metadata_address,
+        ErrorCode::ConstraintSeeds
+    );
+
+    let payer_accounts = payer_accounts(remaining_accounts);
+    // Without a payer the mint authority initialized the prefunded account
+    let rent_payer = *payer_accounts
+        .map_or(mint_authority, |(payer, _)| payer)
+        .key;
+    let update_authority = Some(*update_authority.key)
+        .filter(|update_authority| *update_authority != Pubkey::default());
+    let metadata = new_token_metadata(
+        *mint.key,
+        update_authority,
+        args,
+        Clock::get()?.slot,
+        bump,
+        rent_payer,
+    )?;
+
+    let (metadata_key, _) = Pubkey::find_program_address(
+        &[PREFIX, METADATA_KEY, &TOKEN_METADATA_KEY_ID.to_le_bytes()],
+        program_id,
+    );
+    let signer_seeds: &[&[u8]] = &[
+        PREFIX,
+        METADATA,
+        metadata_key.as_ref(),
+        mint.key.as_ref(),
+        mint.key.as_ref(),
+        &[bump],
+    ];
+    let space = Metadata::size(&metadata.items, &metadata.collections);
+    match payer_accounts {
+        Some((payer, system_program)) => create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: metadata_info.clone(),
+                },
+                &[signer_seeds],
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            program_id,
+        )?,
+        None => {
+            require!(
+                Rent::get()?.is_exempt(metadata_info.lamports(), space),
+                ErrorCode::ConstraintRentExempt
+            );
+            invoke_signed(
+                &system_instruction::allocate(metadata_info.key, space as u64),
+                &[metadata_info.clone()],
+                &[signer_seeds],
+            )?;
+            invoke_signed(
+                &system_instruction::assign(metadata_info.key, program_id),
+                &[metadata_info.clone()],
+                &[signer_seeds],
+            )?;
+        }
+    }
+
+    metadata.try_serialize(&mut &mut metadata_info.try_borrow_mut_data()?[..])
+}
@@ -3,7 +3,11 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+use crate::constants::*;
 use crate::errors::*;
+use crate::signature::*;
 use crate::state::*;
 
 pub fn check_collection_metadata_equality(
@@ -14,54 +18,310 @@ pub fn check_collection_metadata_equality(
         && metadata_collection_metadata_key.id == collection_metadata_key.id
 }
 
+/// The signers of an instruction gated by an update authority
+pub trait AuthoritySigners {
+    /// The signing authority, delegate records are derived from it
+    fn signer(&self) -> &Pubkey;
+
+    /// Whether the signers act as the update authority
+    fn is(&self, update_authority: &Pubkey) -> bool;
+}
+
+impl AuthoritySigners for Pubkey {
+    fn signer(&self) -> &Pubkey {
+        self
+    }
+
+    fn is(&self, update_authority: &Pubkey) -> bool {
+        self.eq(update_authority)
+    }
+}
+
+/// A signer which also acts as the MultisigAuthority when enough of its signers sign with it
+pub struct MultisigSigners<'a> {
+    pub signer: Pubkey,
+    pub multisig_authority: Option<(Pubkey, &'a MultisigAuthority)>,
+    /// All the signers of the transaction, including the signer
+    pub transaction_signers: Vec<Pubkey>,
+}
+
+impl<'a> MultisigSigners<'a> {
+    /// Collects the co-signers of the MultisigAuthority from the signing remaining accounts
+    pub fn new(
+        signer: Pubkey,
+        multisig_authority: Option<&'a Account<MultisigAuthority>>,
+        remaining_accounts: &[AccountInfo],
+    ) -> Self {
+        let transaction_signers = [signer]
+            .into_iter()
+            .chain(
+                remaining_accounts
+                    .iter()
+                    .filter(|account| account.is_signer)
+                    .map(|account| account.key()),
+            )
+            .collect();
+
+        Self {
+            signer,
+            multisig_authority: multisig_authority
+                .map(|multisig_authority| (multisig_authority.key(), &**multisig_authority)),
+            transaction_signers,
+        }
+    }
+}
+
+impl<'a> MultisigSigners<'a> {
+    /// Uses the signers of an off-chain message, the first one is the signer
+    pub fn from_message_signers(
+        message_signers: Vec<Pubkey>,
+        multisig_authority: Option<&'a Account<MultisigAuthority>>,
+    ) -> Result<Self> {
+        let signer = *message_signers
+            .first()
+            .ok_or(MythicMetadataError::InvalidSignatureInstruction)?;
+
+        Ok(Self {
+            signer,
+            multisig_authority: multisig_authority
+                .map(|multisig_authority| (multisig_authority.key(), &**multisig_authority)),
+            transaction_signers: message_signers,
+        })
+    }
+}
+
+impl AuthoritySigners for MultisigSigners<'_> {
+    fn signer(&self) -> &Pubkey {
+        &self.signer
+    }
+
+    fn is(&self, update_authority: &Pubkey) -> bool {
+        self.signer.eq(update_authority)
+            || self
+                .multisig_authority
+                .is_some_and(|(address, multisig_authority)| {
+                    address.eq(update_authority)
+                        && multisig_authority.is_approved_by(&self.transaction_signers)
+                })
+    }
+}
+
 pub fn verify_metadata_update_authority(
     metadata: &Metadata,
-    update_authority: &Pubkey,
+    update_authority: &impl AuthoritySigners,
 ) -> Result<bool> {
-    if metadata.update_authority.is_none() {
-        return err!(MythicMetadataError::ImmutableMetadata);
+    match metadata.effective_update_authority() {
+        Some(expected_update_authority) => Ok(update_authority.is(&expected_update_authority)),
+        None => err!(MythicMetadataError::ImmutableMetadata),
     }
+}
 
-    if let Some(expected_update_authority) = metadata.update_authority {
-        if expected_update_authority.ne(&update_authority) {
-            return Ok(false);
+/// Returns true if the update_authority is the update authority of the collection identified by
+/// collection_path or of one of its ancestor collections
+pub fn is_collection_update_authority(
+    metadata: &Metadata,
+    collection_path: &[u64],
+    update_authority: &impl AuthoritySigners,
+) -> Result<bool> {
+    // Authorities are inherited from the parent collections
+    for depth in (1..=collection_path.len()).rev() {
+        let collection_index = metadata.collection_index(&collection_path[..depth])?;
+        if metadata.collections[collection_index]
+            .update_authority
+            .is_some_and(|expected_update_authority| {
+                update_authority.is(&expected_update_authority)
+            })
+        {
+            return Ok(true);
         }
     }
-    Ok(true)
+
+    Ok(false)
+}
+
+/// Verifies the items of the collection aren't timelocked
+/// Changes of the items of timelocked collections are queued with queue_metadata_change
+pub fn verify_not_timelocked(metadata: &Metadata, collection_path: &[u64]) -> Result<()> {
+    require!(
+        metadata.timelock_slots(collection_path)? == 0,
+        MythicMetadataError::MetadataCollectionTimelocked
+    );
+
+    Ok(())
+}
+
+/// Validates the item value against the content_type of its MetadataKey
+/// The values of multi-valued items are validated one by one
+/// Only the content types the program knows about are validated
+pub fn validate_item_value(item_metadata_key: &MetadataKey, value: &[u8]) -> Result<()> {
+    if item_metadata_key.multi_valued {
+        for value in decode_list(value)? {
+            validate_value(&item_metadata_key.content_type, &value)?;
+        }
+
+        Ok(())
+    } else {
+        validate_value(&item_metadata_key.content_type, value)
+    }
+}
+
+/// Validates a single value against the content_type
+/// EVM address links are verified against the subject by link_evm_address, which bypasses this check
+pub fn validate_value(content_type: &str, value: &[u8]) -> Result<()> {
+    if content_type == EXTERNAL_REFERENCE_CONTENT_TYPE {
+        ExternalReference::from_value(value)?;
+    }
+
+    if content_type == EVM_ADDRESS_CONTENT_TYPE {
+        return err!(MythicMetadataError::EvmAddressNotLinked);
+    }
+
+    Ok(())
+}
+
+/// Verifies the authority is the issuing authority of the Metadata
+/// or a delegate with the permission for the collection identified by collection_path
+pub fn verify_issuing_authority(
+    metadata: &Metadata,
+    authority: &Pubkey,
+    delegate_record: Option<&MetadataDelegate>,
+    collection_path: &[u64],
+    permission: u8,
+) -> Result<()> {
+    if metadata.issuing_authority.eq(authority) {
+        return Ok(());
+    }
+
+    require!(
+        verify_delegate(
+            metadata,
+            authority,
+            delegate_record,
+            collection_path,
+            permission
+        ),
+        MythicMetadataError::Unauthorized
+    );
+
+    Ok(())
+}
+
+/// Verifies the authority is the issuing authority or the Metadata update authority
+/// Permanent changes, finalizing the Metadata and freezing collections, can't be delegated
+pub fn verify_permanent_change_authority(
+    metadata: &Metadata,
+    authority: &impl AuthoritySigners,
+) -> Result<()> {
+    require!(
+        authority.is(&metadata.issuing_authority)
+            || verify_metadata_update_authority(metadata, authority)?,
+        MythicMetadataError::Unauthorized
+    );
+
+    Ok(())
+}
+
+/// Verifies the authority can update the items of the collection identified by collection_path
+/// The authority must be the update authority of the collection or of one of its ancestors,
+/// the Metadata update authority or a delegate with the update permission
+pub fn verify_item_update_authority(
+    metadata: &Metadata,
+    collection_path: &[u64],
+    update_authority: &impl AuthoritySigners,
+    delegate_record: Option<&MetadataDelegate>,
+) -> Result<()> {
+    if !metadata.is_root(collection_path)
+        && is_collection_update_authority(metadata, collection_path, update_authority)?
+    {
+        return Ok(());
+    }
+
+    if verify_metadata_update_authority(metadata, update_authority)? {
+        return Ok(());
+    }
+
+    require!(
+        verify_delegate(
+            metadata,
+            update_authority.signer(),
+            delegate_record,
+            collection_path,
+            MetadataDelegate::UPDATE
+        ),
+        MythicMetadataError::Unauthorized
+    );
+
+    Ok(())
 }
 
-pub fn verify_collection_update_authority(
+fn verify_delegate(
     metadata: &Metadata,
-    collection_metadata_key_id: u64,
-    update_authority: &Pubkey,
-) -> Result<(usize, MetadataCollection)> {
-    match metadata
-        .collections
-        .binary_search_by_key(&collection_metadata_key_id, |collection| {
-            collection.metadata_key_id
-        }) {
-        Ok(collection_index) => {
-            let collection = metadata.collections.get(collection_index).unwrap();
-
-            if collection.update_authority.is_none() {
-                require!(
-                    verify_metadata_update_authority(metadata, update_authority)?,
-                    MythicMetadataError::Unauthorized
-                );
-                return Ok((collection_index, collection.clone()));
-            } else {
-                let collection_update_authority = collection.update_authority.unwrap();
-                if collection_update_authority.ne(&update_authority) {
-                    require!(
-                        verify_metadata_update_authority(metadata, update_authority)?,
-                        MythicMetadataError::Unauthorized
-                    );
-                }
-                return Ok((collection_index, collection.clone()));
+    authority: &Pubkey,
+    delegate_record: Option<&MetadataDelegate>,
+    collection_path: &[u64],
+    permission: u8,
+) -> bool {
+    delegate_record.map_or(false, |delegate_record| {
+        delegate_record.delegate.eq(authority)
+            && delegate_record.is_granted(metadata, collection_path, permission)
+    })
+}
+
+/// Verifies the subject_authority controls the subject
+///
+/// The subject_authority is either the subject itself or, with the subject_account, the mint authority
+/// of a subject mint (SPL Token or Token-2022) or the upgrade authority of a subject program
+/// in which case the subject_account is the program's ProgramData account
+pub fn verify_subject_authority(
+    subject: &Pubkey,
+    subject_authority: &Pubkey,
+    subject_account: Option<&AccountInfo>,
+) -> Result<()> {
+    if subject.eq(subject_authority) {
+        return Ok(());
+    }
+
+    let subject_account = subject_account.ok_or(MythicMetadataError::InvalidSubjectAccount)?;
+    let data = subject_account.try_borrow_data()?;
+
+    let authority = if subject_account.owner.eq(&TOKEN_PROGRAM_ID)
+        || subject_account.owner.eq(&TOKEN_2022_PROGRAM_ID)
+    {
+        require_keys_eq!(
+            subject_account.key(),
+            *subject,
+            MythicMetadataError::InvalidSubjectAccount
+        );
+        // Mint.mint_authority: COption<Pubkey>
+        match data.get(0..36) {
+            Some([1, 0, 0, 0, authority @ ..]) => Pubkey::try_from(authority).ok(),
+            _ => None,
+        }
+    } else if subject_account.owner.eq(&bpf_loader_upgradeable::ID) {
+        let (program_data, _) =
+            Pubkey::find_program_address(&[subject.as_ref()], &bpf_loader_upgradeable::ID);
+        require_keys_eq!(
+            subject_account.key(),
+            program_data,
+            MythicMetadataError::InvalidSubjectAccount
+        );
+        // UpgradeableLoaderState::ProgramData { slot: u64, upgrade_authority_address: Option<Pubkey> }
+        match data.get(0..45) {
+            Some([3, 0, 0, 0, _, _, _, _, _, _, _, _, 1, authority @ ..]) => {
+                Pubkey::try_from(authority).ok()
             }
+            _ => None,
         }
-        Err(_) => return err!(MythicMetadataError::MetadataCollectionNonExistent),
+    } else {
+        return err!(MythicMetadataError::InvalidSubjectAccount);
     };
+
+    require!(
+        authority.eq(&Some(*subject_authority)),
+        MythicMetadataError::Unauthorized
+    );
+
+    Ok(())
 }
 
 pub fn realloc_account<'a>(
@@ -101,3 +361,199 @@ pub fn realloc_account<'a>(
     AccountInfo::realloc(&account, new_account_size, false)?;
     Ok(())
 }
+
+/// Shrinks the Metadata account to the new size and refunds the rent of the removed data to the
+/// rent payers which funded it, see [transfer_rent_shares]
+pub fn refund_rent(
+    metadata_info: &AccountInfo,
+    new_account_size: usize,
+    rent_shares: impl IntoIterator<Item = RentShare>,
+    rent_payer_infos: &[AccountInfo],
+) -> Result<()> {
+    if metadata_info.data_len() > new_account_size {
+        metadata_info.realloc(new_account_size, false)?;
+    }
+
+    let refundable_lamports = metadata_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(metadata_info.data_len()));
+
+    transfer_rent_shares(
+        metadata_info,
+        refundable_lamports,
+        rent_shares,
+        rent_payer_infos,
+    )
+}
+
+/// Transfers the rent of the shares from the Metadata account to their rent payers in rent_payer_infos
+/// At most refundable_lamports are transferred, the shares are refunded in order
+/// The rent of payers which aren't passed stays in the account, the issuing authority receives it on close
+pub fn transfer_rent_shares(
+    metadata_info: &AccountInfo,
+    refundable_lamports: u64,
+    rent_shares: impl IntoIterator<Item = RentShare>,
+    rent_payer_infos: &[AccountInfo],
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let mut refundable_lamports = refundable_lamports;
+
+    for rent_share in rent_shares {
+        let lamports = rent
+            .minimum_balance(rent_share.size)
+            .saturating_sub(rent.minimum_balance(0))
+            .min(refundable_lamports);
+        if lamports == 0 {
+            continue;
+        }
+
+        let Some(rent_payer_info) = rent_payer_infos
+            .iter()
+            .find(|rent_payer_info| rent_payer_info.key.eq(&rent_share.rent_payer))
+        else {
+            continue;
+        };
+
+        **metadata_info.try_borrow_mut_lamports()? -= lamports;
+        **rent_payer_info.try_borrow_mut_lamports()? += lamports;
+        refundable_lamports -= lamports;
+    }
+
+    Ok(())
+}
+
+/// Lists the Metadata in the next page of the registry of its subject, growing the page if needed
+/// The registry and the page may have just been created by init_if_needed, so their seeds are set as well
+/// Registering a registered Metadata is a no-op
+pub fn register_subject_metadata<'info>(
+    subject_registry: &mut Account<'info, SubjectRegistry>,
+    subject_registry_bump: u8,
+    subject_registry_page: &mut Account<'info, SubjectRegistryPage>,
+    subject_registry_page_bump: u8,
+    metadata: &mut Metadata,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    if metadata.registry_page_index.is_some() {
+        return Ok(());
+    }
+
+    let page_index = subject_registry.next_page_index();
+    subject_registry.subject = metadata.subject;
+    subject_registry.registered_count += 1;
+    subject_registry.bump = subject_registry_bump;
+
+    subject_registry_page.subject = metadata.subject;
+    subject_registry_page.page_index = page_index;
+    subject_registry_page.bump = subject_registry_page_bump;
+    subject_registry_page.entries.push(SubjectRegistryEntry {
+        metadata_key_id: metadata.metadata_key_id,
+        issuing_authority: metadata.issuing_authority,
+    });
+    metadata.registry_page_index = Some(page_index);
+
+    realloc_account(
+        subject_registry_page.to_account_info(),
+        SubjectRegistryPage::size(subject_registry_page.entries.len()),
+        payer,
+        system_program,
+    )
+}
+
+/// Lists the Metadata like register_subject_metadata when the optional registry accounts of a
+/// create instruction are passed, without them the Metadata can be listed with register_metadata
+pub fn register_optional_subject_metadata<'info>(
+    subject_registry: &mut Option<Account<'info, SubjectRegistry>>,
+    subject_registry_bump: Option<u8>,
+    subject_registry_page: &mut Option<Account<'info, SubjectRegistryPage>>,
+    subject_registry_page_bump: Option<u8>,
+    metadata: &mut Metadata,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    match (
+        subject_registry,
+        subject_registry_bump,
+        subject_registry_page,
+        subject_registry_page_bump,
+    ) {
+        (
+            Some(subject_registry),
+            Some(subject_registry_bump),
+            Some(subject_registry_page),
+            Some(subject_registry_page_bump),
+        ) => register_subject_metadata(
+            subject_registry,
+            subject_registry_bump,
+            subject_registry_page,
+            subject_registry_page_bump,
+            metadata,
+            payer,
+            system_program,
+        ),
+        (None, _, None, _) => Ok(()),
+        _ => err!(ErrorCode::ConstraintAccountIsNone),
+    }
+}
+
+/// Verifies the ed25519 signature is the expected signer's signature of the message
+/// and that the message hasn't expired
+pub fn verify_signed_message(
+    signature: &Ed25519Signature,
+    signer: &Pubkey,
+    message: &[u8],
+    expiry_slot: u64,
+    slot: u64,
+) -> Result<()> {
+    require_keys_eq!(signature.signer, *signer, MythicMetadataError::Unauthorized);
+    require!(
+        signature.message == message,
+        MythicMetadataError::SignedMessageMismatch
+    );
+    require!(
+        slot <= expiry_slot,
+        MythicMetadataError::SignedMessageExpired
+    );
+
+    Ok(())
+}
+
+/// Verifies the ed25519 signatures are all signatures of the message and that it hasn't expired
+/// Returns the signers
+pub fn verify_signed_messages(
+    signatures: &[Ed25519Signature],
+    message: &[u8],
+    expiry_slot: u64,
+    slot: u64,
+) -> Result<Vec<Pubkey>> {
+    signatures
+        .iter()
+        .map(|signature| {
+            verify_signed_message(signature, &signature.signer, message, expiry_slot, slot)?;
+            Ok(signature.signer)
+        })
+        .collect()
+}
+
+/// Records the nonce of a signed message, which must exceed the signer's last nonce
+/// The SignerNonce may have just been created by init_if_needed, so its seeds are set as well
+pub fn consume_signer_nonce(
+    signer_nonce: &mut SignerNonce,
+    signer_nonce_bump: u8,
+    metadata: Pubkey,
+    signer: Pubkey,
+    nonce: u64,
+) -> Result<()> {
+    // A new SignerNonce starts at 0 so the first nonce must be at least 1
+    require!(
+        nonce > signer_nonce.nonce,
+        MythicMetadataError::NonceAlreadyUsed
+    );
+
+    signer_nonce.metadata = metadata;
+    signer_nonce.signer = signer;
+    signer_nonce.nonce = nonce;
+    signer_nonce.bump = signer_nonce_bump;
+
+    Ok(())
+}
@@ -0,0 +1,225 @@
+//! Parsing of ed25519 instructions and the replay protection of signed messages
+
+use anchor_lang::prelude::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::signature::*;
+use mythic_metadata::state::*;
+use mythic_metadata::utils::*;
+
+/// Lays out a single signature like the Solana SDK's new_ed25519_instruction
+fn ed25519_data(signer: &Pubkey, message: &[u8], instruction_index: u16) -> Vec<u8> {
+    let offsets = Ed25519SignatureOffsets {
+        signature_offset: 48,
+        signature_instruction_index: instruction_index,
+        public_key_offset: 16,
+        public_key_instruction_index: instruction_index,
+        message_data_offset: 112,
+        message_data_size: message.len() as u16,
+        message_instruction_index: instruction_index,
+    };
+    [
+        &[1, 0],
+        offsets.try_to_vec().unwrap().as_slice(),
+        signer.as_ref(),
+        &[7; 64],
+        message,
+    ]
+    .concat()
+}
+
+fn attestation(nonce: u64) -> Attestation {
+    Attestation {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: 1,
+        collection_path: vec![2],
+        item_metadata_key_id: 3,
+        value: b"verified".to_vec(),
+        nonce,
+        expiry_slot: 100,
+    }
+}
+
+#[test]
+fn ed25519_instructions_are_parsed() {
+    let signer = Pubkey::new_unique();
+    let message = attestation(1).message();
+
+    assert_eq!(
+        Ed25519Signature::parse(&ed25519_data(&signer, &message, u16::MAX)).unwrap(),
+        Ed25519Signature {
+            signer,
+            message: message.clone()
+        }
+    );
+
+    // Signatures referring to the data of other instructions could verify another message
+    assert_eq!(
+        Ed25519Signature::parse(&ed25519_data(&signer, &message, 0)),
+        Err(MythicMetadataError::InvalidSignatureInstruction.into())
+    );
+
+    let mut two_signatures = ed25519_data(&signer, &message, u16::MAX);
+    two_signatures[0] = 2;
+    let truncated = ed25519_data(&signer, &message, u16::MAX)[..100].to_vec();
+    for data in [two_signatures, truncated, vec![]] {
+        assert_eq!(
+            Ed25519Signature::parse(&data),
+            Err(MythicMetadataError::InvalidSignatureInstruction.into())
+        );
+    }
+}
+
+#[test]
+fn ed25519_instructions_with_several_signatures_are_parsed() {
+    let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let message = attestation(1).message();
+
+    // Both signatures refer to the message following them
+    let offsets = |public_key_offset: u16| Ed25519SignatureOffsets {
+        signature_offset: public_key_offset + 32,
+        signature_instruction_index: u16::MAX,
+        public_key_offset,
+        public_key_instruction_index: u16::MAX,
+        message_data_offset: 222,
+        message_data_size: message.len() as u16,
+        message_instruction_index: u16::MAX,
+    };
+    let data = [
+        &[2, 0],
+        offsets(30).try_to_vec().unwrap().as_slice(),
+        offsets(126).try_to_vec().unwrap().as_slice(),
+        signers[0].as_ref(),
+        &[7; 64],
+        signers[1].as_ref(),
+        &[8; 64],
+        &message,
+    ]
+    .concat();
+
+    let signatures = Ed25519Signature::parse_all(&data).unwrap();
+    assert_eq!(
+        signatures,
+        signers
+            .iter()
+            .map(|signer| Ed25519Signature {
+                signer: *signer,
+                message: message.clone(),
+            })
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        verify_signed_messages(&signatures, &message, 100, 1),
+        Ok(signers.to_vec())
+    );
+
+    // A single signature is expected where only one signer signs
+    assert_eq!(
+        Ed25519Signature::parse(&data),
+        Err(MythicMetadataError::InvalidSignatureInstruction.into())
+    );
+
+    // Every signature must be of the message
+    let mut other_signatures = signatures;
+    other_signatures[1].message = attestation(2).message();
+    assert_eq!(
+        verify_signed_messages(&other_signatures, &message, 100, 1),
+        Err(MythicMetadataError::SignedMessageMismatch.into())
+    );
+}
+
+#[test]
+fn signed_messages_must_match() {
+    let signer = Pubkey::new_unique();
+    let attestation = attestation(1);
+    let signature = Ed25519Signature {
+        signer,
+        message: attestation.message(),
+    };
+    assert!(signature.message.starts_with(ATTESTATION_DOMAIN));
+
+    assert!(verify_signed_message(&signature, &signer, &attestation.message(), 100, 100).is_ok());
+    assert_eq!(
+        verify_signed_message(
+            &signature,
+            &Pubkey::new_unique(),
+            &attestation.message(),
+            100,
+            50
+        ),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+
+    let other_value = Attestation {
+        value: b"forged".to_vec(),
+        ..attestation.clone()
+    };
+    assert_eq!(
+        verify_signed_message(&signature, &signer, &other_value.message(), 100, 50),
+        Err(MythicMetadataError::SignedMessageMismatch.into())
+    );
+    assert_eq!(
+        verify_signed_message(&signature, &signer, &attestation.message(), 100, 101),
+        Err(MythicMetadataError::SignedMessageExpired.into())
+    );
+}
+
+#[test]
+fn nonces_must_increase() {
+    let metadata = Pubkey::new_unique();
+    let signer = Pubkey::new_unique();
+    let mut signer_nonce = SignerNonce {
+        metadata: Pubkey::default(),
+        signer: Pubkey::default(),
+        nonce: 0,
+        bump: 0,
+    };
+
+    assert_eq!(
+        consume_signer_nonce(&mut signer_nonce, 254, metadata, signer, 0),
+        Err(MythicMetadataError::NonceAlreadyUsed.into())
+    );
+    consume_signer_nonce(&mut signer_nonce, 254, metadata, signer, 5).unwrap();
+    assert_eq!(signer_nonce.metadata, metadata);
+    assert_eq!(signer_nonce.signer, signer);
+    assert_eq!(signer_nonce.nonce, 5);
+    assert_eq!(signer_nonce.bump, 254);
+
+    for replayed in [5, 4] {
+        assert_eq!(
+            consume_signer_nonce(&mut signer_nonce, 254, metadata, signer, replayed),
+            Err(MythicMetadataError::NonceAlreadyUsed.into())
+        );
+    }
+    assert!(consume_signer_nonce(&mut signer_nonce, 254, metadata, signer, 9).is_ok());
+}
+
+#[test]
+fn signed_updates_are_domain_separated() {
+    let update = SignedItemUpdate {
+        metadata: Pubkey::new_unique(),
+        update_authority: Pubkey::new_unique(),
+        collection_path: vec![2],
+        item_metadata_key_id: 3,
+        new_value: b"verified".to_vec(),
+        nonce: 1,
+        expiry_slot: 100,
+    };
+    let signer = Pubkey::new_unique();
+    let signature = Ed25519Signature {
+        signer,
+        message: update.message(),
+    };
+
+    assert!(update.message().starts_with(ITEM_UPDATE_DOMAIN));
+    assert!(verify_signed_message(&signature, &signer, &update.message(), 100, 1).is_ok());
+
+    // A signed update can't be posted as an attestation with the same bytes
+    let attestation_signature = Ed25519Signature {
+        signer,
+        message: [ATTESTATION_DOMAIN, &update.try_to_vec().unwrap()].concat(),
+    };
+    assert_eq!(
+        verify_signed_message(&attestation_signature, &signer, &update.message(), 100, 1),
+        Err(MythicMetadataError::SignedMessageMismatch.into())
+    );
+}
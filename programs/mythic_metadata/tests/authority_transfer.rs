@@ -0,0 +1,32 @@
+//! Two-step update authority transfers
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use mythic_metadata::state::*;
+
+#[test]
+fn authority_transfer_size_matches_serialized_length() {
+    for collection_path in [vec![1], vec![1, 2, 3]] {
+        let authority_transfer = AuthorityTransfer {
+            metadata: Pubkey::new_unique(),
+            collection_path: collection_path.clone(),
+            new_update_authority: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            bump: 255,
+        };
+        assert_eq!(
+            AuthorityTransfer::size(collection_path.len()),
+            AuthorityTransfer::DISCRIMINATOR.len() + authority_transfer.try_to_vec().unwrap().len()
+        );
+    }
+}
+
+#[test]
+fn authority_transfer_seeds_match_item_seeds_of_the_parent() {
+    let parent_path = [1, 2];
+    assert_eq!(
+        path_seed(&collection_path(&parent_path, 3)),
+        item_seed(&parent_path, 3)
+    );
+    assert_ne!(path_seed(&[1, 2, 3]), path_seed(&[1, 23]));
+}
@@ -0,0 +1,144 @@
+//! Copying the items and collections of a Metadata into a new Metadata
+
+use anchor_lang::prelude::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+
+const ROOT: u64 = 1;
+const SOCIALS: u64 = 2;
+const TWITTER: u64 = 3;
+const HANDLE: u64 = 4;
+const TREASURY: u64 = 5;
+const NAME: u64 = 6;
+const SUB_DAO_ROOT: u64 = 7;
+
+fn metadata(metadata_key_id: u64) -> Metadata {
+    Metadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: None,
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    }
+}
+
+fn item(metadata_key_id: u64, value: &[u8]) -> MetadataItem {
+    MetadataItem {
+        metadata_key_id,
+        update_slot: 1,
+        value: value.to_vec(),
+        rent_payer: Pubkey::default(),
+        growth_rent_shares: vec![],
+    }
+}
+
+/// A name root item, treasury and socials/twitter, each holding a handle item
+fn source() -> Metadata {
+    let mut source = metadata(ROOT);
+    source.append_item(&[ROOT], item(NAME, b"Mythic")).unwrap();
+    for (parent_path, metadata_key_id) in
+        [(&[][..], TREASURY), (&[], SOCIALS), (&[SOCIALS], TWITTER)]
+    {
+        source
+            .append_collection(MetadataCollection {
+                metadata_key_id,
+                parent_path: parent_path.to_vec(),
+                update_slot: 1,
+                update_authority: Some(Pubkey::new_unique()),
+                timelock_slots: 0,
+                frozen: false,
+                items: vec![],
+                rent_payer: Pubkey::default(),
+            })
+            .unwrap();
+    }
+    source
+        .append_item(&[TREASURY], item(HANDLE, b"treasury"))
+        .unwrap();
+    source
+        .append_item(&[SOCIALS, TWITTER], item(HANDLE, b"@mythic"))
+        .unwrap();
+    source
+}
+
+fn paths(metadata: &Metadata) -> Vec<Vec<u64>> {
+    metadata
+        .collections
+        .iter()
+        .map(|collection| collection.path().copied().collect())
+        .collect()
+}
+
+#[test]
+fn everything_is_copied_without_a_filter() {
+    let source = source();
+    let mut metadata = metadata(SUB_DAO_ROOT);
+
+    let rent_payer = Pubkey::new_unique();
+    metadata.copy_from(&source, &[], 5, rent_payer).unwrap();
+    metadata.validate().unwrap();
+
+    // The root items are copied into the new Metadata's root items
+    assert_eq!(metadata.items.len(), 1);
+    assert_eq!(metadata.items[0].value, b"Mythic");
+    assert_eq!(metadata.items[0].update_slot, 5);
+    assert_eq!(paths(&metadata), paths(&source));
+    // The copies are funded by the rent payer of the clone
+    assert!(metadata
+        .rent_shares()
+        .all(|rent_share| rent_share.rent_payer == rent_payer));
+    for (collection, source_collection) in metadata.collections.iter().zip(&source.collections) {
+        assert_eq!(collection.update_slot, 5);
+        assert_eq!(collection.update_authority, None);
+        assert_eq!(collection.items.len(), source_collection.items.len());
+    }
+    assert_eq!(metadata.update_slot, 5);
+}
+
+#[test]
+fn the_filter_selects_collections_and_root_items() {
+    let source = source();
+
+    // A nested collection brings its ancestors without their items
+    let mut twitter_only = metadata(SUB_DAO_ROOT);
+    twitter_only
+        .copy_from(&source, &[vec![SOCIALS, TWITTER]], 5, Pubkey::default())
+        .unwrap();
+    assert!(twitter_only.items.is_empty());
+    assert_eq!(
+        paths(&twitter_only),
+        vec![vec![SOCIALS], vec![SOCIALS, TWITTER]]
+    );
+    assert!(twitter_only.collections[0].items.is_empty());
+    assert_eq!(twitter_only.collections[1].items[0].value, b"@mythic");
+
+    // A collection brings its nested collections and the root path the root items
+    let mut socials = metadata(SUB_DAO_ROOT);
+    socials
+        .copy_from(&source, &[vec![ROOT], vec![SOCIALS]], 5, Pubkey::default())
+        .unwrap();
+    assert_eq!(socials.items.len(), 1);
+    assert_eq!(paths(&socials), vec![vec![SOCIALS], vec![SOCIALS, TWITTER]]);
+
+    assert_eq!(
+        metadata(SUB_DAO_ROOT).copy_from(&source, &[vec![NAME]], 5, Pubkey::default()),
+        Err(MythicMetadataError::MetadataCollectionNonExistent.into())
+    );
+}
+
+#[test]
+fn collections_clashing_with_the_root_are_rejected() {
+    let source = source();
+
+    assert_eq!(
+        metadata(TREASURY).copy_from(&source, &[vec![TREASURY]], 5, Pubkey::default()),
+        Err(MythicMetadataError::InvalidMetadataKey.into())
+    );
+}
@@ -0,0 +1,129 @@
+//! Endorsements of item versions and their invalidation by updates
+
+use anchor_lang::prelude::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+
+const ROOT: u64 = 1;
+const SOCIALS: u64 = 2;
+const HANDLE: u64 = 3;
+
+fn metadata() -> Metadata {
+    let mut metadata = Metadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: None,
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    };
+    metadata
+        .append_collection(MetadataCollection {
+            metadata_key_id: SOCIALS,
+            parent_path: vec![],
+            update_slot: 0,
+            update_authority: None,
+            timelock_slots: 0,
+            frozen: false,
+            items: vec![],
+            rent_payer: Pubkey::default(),
+        })
+        .unwrap();
+    metadata
+        .append_item(
+            &[SOCIALS],
+            MetadataItem {
+                metadata_key_id: HANDLE,
+                update_slot: 1,
+                value: b"@mythic".to_vec(),
+                rent_payer: Pubkey::default(),
+                growth_rent_shares: vec![],
+            },
+        )
+        .unwrap();
+    metadata
+}
+
+fn endorse(metadata: &Metadata, collection_path: &[u64], item_metadata_key_id: u64) -> Endorsement {
+    let item = metadata
+        .item(collection_path, item_metadata_key_id)
+        .unwrap();
+    Endorsement {
+        metadata: Pubkey::new_unique(),
+        endorser: Pubkey::new_unique(),
+        collection_path: collection_path.to_vec(),
+        item_metadata_key_id,
+        value_hash: Endorsement::value_hash(&item.value),
+        item_update_slot: item.update_slot,
+        bump: 255,
+    }
+}
+
+#[test]
+fn updates_invalidate_endorsements() {
+    let mut metadata = metadata();
+    let endorsement = endorse(&metadata, &[SOCIALS], HANDLE);
+    assert!(endorsement.is_valid_for(metadata.item(&[SOCIALS], HANDLE).unwrap()));
+
+    metadata
+        .update_item(
+            &[SOCIALS],
+            MetadataItem {
+                metadata_key_id: HANDLE,
+                update_slot: 2,
+                value: b"@mythic".to_vec(),
+                rent_payer: Pubkey::default(),
+                growth_rent_shares: vec![],
+            },
+        )
+        .unwrap();
+    assert!(!endorsement.is_valid_for(metadata.item(&[SOCIALS], HANDLE).unwrap()));
+
+    // Updates in the endorsed slot are caught by the value hash
+    metadata
+        .update_item(
+            &[SOCIALS],
+            MetadataItem {
+                metadata_key_id: HANDLE,
+                update_slot: 1,
+                value: b"@other".to_vec(),
+                rent_payer: Pubkey::default(),
+                growth_rent_shares: vec![],
+            },
+        )
+        .unwrap();
+    assert!(!endorsement.is_valid_for(metadata.item(&[SOCIALS], HANDLE).unwrap()));
+}
+
+#[test]
+fn items_are_looked_up_by_path() {
+    let metadata = metadata();
+
+    assert_eq!(
+        metadata.item(&[SOCIALS], HANDLE).unwrap().value,
+        b"@mythic".to_vec()
+    );
+    assert_eq!(
+        metadata.item(&[ROOT], HANDLE).err(),
+        Some(MythicMetadataError::MetadataItemNonExistent.into())
+    );
+    assert_eq!(
+        metadata.item(&[HANDLE], HANDLE).err(),
+        Some(MythicMetadataError::MetadataCollectionNonExistent.into())
+    );
+}
+
+#[test]
+fn item_seeds_separate_paths() {
+    assert_ne!(item_seed(&[SOCIALS], HANDLE), item_seed(&[ROOT], HANDLE));
+    assert_ne!(
+        item_seed(&[SOCIALS, HANDLE], ROOT),
+        item_seed(&[SOCIALS], HANDLE)
+    );
+}
@@ -0,0 +1,135 @@
+//! Verification of the secp256k1 proofs linking EVM addresses to subjects
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use mythic_metadata::constants::EVM_ADDRESS_CONTENT_TYPE;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+use mythic_metadata::utils::*;
+
+/// Signs the subject's link message like personal_sign
+fn link(secret_key: &libsecp256k1::SecretKey, subject: &Pubkey) -> EvmAddressLink {
+    let public_key = libsecp256k1::PublicKey::from_secret_key(secret_key);
+    let address = keccak::hash(&public_key.serialize()[1..]).to_bytes()[12..]
+        .try_into()
+        .unwrap();
+    let hash = EvmAddressLink::eip191_hash(EvmAddressLink::message(&address, subject).as_bytes());
+    let (signature, recovery_id) =
+        libsecp256k1::sign(&libsecp256k1::Message::parse(&hash), secret_key);
+
+    EvmAddressLink {
+        address,
+        signature: signature.serialize(),
+        recovery_id: recovery_id.serialize(),
+    }
+}
+
+fn secret_key() -> libsecp256k1::SecretKey {
+    libsecp256k1::SecretKey::parse(&[7; 32]).unwrap()
+}
+
+#[test]
+fn links_verify_against_their_subject() {
+    let subject = Pubkey::new_unique();
+    let evm_address_link = link(&secret_key(), &subject);
+
+    assert_eq!(evm_address_link.verify(&subject), Ok(()));
+    assert_eq!(
+        EvmAddressLink::from_value(&evm_address_link.try_to_vec().unwrap()).unwrap(),
+        evm_address_link
+    );
+
+    // The signature can't be replayed for another subject
+    assert_eq!(
+        evm_address_link.verify(&Pubkey::new_unique()),
+        Err(MythicMetadataError::InvalidEvmAddressLink.into())
+    );
+}
+
+#[test]
+fn links_to_other_addresses_are_rejected() {
+    let subject = Pubkey::new_unique();
+    let mut evm_address_link = link(&secret_key(), &subject);
+    evm_address_link.address[0] ^= 1;
+
+    assert_eq!(
+        evm_address_link.verify(&subject),
+        Err(MythicMetadataError::InvalidEvmAddressLink.into())
+    );
+
+    let mut evm_address_link = link(&secret_key(), &subject);
+    evm_address_link.recovery_id ^= 1;
+
+    assert_eq!(
+        evm_address_link.verify(&subject),
+        Err(MythicMetadataError::InvalidEvmAddressLink.into())
+    );
+}
+
+#[test]
+fn evm_addresses_are_only_written_by_link_evm_address() {
+    let evm_address_link = link(&secret_key(), &Pubkey::new_unique());
+
+    assert_eq!(
+        validate_value(
+            EVM_ADDRESS_CONTENT_TYPE,
+            &evm_address_link.try_to_vec().unwrap()
+        ),
+        Err(MythicMetadataError::EvmAddressNotLinked.into())
+    );
+}
+
+#[test]
+fn links_are_not_cloned_to_other_metadata() {
+    let subject = Pubkey::new_unique();
+    let evm_address_link = link(&secret_key(), &subject).try_to_vec().unwrap();
+    let evm_address_list = vec![evm_address_link.clone()].try_to_vec().unwrap();
+    let item = |metadata_key_id: u64, value: &[u8]| MetadataItem {
+        metadata_key_id,
+        update_slot: 0,
+        value: value.to_vec(),
+        rent_payer: Pubkey::default(),
+        growth_rent_shares: vec![],
+    };
+    let metadata = |subject: Pubkey| Metadata {
+        subject,
+        metadata_key_id: 1,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: None,
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    };
+
+    let mut source = metadata(subject);
+    for (metadata_key_id, value) in [
+        (2, &evm_address_link[..]),
+        (3, &evm_address_list),
+        (4, b"name"),
+    ] {
+        source
+            .append_item(&[1], item(metadata_key_id, value))
+            .unwrap();
+    }
+    assert!(EvmAddressLink::is_proof_for(&evm_address_link, &subject));
+    assert!(EvmAddressLink::is_proof_for(&evm_address_list, &subject));
+
+    let mut clone = metadata(Pubkey::new_unique());
+    clone
+        .copy_from(&source, &[], 1, Pubkey::new_unique())
+        .unwrap();
+
+    assert_eq!(
+        clone
+            .items
+            .iter()
+            .map(|item| item.metadata_key_id)
+            .collect::<Vec<_>>(),
+        vec![4]
+    );
+}
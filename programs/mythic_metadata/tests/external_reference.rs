@@ -0,0 +1,95 @@
+//! External reference values: encoding, validation and content verification
+
+use anchor_lang::prelude::*;
+use mythic_metadata::constants::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+use mythic_metadata::utils::validate_item_value;
+
+const CONTENT: &[u8] = b"{\"name\":\"Mythic\"}";
+
+fn metadata_key(content_type: &str) -> MetadataKey {
+    MetadataKey {
+        id: 1,
+        namespace_authority: Pubkey::new_unique(),
+        name: "document".to_string(),
+        label: "Document".to_string(),
+        description: "An off-chain document".to_string(),
+        content_type: content_type.to_string(),
+        bump: 255,
+        multi_valued: false,
+    }
+}
+
+#[test]
+fn content_matches_the_digest() {
+    for hash_algorithm in [
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Keccak256,
+        HashAlgorithm::Blake3,
+    ] {
+        let external_reference = ExternalReference::new(
+            "https://example.com/doc.json".to_string(),
+            hash_algorithm,
+            CONTENT,
+        );
+
+        assert!(external_reference.matches(CONTENT));
+        assert!(!external_reference.matches(b"{\"name\":\"Mythical\"}"));
+    }
+
+    // Every algorithm produces a different digest
+    let digests = [
+        HashAlgorithm::Sha256.digest(CONTENT),
+        HashAlgorithm::Keccak256.digest(CONTENT),
+        HashAlgorithm::Blake3.digest(CONTENT),
+    ];
+    assert_ne!(digests[0], digests[1]);
+    assert_ne!(digests[1], digests[2]);
+
+    // A size mismatch fails even if the digest were forged
+    let mut external_reference =
+        ExternalReference::new("ipfs://bafy".to_string(), HashAlgorithm::Sha256, CONTENT);
+    external_reference.size = Some(1);
+    assert!(!external_reference.matches(CONTENT));
+    external_reference.size = None;
+    assert!(external_reference.matches(CONTENT));
+}
+
+#[test]
+fn item_values_are_validated_by_content_type() {
+    let external_reference_key = metadata_key(EXTERNAL_REFERENCE_CONTENT_TYPE);
+    let external_reference =
+        ExternalReference::new("ar://tx".to_string(), HashAlgorithm::Blake3, CONTENT);
+
+    let value = external_reference.try_to_vec().unwrap();
+    assert!(validate_item_value(&external_reference_key, &value).is_ok());
+    assert_eq!(
+        ExternalReference::from_value(&value).unwrap(),
+        external_reference
+    );
+
+    for invalid_value in [
+        b"not borsh".to_vec(),
+        ExternalReference {
+            uri: String::new(),
+            ..external_reference.clone()
+        }
+        .try_to_vec()
+        .unwrap(),
+        ExternalReference {
+            uri: "a".repeat(MAX_URI_LEN + 1),
+            ..external_reference.clone()
+        }
+        .try_to_vec()
+        .unwrap(),
+    ] {
+        assert_eq!(
+            validate_item_value(&external_reference_key, &invalid_value),
+            Err(MythicMetadataError::InvalidExternalReference.into())
+        );
+    }
+
+    // Other content types are opaque to the program
+    assert!(validate_item_value(&metadata_key("string"), b"not borsh").is_ok());
+}
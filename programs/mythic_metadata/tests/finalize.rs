@@ -0,0 +1,183 @@
+//! Finalized Metadata is immutable, revoking the update authority isn't enough
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+use mythic_metadata::utils::*;
+
+const ROOT: u64 = 1;
+const SOCIALS: u64 = 2;
+const ADDRESS: u64 = 3;
+const OTHER: u64 = 4;
+
+fn item(metadata_key_id: u64, value: &[u8]) -> MetadataItem {
+    MetadataItem {
+        metadata_key_id,
+        update_slot: 1,
+        value: value.to_vec(),
+        rent_payer: Pubkey::default(),
+        growth_rent_shares: vec![],
+    }
+}
+
+fn metadata() -> Metadata {
+    let mut metadata = Metadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: Some(Pubkey::new_unique()),
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    };
+    metadata
+        .append_collection(MetadataCollection {
+            metadata_key_id: SOCIALS,
+            parent_path: vec![],
+            update_slot: 0,
+            update_authority: Some(Pubkey::new_unique()),
+            timelock_slots: 0,
+            frozen: false,
+            items: vec![],
+            rent_payer: Pubkey::default(),
+        })
+        .unwrap();
+    for collection_path in [&[SOCIALS][..], &[ROOT]] {
+        metadata
+            .append_item(collection_path, item(ADDRESS, b"address"))
+            .unwrap();
+    }
+    metadata
+}
+
+fn immutable() -> Result<()> {
+    err!(MythicMetadataError::ImmutableMetadata)
+}
+
+#[test]
+fn revoked_update_authority_falls_back_to_the_issuing_authority() {
+    let mut metadata = metadata();
+    let update_authority = metadata.update_authority.unwrap();
+    metadata.set_update_authority(&[ROOT], None).unwrap();
+
+    assert_eq!(
+        metadata.effective_update_authority(),
+        Some(metadata.issuing_authority)
+    );
+    assert!(verify_metadata_update_authority(&metadata, &metadata.issuing_authority).unwrap());
+    assert!(!verify_metadata_update_authority(&metadata, &update_authority).unwrap());
+    verify_item_update_authority(&metadata, &[ROOT], &metadata.issuing_authority, None).unwrap();
+    metadata
+        .update_item(&[ROOT], item(ADDRESS, b"other"))
+        .unwrap();
+}
+
+#[test]
+fn finalized_metadata_rejects_changes() {
+    let mut metadata = metadata();
+    let collection_update_authority = metadata.collections[0].update_authority.unwrap();
+    metadata.finalize(10).unwrap();
+
+    assert_eq!(metadata.finalized_slot, Some(10));
+    assert_eq!(metadata.finalize(11), immutable());
+    assert_eq!(metadata.verify_closable(), immutable());
+    assert_eq!(metadata.effective_update_authority(), None);
+    assert_eq!(
+        verify_metadata_update_authority(&metadata, &metadata.issuing_authority).err(),
+        immutable().err()
+    );
+
+    // Collection update authorities can't change their collections either
+    verify_item_update_authority(&metadata, &[SOCIALS], &collection_update_authority, None)
+        .unwrap();
+    for collection_path in [&[SOCIALS][..], &[ROOT]] {
+        assert_eq!(
+            metadata.append_item(collection_path, item(OTHER, b"other")),
+            immutable()
+        );
+        assert_eq!(
+            metadata
+                .update_item(collection_path, item(ADDRESS, b"other"))
+                .err(),
+            immutable().err()
+        );
+        assert_eq!(
+            metadata.remove_item(collection_path, ADDRESS).err(),
+            immutable().err()
+        );
+        assert_eq!(
+            metadata.set_update_authority(collection_path, None),
+            immutable()
+        );
+    }
+    assert_eq!(
+        metadata.move_item(&[ROOT], &[SOCIALS], ADDRESS, 2),
+        immutable()
+    );
+    assert_eq!(metadata.set_timelock(&[SOCIALS], 100), immutable());
+    assert_eq!(metadata.freeze_collection(&[SOCIALS]), immutable());
+    assert_eq!(
+        metadata.remove_collection(&[SOCIALS]).err(),
+        immutable().err()
+    );
+    assert_eq!(
+        metadata.append_collection(MetadataCollection {
+            metadata_key_id: OTHER,
+            parent_path: vec![],
+            update_slot: 0,
+            update_authority: None,
+            timelock_slots: 0,
+            frozen: false,
+            items: vec![],
+            rent_payer: Pubkey::default(),
+        }),
+        immutable()
+    );
+}
+
+#[test]
+fn metadata_with_open_records_cant_be_closed() {
+    let mut metadata = metadata();
+    metadata.verify_closable().unwrap();
+
+    metadata.open_record();
+    metadata.open_record();
+    metadata.close_record();
+    assert_eq!(
+        metadata.verify_closable(),
+        err!(MythicMetadataError::MetadataRecordsOpen)
+    );
+
+    metadata.close_record();
+    metadata.verify_closable().unwrap();
+}
+
+#[test]
+fn state_hash_changes_with_every_update() {
+    let mut metadata = metadata();
+    let state_hash = metadata.state_hash().unwrap();
+    assert_eq!(metadata.state_hash().unwrap(), state_hash);
+
+    metadata
+        .update_item(&[ROOT], item(ADDRESS, b"other"))
+        .unwrap();
+    assert_ne!(metadata.state_hash().unwrap(), state_hash);
+}
+
+#[test]
+fn metadata_size_covers_the_finalized_slot_and_the_registry_page() {
+    let mut metadata = metadata();
+    metadata.finalize(10).unwrap();
+    metadata.registry_page_index = Some(0);
+
+    assert_eq!(
+        Metadata::size(&metadata.items, &metadata.collections),
+        Metadata::DISCRIMINATOR.len() + metadata.try_to_vec().unwrap().len()
+    );
+}
@@ -0,0 +1,175 @@
+//! Frozen collections reject every change
+
+use anchor_lang::prelude::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+use mythic_metadata::utils::verify_permanent_change_authority;
+
+const ROOT: u64 = 1;
+const TREASURY: u64 = 2;
+const SOLANA: u64 = 3;
+const SOCIALS: u64 = 4;
+const ADDRESS: u64 = 5;
+const OTHER: u64 = 6;
+
+fn collection(parent_path: &[u64], metadata_key_id: u64) -> MetadataCollection {
+    MetadataCollection {
+        metadata_key_id,
+        parent_path: parent_path.to_vec(),
+        update_slot: 0,
+        update_authority: None,
+        timelock_slots: 0,
+        frozen: false,
+        items: vec![],
+        rent_payer: Pubkey::default(),
+    }
+}
+
+fn item(metadata_key_id: u64, value: &[u8]) -> MetadataItem {
+    MetadataItem {
+        metadata_key_id,
+        update_slot: 1,
+        value: value.to_vec(),
+        rent_payer: Pubkey::default(),
+        growth_rent_shares: vec![],
+    }
+}
+
+fn metadata() -> Metadata {
+    let mut metadata = Metadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: Some(Pubkey::new_unique()),
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    };
+    for (parent_path, metadata_key_id) in
+        [(&[][..], TREASURY), (&[TREASURY], SOLANA), (&[], SOCIALS)]
+    {
+        metadata
+            .append_collection(collection(parent_path, metadata_key_id))
+            .unwrap();
+    }
+    for collection_path in [&[TREASURY][..], &[TREASURY, SOLANA], &[SOCIALS], &[ROOT]] {
+        metadata
+            .append_item(collection_path, item(ADDRESS, b"address"))
+            .unwrap();
+    }
+    metadata
+}
+
+fn frozen() -> Result<()> {
+    err!(MythicMetadataError::MetadataCollectionFrozen)
+}
+
+#[test]
+fn frozen_collections_reject_changes() {
+    let mut metadata = metadata();
+    metadata.freeze_collection(&[TREASURY]).unwrap();
+    assert_eq!(metadata.freeze_collection(&[TREASURY]), frozen());
+
+    for collection_path in [&[TREASURY][..], &[TREASURY, SOLANA]] {
+        assert!(metadata.is_frozen(collection_path).unwrap());
+        assert_eq!(
+            metadata.append_item(collection_path, item(OTHER, b"other")),
+            frozen()
+        );
+        assert_eq!(
+            metadata
+                .update_item(collection_path, item(ADDRESS, b"other"))
+                .err(),
+            frozen().err()
+        );
+        assert_eq!(
+            metadata.remove_item(collection_path, ADDRESS).err(),
+            frozen().err()
+        );
+        assert_eq!(
+            metadata.push_list_value(
+                collection_path,
+                OTHER,
+                b"other".to_vec(),
+                2,
+                Pubkey::default()
+            ),
+            frozen()
+        );
+        assert_eq!(
+            metadata.set_update_authority(collection_path, Some(Pubkey::new_unique())),
+            frozen()
+        );
+        assert_eq!(metadata.set_timelock(collection_path, 100), frozen());
+        assert_eq!(
+            metadata.append_collection(collection(collection_path, OTHER)),
+            frozen()
+        );
+        assert_eq!(
+            metadata.rekey_collection(collection_path, OTHER, 2),
+            frozen()
+        );
+        assert_eq!(
+            metadata.remove_collection(collection_path).err(),
+            frozen().err()
+        );
+    }
+
+    // Items can't be moved out of or into frozen collections
+    assert_eq!(
+        metadata.move_item(&[TREASURY], &[SOCIALS], ADDRESS, 2),
+        frozen()
+    );
+    metadata
+        .append_item(&[ROOT], item(OTHER, b"other"))
+        .unwrap();
+    assert_eq!(metadata.move_item(&[ROOT], &[TREASURY], OTHER, 2), frozen());
+    assert!(metadata.item(&[ROOT], OTHER).is_ok());
+
+    // The other collections and the root items stay editable
+    assert!(!metadata.is_frozen(&[SOCIALS]).unwrap());
+    metadata
+        .update_item(&[SOCIALS], item(ADDRESS, b"other"))
+        .unwrap();
+    metadata
+        .update_item(&[ROOT], item(ADDRESS, b"other"))
+        .unwrap();
+    metadata.remove_collection(&[SOCIALS]).unwrap();
+}
+
+#[test]
+fn collections_holding_frozen_collections_cant_be_removed() {
+    let mut metadata = metadata();
+    metadata.freeze_collection(&[TREASURY, SOLANA]).unwrap();
+
+    assert!(!metadata.is_frozen(&[TREASURY]).unwrap());
+    metadata
+        .update_item(&[TREASURY], item(ADDRESS, b"other"))
+        .unwrap();
+    assert_eq!(
+        metadata.remove_collection(&[TREASURY]).err(),
+        frozen().err()
+    );
+    assert_eq!(metadata.rekey_collection(&[TREASURY], OTHER, 2), frozen());
+}
+
+#[test]
+fn only_the_issuing_and_update_authorities_can_freeze() {
+    let mut metadata = metadata();
+    let update_authority = metadata.update_authority.unwrap();
+    verify_permanent_change_authority(&metadata, &metadata.issuing_authority).unwrap();
+    verify_permanent_change_authority(&metadata, &update_authority).unwrap();
+
+    // Delegates, even with every permission, and collection update authorities can't
+    let delegate = Pubkey::new_unique();
+    metadata.collections[0].update_authority = Some(delegate);
+    assert_eq!(
+        verify_permanent_change_authority(&metadata, &delegate),
+        err!(MythicMetadataError::Unauthorized)
+    );
+}
@@ -0,0 +1,238 @@
+//! Verification of SPL Governance authorities against hand serialized realm and governance accounts
+
+use anchor_lang::prelude::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::governance::*;
+
+struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+impl TestAccount {
+    fn new(owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            key: Pubkey::new_unique(),
+            owner,
+            lamports: 0,
+            data,
+        }
+    }
+
+    fn info(&mut self) -> AccountInfo {
+        AccountInfo::new(
+            &self.key,
+            false,
+            false,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+}
+
+/// Serializes the leading fields of a RealmV2 up to and including the authority
+fn realm_data(council_mint: Option<Pubkey>, authority: Option<Pubkey>) -> Vec<u8> {
+    let mut data = vec![16];
+    data.extend(Pubkey::new_unique().to_bytes()); // community_mint
+    data.extend([0, 0]); // legacy1, legacy2
+    data.extend([0; 6]); // reserved
+    data.extend(1u64.to_le_bytes()); // min_community_weight_to_create_governance
+    data.push(0); // community_mint_max_voter_weight_source: SupplyFraction
+    data.extend(10_000_000_000u64.to_le_bytes());
+    data.extend(council_mint.try_to_vec().unwrap());
+    data.extend([0; 6]); // reserved
+    data.extend(0u16.to_le_bytes()); // legacy1
+    data.extend(authority.try_to_vec().unwrap());
+    data.extend("Mythic DAO".to_string().try_to_vec().unwrap()); // name
+    data
+}
+
+fn governance_data(realm: &Pubkey) -> Vec<u8> {
+    let mut data = vec![18];
+    data.extend(realm.to_bytes());
+    data.extend(Pubkey::new_unique().to_bytes()); // governance_seed
+    data
+}
+
+struct Dao {
+    governance_program: Pubkey,
+    realm: TestAccount,
+    governance: TestAccount,
+}
+
+impl Dao {
+    fn new(council_mint: Option<Pubkey>) -> Self {
+        let governance_program = SPL_GOVERNANCE_PROGRAM_ID;
+        let mut realm = TestAccount::new(governance_program, vec![]);
+        let governance = TestAccount::new(governance_program, governance_data(&realm.key));
+        realm.data = realm_data(council_mint, Some(governance.key));
+
+        Self {
+            governance_program,
+            realm,
+            governance,
+        }
+    }
+
+    fn verify(&mut self, authority: &Pubkey) -> Result<()> {
+        let governance_program = self.governance_program;
+        verify_governance_authority(
+            authority,
+            &self.governance.info(),
+            &self.realm.info(),
+            &governance_program,
+        )
+    }
+}
+
+#[test]
+fn governance_and_native_treasury_are_authorities() {
+    for council_mint in [None, Some(Pubkey::new_unique())] {
+        let mut dao = Dao::new(council_mint);
+        let governance = dao.governance.key;
+        let native_treasury = find_native_treasury_address(&governance, &dao.governance_program);
+
+        assert_eq!(
+            realm_authority(&dao.realm.info(), &dao.governance_program).unwrap(),
+            Some(governance)
+        );
+        assert!(dao.verify(&governance).is_ok());
+        assert!(dao.verify(&native_treasury).is_ok());
+        assert_eq!(
+            dao.verify(&Pubkey::new_unique()),
+            Err(MythicMetadataError::Unauthorized.into())
+        );
+    }
+}
+
+#[test]
+fn governance_must_be_the_realm_authority() {
+    let mut dao = Dao::new(None);
+    let governance = dao.governance.key;
+
+    dao.realm.data = realm_data(None, Some(Pubkey::new_unique()));
+    assert_eq!(
+        dao.verify(&governance),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+
+    dao.realm.data = realm_data(None, None);
+    assert_eq!(
+        dao.verify(&governance),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+}
+
+#[test]
+fn governance_accounts_are_validated() {
+    let mut dao = Dao::new(None);
+    let governance = dao.governance.key;
+
+    // Governance of another realm
+    dao.governance.data = governance_data(&Pubkey::new_unique());
+    assert_eq!(
+        dao.verify(&governance),
+        Err(MythicMetadataError::InvalidGovernanceAccount.into())
+    );
+
+    // Not a governance account
+    dao.governance.data = governance_data(&dao.realm.key);
+    dao.governance.data[0] = 17;
+    assert_eq!(
+        dao.verify(&governance),
+        Err(MythicMetadataError::InvalidGovernanceAccount.into())
+    );
+
+    // Truncated realm
+    dao.governance.data = governance_data(&dao.realm.key);
+    dao.realm.data.truncate(60);
+    assert_eq!(
+        dao.verify(&governance),
+        Err(MythicMetadataError::InvalidGovernanceAccount.into())
+    );
+
+    // Accounts owned by another program
+    dao.realm.owner = Pubkey::new_unique();
+    assert!(dao.verify(&governance).is_err());
+}
+
+#[test]
+fn realms_of_other_governance_programs_are_rejected() {
+    // A program owning accounts in the realm and governance layouts
+    let mut dao = Dao::new(None);
+    let governance = dao.governance.key;
+    let governance_program = Pubkey::new_unique();
+    dao.governance_program = governance_program;
+    dao.realm.owner = governance_program;
+    dao.governance.owner = governance_program;
+
+    assert_eq!(
+        dao.verify(&governance),
+        Err(MythicMetadataError::InvalidGovernanceProgram.into())
+    );
+}
+
+#[test]
+fn only_delegates_of_former_realm_authorities_are_stale() {
+    let mut dao = Dao::new(None);
+    let governance = dao.governance.key;
+    let native_treasury = find_native_treasury_address(&governance, &dao.governance_program);
+    let governance_program = dao.governance_program;
+
+    // The governance is still the realm's authority
+    for delegate in [governance, native_treasury] {
+        assert_eq!(
+            verify_stale_governance_delegate(
+                &delegate,
+                &dao.governance.info(),
+                &dao.realm.info(),
+                &governance_program
+            ),
+            Err(MythicMetadataError::GovernanceDelegateNotStale.into())
+        );
+    }
+
+    // The realm moved its authority to another governance, or dropped it
+    for authority in [Some(Pubkey::new_unique()), None] {
+        dao.realm.data = realm_data(None, authority);
+        for delegate in [governance, native_treasury] {
+            assert_eq!(
+                verify_stale_governance_delegate(
+                    &delegate,
+                    &dao.governance.info(),
+                    &dao.realm.info(),
+                    &governance_program
+                ),
+                Ok(authority)
+            );
+        }
+    }
+
+    // Delegates which aren't the governance or its native treasury
+    assert_eq!(
+        verify_stale_governance_delegate(
+            &Pubkey::new_unique(),
+            &dao.governance.info(),
+            &dao.realm.info(),
+            &governance_program
+        ),
+        Err(MythicMetadataError::InvalidGovernanceAccount.into())
+    );
+
+    // Governances of other realms
+    dao.governance.data = governance_data(&Pubkey::new_unique());
+    assert_eq!(
+        verify_stale_governance_delegate(
+            &governance,
+            &dao.governance.info(),
+            &dao.realm.info(),
+            &governance_program
+        ),
+        Err(MythicMetadataError::InvalidGovernanceAccount.into())
+    );
+}
@@ -0,0 +1,188 @@
+//! Multi-valued items: list changes, bounds and value validation
+
+use anchor_lang::prelude::*;
+use mythic_metadata::constants::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+use mythic_metadata::utils::validate_item_value;
+
+const ROOT: u64 = 1;
+const TEAM: u64 = 2;
+const LINKS: u64 = 3;
+
+fn metadata() -> Metadata {
+    let mut metadata = Metadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: None,
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    };
+    metadata
+        .append_collection(MetadataCollection {
+            metadata_key_id: TEAM,
+            parent_path: vec![],
+            update_slot: 0,
+            update_authority: None,
+            timelock_slots: 0,
+            frozen: false,
+            items: vec![],
+            rent_payer: Pubkey::default(),
+        })
+        .unwrap();
+    metadata
+}
+
+fn metadata_key(content_type: &str, multi_valued: bool) -> MetadataKey {
+    MetadataKey {
+        id: LINKS,
+        namespace_authority: Pubkey::new_unique(),
+        name: "links".to_string(),
+        label: "Links".to_string(),
+        description: "Links".to_string(),
+        content_type: content_type.to_string(),
+        bump: 255,
+        multi_valued,
+    }
+}
+
+fn values(metadata: &Metadata, collection_path: &[u64]) -> Vec<Vec<u8>> {
+    decode_list(&metadata.collection_items(collection_path).unwrap()[0].value).unwrap()
+}
+
+#[test]
+fn list_values_are_pushed_replaced_and_removed() {
+    let mut metadata = metadata();
+
+    for (slot, value) in [b"a", b"b", b"c"].into_iter().enumerate() {
+        metadata
+            .push_list_value(
+                &[TEAM],
+                LINKS,
+                value.to_vec(),
+                slot as u64,
+                Pubkey::default(),
+            )
+            .unwrap();
+    }
+    assert_eq!(values(&metadata, &[TEAM]), vec![b"a", b"b", b"c"]);
+    assert_eq!(metadata.collections[0].update_slot, 2);
+
+    metadata
+        .replace_list_value(&[TEAM], LINKS, 1, b"B".to_vec(), 3, Pubkey::default())
+        .unwrap();
+    metadata.remove_list_value(&[TEAM], LINKS, 0, 4).unwrap();
+    assert_eq!(values(&metadata, &[TEAM]), vec![b"B", b"c"]);
+    assert_eq!(metadata.collections[0].items[0].update_slot, 4);
+
+    assert_eq!(
+        metadata.remove_list_value(&[TEAM], LINKS, 2, 5),
+        Err(MythicMetadataError::MetadataListIndexOutOfBounds.into())
+    );
+    assert_eq!(
+        metadata.replace_list_value(&[TEAM], LINKS, 2, vec![], 5, Pubkey::default()),
+        Err(MythicMetadataError::MetadataListIndexOutOfBounds.into())
+    );
+
+    metadata.clear_list(&[TEAM], LINKS, 6).unwrap();
+    assert!(values(&metadata, &[TEAM]).is_empty());
+
+    // The root items hold lists as well, only existing lists can be cleared
+    assert_eq!(
+        metadata.clear_list(&[ROOT], LINKS, 7),
+        Err(MythicMetadataError::MetadataItemNonExistent.into())
+    );
+    metadata
+        .push_list_value(&[ROOT], LINKS, b"root".to_vec(), 7, Pubkey::default())
+        .unwrap();
+    assert_eq!(values(&metadata, &[ROOT]), vec![b"root"]);
+}
+
+#[test]
+fn list_length_is_bounded() {
+    let mut metadata = metadata();
+    for _ in 0..MAX_LIST_LEN {
+        metadata
+            .push_list_value(&[TEAM], LINKS, vec![1], 1, Pubkey::default())
+            .unwrap();
+    }
+
+    assert_eq!(
+        metadata.push_list_value(&[TEAM], LINKS, vec![1], 1, Pubkey::default()),
+        Err(MythicMetadataError::MetadataListFull.into())
+    );
+
+    let too_long = vec![vec![1u8]; MAX_LIST_LEN + 1].try_to_vec().unwrap();
+    assert_eq!(
+        validate_item_value(&metadata_key("string", true), &too_long),
+        Err(MythicMetadataError::MetadataListFull.into())
+    );
+}
+
+#[test]
+fn list_values_are_validated() {
+    let external_reference = ExternalReference::new(
+        "https://example.com".to_string(),
+        HashAlgorithm::Sha256,
+        b"content",
+    )
+    .try_to_vec()
+    .unwrap();
+
+    let multi_valued = metadata_key(EXTERNAL_REFERENCE_CONTENT_TYPE, true);
+    assert!(validate_item_value(
+        &multi_valued,
+        &vec![external_reference.clone(), external_reference.clone()]
+            .try_to_vec()
+            .unwrap()
+    )
+    .is_ok());
+    assert_eq!(
+        validate_item_value(
+            &multi_valued,
+            &vec![external_reference.clone(), b"invalid".to_vec()]
+                .try_to_vec()
+                .unwrap()
+        ),
+        Err(MythicMetadataError::InvalidExternalReference.into())
+    );
+    // A single value isn't a list
+    assert_eq!(
+        validate_item_value(&metadata_key("string", true), b"a"),
+        Err(MythicMetadataError::InvalidMetadataList.into())
+    );
+    assert!(validate_item_value(
+        &metadata_key(EXTERNAL_REFERENCE_CONTENT_TYPE, false),
+        &external_reference
+    )
+    .is_ok());
+}
+
+#[test]
+fn create_metadata_key_args_without_multi_valued_are_single_valued() {
+    let args = mythic_metadata::CreateMetadataKeyArgs {
+        id: LINKS,
+        name: "links".to_string(),
+        label: "Links".to_string(),
+        description: "Links".to_string(),
+        content_type: "string".to_string(),
+        multi_valued: true,
+    };
+    let data = args.try_to_vec().unwrap();
+
+    let args = mythic_metadata::CreateMetadataKeyArgs::try_from_slice(&data).unwrap();
+    assert!(args.multi_valued);
+
+    // Clients built before multi-valued keys send the args without the flag
+    let args =
+        mythic_metadata::CreateMetadataKeyArgs::try_from_slice(&data[..data.len() - 1]).unwrap();
+    assert!(!args.multi_valued);
+    assert_eq!(args.content_type, "string");
+}
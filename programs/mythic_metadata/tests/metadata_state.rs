@@ -0,0 +1,660 @@
+//! Property based tests for the Metadata state machine
+//!
+//! Random sequences of instructions are executed against both `Metadata` and a simple
+//! BTreeMap based model. A failing instruction is rolled back the same way a failing
+//! transaction would be. After every step the sorted order, size accounting,
+//! validation limits and authority rules, including a single MetadataDelegate, are checked.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::*;
+use mythic_metadata::constants::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+use mythic_metadata::utils::*;
+use proptest::prelude::*;
+
+const ROOT_METADATA_KEY_ID: u64 = 0;
+const AUTHORITIES: u8 = 3;
+/// The authority index of the delegate, it's never used as an update authority
+const DELEGATE_AUTHORITY: u8 = AUTHORITIES;
+
+fn authority(index: u8) -> Pubkey {
+    Pubkey::new_from_array([index + 1; 32])
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+    AppendCollection {
+        delegated: bool,
+        collection: u64,
+        update_authority: Option<u8>,
+    },
+    RemoveCollection {
+        delegated: bool,
+        collection: u64,
+    },
+    AppendItems {
+        delegated: bool,
+        collection: u64,
+        items: Vec<(u64, usize)>,
+    },
+    UpdateItem {
+        signer: u8,
+        collection: u64,
+        item: u64,
+        value_len: usize,
+    },
+    RemoveItem {
+        delegated: bool,
+        collection: u64,
+        item: u64,
+    },
+    SetUpdateAuthority {
+        delegated: bool,
+        collection: u64,
+        update_authority: Option<u8>,
+    },
+    SetDelegate {
+        collection: u64,
+        permissions: u8,
+    },
+}
+
+fn collection_id() -> impl Strategy<Value = u64> {
+    prop_oneof![0..6u64, 0..128u64]
+}
+
+fn item_id() -> impl Strategy<Value = u64> {
+    prop_oneof![3 => 0..8u64, 1 => 0..128u64]
+}
+
+fn value_len() -> impl Strategy<Value = usize> {
+    prop_oneof![
+        20 => 0..64usize,
+        1 => MAX_VALUE_LEN - 1..MAX_VALUE_LEN + 2,
+    ]
+}
+
+fn update_authority() -> impl Strategy<Value = Option<u8>> {
+    proptest::option::weighted(0.8, 0..AUTHORITIES)
+}
+
+fn delegated() -> impl Strategy<Value = bool> {
+    proptest::bool::weighted(0.2)
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        2 => (delegated(), collection_id(), update_authority()).prop_map(
+            |(delegated, collection, update_authority)| Op::AppendCollection {
+                delegated,
+                collection,
+                update_authority,
+            }
+        ),
+        1 => (delegated(), collection_id()).prop_map(|(delegated, collection)| {
+            Op::RemoveCollection {
+                delegated,
+                collection,
+            }
+        }),
+        6 => (
+            delegated(),
+            collection_id(),
+            prop::collection::vec((item_id(), value_len()), 1..12)
+        )
+            .prop_map(|(delegated, collection, items)| Op::AppendItems {
+                delegated,
+                collection,
+                items
+            }),
+        4 => (0..=DELEGATE_AUTHORITY, collection_id(), item_id(), value_len()).prop_map(
+            |(signer, collection, item, value_len)| Op::UpdateItem {
+                signer,
+                collection,
+                item,
+                value_len,
+            }
+        ),
+        2 => (delegated(), collection_id(), item_id()).prop_map(
+            |(delegated, collection, item)| Op::RemoveItem {
+                delegated,
+                collection,
+                item,
+            }
+        ),
+        1 => (delegated(), collection_id(), update_authority()).prop_map(
+            |(delegated, collection, update_authority)| Op::SetUpdateAuthority {
+                delegated,
+                collection,
+                update_authority,
+            }
+        ),
+        1 => (collection_id(), 0..1u8 << 5).prop_map(|(collection, permissions)| {
+            Op::SetDelegate {
+                collection,
+                permissions,
+            }
+        }),
+    ]
+}
+
+/// (update_slot, value) by metadata_key_id
+type ModelItems = BTreeMap<u64, (u64, Vec<u8>)>;
+
+#[derive(Clone, Debug, Default)]
+struct ModelCollection {
+    update_slot: u64,
+    update_authority: Option<Pubkey>,
+    items: ModelItems,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Model {
+    update_slot: u64,
+    update_authority: Option<Pubkey>,
+    items: ModelItems,
+    collections: BTreeMap<u64, ModelCollection>,
+    /// (collection, permissions) granted to the delegate
+    delegate: Option<(u64, u8)>,
+}
+
+impl Model {
+    fn authorize(
+        &self,
+        collection: u64,
+        signer: &Pubkey,
+    ) -> std::result::Result<(), MythicMetadataError> {
+        if collection != ROOT_METADATA_KEY_ID {
+            let collection = self
+                .collections
+                .get(&collection)
+                .ok_or(MythicMetadataError::MetadataCollectionNonExistent)?;
+            if collection.update_authority == Some(*signer) {
+                return Ok(());
+            }
+        }
+
+        // A revoked update authority falls back to the issuing authority, which never signs here
+        match self.update_authority {
+            Some(update_authority) if update_authority == *signer => Ok(()),
+            _ if *signer == authority(DELEGATE_AUTHORITY) => {
+                self.authorize_delegate(collection, MetadataDelegate::UPDATE)
+            }
+            _ => Err(MythicMetadataError::Unauthorized),
+        }
+    }
+
+    fn authorize_delegate(
+        &self,
+        collection: u64,
+        permission: u8,
+    ) -> std::result::Result<(), MythicMetadataError> {
+        match self.delegate {
+            Some((scope, permissions))
+                if (scope == ROOT_METADATA_KEY_ID || scope == collection)
+                    && permissions & permission == permission =>
+            {
+                Ok(())
+            }
+            _ => Err(MythicMetadataError::Unauthorized),
+        }
+    }
+
+    fn authorize_issuing(
+        &self,
+        delegated: bool,
+        collection: u64,
+        permission: u8,
+    ) -> std::result::Result<(), MythicMetadataError> {
+        if delegated {
+            self.authorize_delegate(collection, permission)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn items_mut(
+        &mut self,
+        collection: u64,
+    ) -> std::result::Result<(&mut ModelItems, &mut u64), MythicMetadataError> {
+        if collection == ROOT_METADATA_KEY_ID {
+            return Ok((&mut self.items, &mut self.update_slot));
+        }
+
+        let collection = self
+            .collections
+            .get_mut(&collection)
+            .ok_or(MythicMetadataError::MetadataCollectionNonExistent)?;
+        Ok((&mut collection.items, &mut collection.update_slot))
+    }
+
+    fn apply(&mut self, op: &Op, slot: u64) -> std::result::Result<(), MythicMetadataError> {
+        match op {
+            Op::AppendCollection {
+                delegated,
+                collection,
+                update_authority,
+            } => {
+                self.authorize_issuing(
+                    *delegated,
+                    *collection,
+                    MetadataDelegate::MANAGE_COLLECTIONS,
+                )?;
+                if self.collections.contains_key(collection) {
+                    return Err(MythicMetadataError::MetadataCollectionAlreadyExists);
+                }
+                if self.collections.len() == MAX_COLLECTIONS_PER_METADATA {
+                    return Err(MythicMetadataError::MetadataCollectionFull);
+                }
+                self.collections.insert(
+                    *collection,
+                    ModelCollection {
+                        update_slot: slot,
+                        update_authority: update_authority.map(authority),
+                        items: BTreeMap::new(),
+                    },
+                );
+            }
+            Op::RemoveCollection {
+                delegated,
+                collection,
+            } => {
+                self.authorize_issuing(
+                    *delegated,
+                    *collection,
+                    MetadataDelegate::MANAGE_COLLECTIONS,
+                )?;
+                self.collections
+                    .remove(collection)
+                    .ok_or(MythicMetadataError::MetadataCollectionNonExistent)?;
+            }
+            Op::AppendItems {
+                delegated,
+                collection,
+                items,
+            } => {
+                self.authorize_issuing(*delegated, *collection, MetadataDelegate::APPEND)?;
+                let (model_items, update_slot) = self.items_mut(*collection)?;
+                for (item, value_len) in items {
+                    if model_items.contains_key(item) {
+                        return Err(MythicMetadataError::MetadataItemAlreadyExists);
+                    }
+                    model_items.insert(*item, (slot, value(*value_len, slot)));
+                    *update_slot = slot;
+                }
+                if model_items.len() > MAX_ITEMS_PER_COLLECTION {
+                    return Err(MythicMetadataError::MetadataItemFull);
+                }
+                if items
+                    .iter()
+                    .any(|(_, value_len)| *value_len > MAX_VALUE_LEN)
+                {
+                    return Err(MythicMetadataError::MetadataItemValueLenExceeded);
+                }
+            }
+            Op::UpdateItem {
+                signer,
+                collection,
+                item,
+                value_len,
+            } => {
+                self.authorize(*collection, &authority(*signer))?;
+                let (model_items, update_slot) = self.items_mut(*collection)?;
+                let model_item = model_items
+                    .get_mut(item)
+                    .ok_or(MythicMetadataError::MetadataItemNonExistent)?;
+                *model_item = (slot, value(*value_len, slot));
+                *update_slot = slot;
+                if *value_len > MAX_VALUE_LEN {
+                    return Err(MythicMetadataError::MetadataItemValueLenExceeded);
+                }
+            }
+            Op::RemoveItem {
+                delegated,
+                collection,
+                item,
+            } => {
+                self.authorize_issuing(*delegated, *collection, MetadataDelegate::REMOVE)?;
+                let (model_items, _) = self.items_mut(*collection)?;
+                model_items
+                    .remove(item)
+                    .ok_or(MythicMetadataError::MetadataItemNonExistent)?;
+            }
+            Op::SetUpdateAuthority {
+                delegated,
+                collection,
+                update_authority,
+            } => {
+                self.authorize_issuing(
+                    *delegated,
+                    *collection,
+                    MetadataDelegate::MANAGE_AUTHORITIES,
+                )?;
+                let update_authority = update_authority.map(authority);
+                if *collection == ROOT_METADATA_KEY_ID {
+                    self.update_authority = update_authority;
+                } else {
+                    self.collections
+                        .get_mut(collection)
+                        .ok_or(MythicMetadataError::MetadataCollectionNonExistent)?
+                        .update_authority = update_authority;
+                }
+            }
+            Op::SetDelegate {
+                collection,
+                permissions,
+            } => {
+                if *permissions == 0 {
+                    return Err(MythicMetadataError::InvalidDelegatePermissions);
+                }
+                self.delegate = Some((*collection, *permissions));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn value(len: usize, slot: u64) -> Vec<u8> {
+    vec![slot as u8; len]
+}
+
+fn signer(metadata: &Metadata, delegated: bool) -> Pubkey {
+    if delegated {
+        authority(DELEGATE_AUTHORITY)
+    } else {
+        metadata.issuing_authority
+    }
+}
+
+/// Mirrors the instruction handlers without the account checks and reallocation
+fn execute(
+    metadata: &mut Metadata,
+    delegate_record: &mut Option<MetadataDelegate>,
+    op: &Op,
+    slot: u64,
+) -> Result<()> {
+    match op {
+        Op::AppendCollection {
+            delegated,
+            collection,
+            update_authority,
+        } => {
+            verify_issuing_authority(
+                metadata,
+                &signer(metadata, *delegated),
+                delegate_record.as_ref(),
+                &[*collection],
+                MetadataDelegate::MANAGE_COLLECTIONS,
+            )?;
+            metadata.append_collection(MetadataCollection {
+                metadata_key_id: *collection,
+                parent_path: vec![],
+                update_slot: slot,
+                update_authority: update_authority.map(authority),
+                timelock_slots: 0,
+                frozen: false,
+                items: vec![],
+                rent_payer: Pubkey::default(),
+            })?;
+        }
+        Op::RemoveCollection {
+            delegated,
+            collection,
+        } => {
+            verify_issuing_authority(
+                metadata,
+                &signer(metadata, *delegated),
+                delegate_record.as_ref(),
+                &[*collection],
+                MetadataDelegate::MANAGE_COLLECTIONS,
+            )?;
+            metadata.remove_collection(&[*collection])?;
+        }
+        Op::AppendItems {
+            delegated,
+            collection,
+            items,
+        } => {
+            verify_issuing_authority(
+                metadata,
+                &signer(metadata, *delegated),
+                delegate_record.as_ref(),
+                &[*collection],
+                MetadataDelegate::APPEND,
+            )?;
+            for (item, value_len) in items {
+                metadata.append_item(
+                    &[*collection],
+                    MetadataItem {
+                        metadata_key_id: *item,
+                        update_slot: slot,
+                        value: value(*value_len, slot),
+                        rent_payer: Pubkey::default(),
+                        growth_rent_shares: vec![],
+                    },
+                )?;
+            }
+        }
+        Op::UpdateItem {
+            signer,
+            collection,
+            item,
+            value_len,
+        } => {
+            verify_item_update_authority(
+                metadata,
+                &[*collection],
+                &authority(*signer),
+                delegate_record.as_ref(),
+            )?;
+            metadata.update_item(
+                &[*collection],
+                MetadataItem {
+                    metadata_key_id: *item,
+                    update_slot: slot,
+                    value: value(*value_len, slot),
+                    rent_payer: Pubkey::default(),
+                    growth_rent_shares: vec![],
+                },
+            )?;
+        }
+        Op::RemoveItem {
+            delegated,
+            collection,
+            item,
+        } => {
+            verify_issuing_authority(
+                metadata,
+                &signer(metadata, *delegated),
+                delegate_record.as_ref(),
+                &[*collection],
+                MetadataDelegate::REMOVE,
+            )?;
+            metadata.remove_item(&[*collection], *item)?;
+        }
+        Op::SetUpdateAuthority {
+            delegated,
+            collection,
+            update_authority,
+        } => {
+            verify_issuing_authority(
+                metadata,
+                &signer(metadata, *delegated),
+                delegate_record.as_ref(),
+                &[*collection],
+                MetadataDelegate::MANAGE_AUTHORITIES,
+            )?;
+            metadata.set_update_authority(&[*collection], update_authority.map(authority))?;
+        }
+        Op::SetDelegate {
+            collection,
+            permissions,
+        } => {
+            let record = MetadataDelegate {
+                metadata: Pubkey::default(),
+                collection_path: vec![*collection],
+                delegate: authority(DELEGATE_AUTHORITY),
+                permissions: *permissions,
+                bump: 255,
+            };
+            record.validate()?;
+            *delegate_record = Some(record);
+        }
+    }
+
+    metadata.validate()
+}
+
+fn assert_items_match(items: &[MetadataItem], model_items: &ModelItems) {
+    assert!(
+        items
+            .windows(2)
+            .all(|pair| pair[0].metadata_key_id < pair[1].metadata_key_id),
+        "items are not sorted by metadata_key_id"
+    );
+    let items: ModelItems = items
+        .iter()
+        .map(|item| (item.metadata_key_id, (item.update_slot, item.value.clone())))
+        .collect();
+    assert_eq!(&items, model_items);
+}
+
+fn assert_invariants(
+    metadata: &Metadata,
+    delegate_record: Option<&MetadataDelegate>,
+    model: &Model,
+) {
+    // Ordering and content
+    assert_eq!(metadata.update_slot, model.update_slot);
+    assert_eq!(metadata.update_authority, model.update_authority);
+    assert_items_match(&metadata.items, &model.items);
+    assert!(
+        metadata
+            .collections
+            .windows(2)
+            .all(|pair| pair[0].metadata_key_id < pair[1].metadata_key_id),
+        "collections are not sorted by metadata_key_id"
+    );
+    assert_eq!(
+        metadata
+            .collections
+            .iter()
+            .map(|collection| collection.metadata_key_id)
+            .collect::<Vec<_>>(),
+        model.collections.keys().copied().collect::<Vec<_>>()
+    );
+    for collection in &metadata.collections {
+        let model_collection = &model.collections[&collection.metadata_key_id];
+        assert_eq!(collection.update_slot, model_collection.update_slot);
+        assert_eq!(
+            collection.update_authority,
+            model_collection.update_authority
+        );
+        assert_items_match(&collection.items, &model_collection.items);
+    }
+
+    // Size accounting
+    // Metadata::size always reserves space for the optional authorities and the finalized slot
+    let mut data = vec![];
+    metadata.try_serialize(&mut data).unwrap();
+    let unset_authorities = std::iter::once(&metadata.update_authority)
+        .chain(
+            metadata
+                .collections
+                .iter()
+                .map(|collection| &collection.update_authority),
+        )
+        .filter(|update_authority| update_authority.is_none())
+        .count();
+    assert_eq!(
+        Metadata::size(&metadata.items, &metadata.collections),
+        data.len() + 32 * unset_authorities + 8 + 4
+    );
+    let deserialized = Metadata::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(
+        deserialized.try_to_vec().unwrap(),
+        metadata.try_to_vec().unwrap()
+    );
+
+    // Limits
+    metadata.validate().unwrap();
+
+    // Authority rules
+    for collection in 0..6 {
+        for signer in (0..=DELEGATE_AUTHORITY).map(authority) {
+            assert_eq!(
+                verify_item_update_authority(metadata, &[collection], &signer, delegate_record),
+                model.authorize(collection, &signer).map_err(Error::from)
+            );
+        }
+
+        for permission in (0..5).map(|bit| 1u8 << bit) {
+            assert_eq!(
+                verify_issuing_authority(
+                    metadata,
+                    &authority(DELEGATE_AUTHORITY),
+                    delegate_record,
+                    &[collection],
+                    permission
+                ),
+                model
+                    .authorize_delegate(collection, permission)
+                    .map_err(Error::from)
+            );
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(128))]
+
+    #[test]
+    fn metadata_matches_model(
+        update_authority in update_authority(),
+        ops in prop::collection::vec(op(), 1..200),
+    ) {
+        let update_authority = update_authority.map(authority);
+        let mut metadata = Metadata {
+            subject: Pubkey::new_unique(),
+            metadata_key_id: ROOT_METADATA_KEY_ID,
+            issuing_authority: Pubkey::new_unique(),
+            update_slot: 0,
+            update_authority,
+            items: vec![],
+            collections: vec![],
+            bump: 255,
+            verified_subject: false,
+            finalized_slot: None,
+            registry_page_index: None,
+            records_count: 0,
+        };
+        let mut delegate_record = None;
+        let mut model = Model {
+            update_authority,
+            ..Model::default()
+        };
+
+        for (step, op) in ops.iter().enumerate() {
+            let slot = step as u64 + 1;
+
+            // Failed instructions are rolled back together with the transaction
+            let mut next_metadata = metadata.clone();
+            let mut next_delegate_record = delegate_record.clone();
+            let mut next_model = model.clone();
+            let result = execute(&mut next_metadata, &mut next_delegate_record, op, slot);
+            let expected = next_model.apply(op, slot).map_err(Error::from);
+            prop_assert_eq!(&result, &expected, "step {} {:?}", step, op);
+
+            if result.is_ok() {
+                metadata = next_metadata;
+                delegate_record = next_delegate_record;
+                model = next_model;
+            }
+
+            assert_invariants(&metadata, delegate_record.as_ref(), &model);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Parsing of hand serialized Metaplex Metadata accounts
+
+use anchor_lang::prelude::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::metaplex::MetaplexMetadata;
+
+/// Serializes a MetadataV1 with the strings padded like Metaplex does
+fn metaplex_data(update_authority: &Pubkey, mint: &Pubkey) -> Vec<u8> {
+    let padded = |value: &str, len: usize| format!("{value:\0<len$}");
+
+    let mut data = vec![4];
+    data.extend(update_authority.to_bytes());
+    data.extend(mint.to_bytes());
+    data.extend(padded("Mythic #1", 32).try_to_vec().unwrap());
+    data.extend(padded("MYTH", 10).try_to_vec().unwrap());
+    data.extend(
+        padded("https://example.com/1.json", 200)
+            .try_to_vec()
+            .unwrap(),
+    );
+    data.extend(500u16.to_le_bytes()); // seller_fee_basis_points
+    data.push(0); // creators
+    data
+}
+
+#[test]
+fn metaplex_metadata_is_parsed_and_trimmed() {
+    let update_authority = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+
+    assert_eq!(
+        MetaplexMetadata::parse(&metaplex_data(&update_authority, &mint)).unwrap(),
+        MetaplexMetadata {
+            update_authority,
+            mint,
+            name: "Mythic #1".to_string(),
+            symbol: "MYTH".to_string(),
+            uri: "https://example.com/1.json".to_string(),
+        }
+    );
+}
+
+#[test]
+fn other_accounts_are_rejected() {
+    let mut data = metaplex_data(&Pubkey::new_unique(), &Pubkey::new_unique());
+
+    // Truncated
+    assert_eq!(
+        MetaplexMetadata::parse(&data[..100]),
+        Err(MythicMetadataError::InvalidMetaplexMetadata.into())
+    );
+
+    // MasterEditionV2
+    data[0] = 6;
+    assert_eq!(
+        MetaplexMetadata::parse(&data),
+        Err(MythicMetadataError::InvalidMetaplexMetadata.into())
+    );
+    assert!(MetaplexMetadata::parse(&[]).is_err());
+}
@@ -0,0 +1,153 @@
+//! Metadata and MetadataKey accounts created in the legacy layouts are rewritten in the current layouts
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::Discriminator;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+
+const ROOT: u64 = 1;
+const SOCIALS: u64 = 2;
+const HANDLE: u64 = 3;
+
+fn legacy_item(metadata_key_id: u64, value: &[u8]) -> LegacyMetadataItem {
+    LegacyMetadataItem {
+        metadata_key_id,
+        update_slot: 1,
+        value: value.to_vec(),
+    }
+}
+
+fn legacy_metadata() -> LegacyMetadata {
+    LegacyMetadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 2,
+        update_authority: Some(Pubkey::new_unique()),
+        items: vec![legacy_item(HANDLE, b"mythic")],
+        collections: vec![LegacyMetadataCollection {
+            metadata_key_id: SOCIALS,
+            update_slot: 2,
+            update_authority: None,
+            items: vec![legacy_item(HANDLE, b"@mythic")],
+        }],
+        bump: 254,
+    }
+}
+
+fn account_data(discriminator: [u8; 8], data: impl AnchorSerialize) -> Vec<u8> {
+    [&discriminator[..], &data.try_to_vec().unwrap()].concat()
+}
+
+#[test]
+fn legacy_metadata_is_migrated() {
+    let legacy = legacy_metadata();
+    // Removed items leave stale bytes after the legacy data
+    let mut data = account_data(LEGACY_METADATA_DISCRIMINATOR, legacy.clone());
+    data.extend([1; 16]);
+
+    let metadata = LegacyMetadata::try_from_account_data(&data)
+        .unwrap()
+        .into_metadata();
+    metadata.validate().unwrap();
+    assert_eq!(metadata.subject, legacy.subject);
+    assert_eq!(metadata.update_authority, legacy.update_authority);
+    assert_eq!(metadata.bump, 254);
+    // The legacy program couldn't finalize a Metadata
+    assert_eq!(metadata.finalized_slot, None);
+    assert_eq!(metadata.item(&[ROOT], HANDLE).unwrap().value, b"mythic");
+    assert_eq!(metadata.item(&[SOCIALS], HANDLE).unwrap().value, b"@mythic");
+
+    // The issuing authority funded the legacy account, it receives its rent on close
+    assert!(metadata
+        .rent_shares()
+        .all(|rent_share| rent_share.rent_payer == legacy.issuing_authority));
+
+    // Migrated accounts deserialize in the current layout and can't be migrated again
+    let mut migrated = vec![];
+    metadata.try_serialize(&mut migrated).unwrap();
+    Metadata::try_deserialize(&mut &migrated[..]).unwrap();
+    assert_eq!(
+        LegacyMetadata::try_from_account_data(&migrated).err(),
+        Some(MythicMetadataError::AccountAlreadyMigrated.into())
+    );
+}
+
+#[test]
+fn legacy_metadata_is_told_apart_by_its_discriminator() {
+    assert_eq!(
+        LEGACY_METADATA_DISCRIMINATOR,
+        hash(b"account:Metadata").to_bytes()[..8]
+    );
+    assert_ne!(LEGACY_METADATA_DISCRIMINATOR, Metadata::DISCRIMINATOR);
+
+    // The legacy program never shrank accounts: removing the items of this Metadata left zeroed
+    // bytes after its data, which deserialize as the fields the current layout appends
+    let legacy = LegacyMetadata {
+        update_authority: None,
+        items: vec![],
+        collections: vec![],
+        ..legacy_metadata()
+    };
+    let mut data = account_data(LEGACY_METADATA_DISCRIMINATOR, legacy.clone());
+    data.extend([0; 64]);
+    assert!(Metadata::deserialize(&mut &data[8..]).is_ok());
+    assert!(Metadata::try_deserialize(&mut &data[..]).is_err());
+
+    let metadata = LegacyMetadata::try_from_account_data(&data)
+        .unwrap()
+        .into_metadata();
+    assert_eq!(metadata.subject, legacy.subject);
+    assert_eq!(metadata.update_authority, None);
+    assert_eq!(metadata.finalized_slot, None);
+    assert!(metadata.items.is_empty() && metadata.collections.is_empty());
+
+    assert_eq!(
+        LegacyMetadata::try_from_account_data(&account_data(
+            LEGACY_METADATA_KEY_DISCRIMINATOR,
+            legacy
+        ))
+        .err(),
+        Some(ErrorCode::AccountDiscriminatorMismatch.into())
+    );
+}
+
+#[test]
+fn legacy_metadata_keys_are_migrated() {
+    let legacy = LegacyMetadataKey {
+        id: HANDLE,
+        namespace_authority: Pubkey::new_unique(),
+        name: "handle".to_string(),
+        label: "Handle".to_string(),
+        description: "Handle".to_string(),
+        content_type: "string".to_string(),
+        bump: 253,
+    };
+    assert_eq!(
+        LEGACY_METADATA_KEY_DISCRIMINATOR,
+        hash(b"account:MetadataKey").to_bytes()[..8]
+    );
+    let data = account_data(LEGACY_METADATA_KEY_DISCRIMINATOR, legacy.clone());
+
+    let metadata_key = LegacyMetadataKey::try_from_account_data(&data)
+        .unwrap()
+        .into_metadata_key();
+    assert_eq!(metadata_key.id, HANDLE);
+    assert_eq!(metadata_key.namespace_authority, legacy.namespace_authority);
+    assert!(!metadata_key.multi_valued);
+
+    let mut migrated = vec![];
+    metadata_key.try_serialize(&mut migrated).unwrap();
+    assert_eq!(
+        LegacyMetadataKey::try_from_account_data(&migrated).err(),
+        Some(MythicMetadataError::AccountAlreadyMigrated.into())
+    );
+
+    // Only MetadataKey accounts are migrated as MetadataKeys
+    assert!(LegacyMetadataKey::try_from_account_data(&account_data(
+        LEGACY_METADATA_DISCRIMINATOR,
+        legacy_metadata()
+    ))
+    .is_err());
+}
@@ -0,0 +1,163 @@
+//! M-of-N update authorities
+
+use anchor_lang::prelude::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+use mythic_metadata::utils::*;
+
+const ROOT: u64 = 1;
+const SOCIALS: u64 = 2;
+
+fn multisig_authority(threshold: u8, signers: &[Pubkey]) -> MultisigAuthority {
+    let mut signers = signers.to_vec();
+    signers.sort();
+    MultisigAuthority {
+        create_key: Pubkey::new_unique(),
+        threshold,
+        signers,
+        bump: 255,
+    }
+}
+
+fn metadata(update_authority: Pubkey, socials_update_authority: Option<Pubkey>) -> Metadata {
+    let mut metadata = Metadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: Some(update_authority),
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    };
+    metadata
+        .append_collection(MetadataCollection {
+            metadata_key_id: SOCIALS,
+            parent_path: vec![],
+            update_slot: 0,
+            update_authority: socials_update_authority,
+            timelock_slots: 0,
+            frozen: false,
+            items: vec![],
+            rent_payer: Pubkey::default(),
+        })
+        .unwrap();
+    metadata
+}
+
+fn signers<'a>(
+    multisig_address: Pubkey,
+    multisig_authority: &'a MultisigAuthority,
+    transaction_signers: &[Pubkey],
+) -> MultisigSigners<'a> {
+    MultisigSigners {
+        signer: transaction_signers[0],
+        multisig_authority: Some((multisig_address, multisig_authority)),
+        transaction_signers: transaction_signers.to_vec(),
+    }
+}
+
+#[test]
+fn multisig_authorities_are_validated() {
+    let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    multisig_authority(2, &members).validate().unwrap();
+    for invalid in [
+        multisig_authority(0, &members),
+        multisig_authority(3, &members),
+        multisig_authority(1, &[members[0], members[0]]),
+        multisig_authority(1, &[Pubkey::new_unique(); 11]),
+    ] {
+        assert_eq!(
+            invalid.validate(),
+            Err(MythicMetadataError::InvalidMultisigAuthority.into())
+        );
+    }
+}
+
+#[test]
+fn threshold_signers_act_as_the_metadata_update_authority() {
+    let members = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let outsider = Pubkey::new_unique();
+    let multisig = multisig_authority(2, &members);
+    let multisig_address = Pubkey::new_unique();
+    let metadata = metadata(multisig_address, None);
+
+    assert_eq!(
+        verify_item_update_authority(
+            &metadata,
+            &[ROOT],
+            &signers(multisig_address, &multisig, &[members[0], members[2]]),
+            None
+        ),
+        Ok(())
+    );
+
+    for transaction_signers in [
+        &[members[0]][..],
+        &[members[0], members[0]],
+        &[members[1], outsider],
+    ] {
+        assert_eq!(
+            verify_item_update_authority(
+                &metadata,
+                &[ROOT],
+                &signers(multisig_address, &multisig, transaction_signers),
+                None
+            ),
+            Err(MythicMetadataError::Unauthorized.into())
+        );
+    }
+
+    // The approvals only count for the multisig they were collected for
+    let other_multisig = multisig_authority(1, &[outsider]);
+    assert_eq!(
+        verify_item_update_authority(
+            &metadata,
+            &[ROOT],
+            &signers(Pubkey::new_unique(), &other_multisig, &[outsider]),
+            None
+        ),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+}
+
+#[test]
+fn threshold_signers_act_as_the_collection_update_authority() {
+    let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let multisig = multisig_authority(2, &members);
+    let multisig_address = Pubkey::new_unique();
+    let metadata = metadata(Pubkey::new_unique(), Some(multisig_address));
+
+    assert!(is_collection_update_authority(
+        &metadata,
+        &[SOCIALS],
+        &signers(multisig_address, &multisig, &members)
+    )
+    .unwrap());
+    assert!(!is_collection_update_authority(
+        &metadata,
+        &[SOCIALS],
+        &signers(multisig_address, &multisig, &members[..1])
+    )
+    .unwrap());
+
+    // Root items are only updated by the Metadata update authority
+    assert_eq!(
+        verify_item_update_authority(
+            &metadata,
+            &[ROOT],
+            &signers(multisig_address, &multisig, &members),
+            None
+        ),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+}
@@ -0,0 +1,62 @@
+//! Paging of the MetadataKeys listed in the registry of a namespace
+
+use anchor_lang::prelude::*;
+use mythic_metadata::constants::*;
+use mythic_metadata::state::*;
+
+fn registry(keys_count: u64) -> NamespaceRegistry {
+    NamespaceRegistry {
+        namespace_authority: Pubkey::new_unique(),
+        keys_count,
+        bump: 255,
+    }
+}
+
+#[test]
+fn keys_fill_pages_in_order() {
+    let page_len = NAMESPACE_REGISTRY_PAGE_LEN as u64;
+
+    assert_eq!(registry(0).next_page_index(), 0);
+    assert_eq!(registry(0).page_count(), 0);
+    assert_eq!(registry(1).page_count(), 1);
+    assert_eq!(registry(page_len - 1).next_page_index(), 0);
+    assert_eq!(registry(page_len).next_page_index(), 1);
+    assert_eq!(registry(page_len).page_count(), 1);
+    assert_eq!(registry(page_len + 1).page_count(), 2);
+}
+
+#[test]
+fn sizes_match_the_serialized_accounts() {
+    let registry = registry(3);
+    assert_eq!(
+        registry.try_to_vec().unwrap().len() + 8,
+        NamespaceRegistry::size()
+    );
+
+    let page = NamespaceRegistryPage {
+        namespace_authority: registry.namespace_authority,
+        page_index: 0,
+        entries: vec![
+            NamespaceRegistryEntry {
+                id: 1,
+                name: "twitter".to_string(),
+                content_type: "string".to_string(),
+            },
+            NamespaceRegistryEntry {
+                id: 2,
+                name: "logo".to_string(),
+                content_type: "image".to_string(),
+            },
+        ],
+        bump: 255,
+    };
+    assert_eq!(
+        page.try_to_vec().unwrap().len() + 8,
+        NamespaceRegistryPage::size(&page.entries)
+    );
+
+    // A full page of the longest names and content types stays within the realloc limit
+    let longest =
+        NamespaceRegistryEntry::size(&"n".repeat(MAX_NAME_LEN), &"c".repeat(MAX_CONTENT_TYPE_LEN));
+    assert!(NamespaceRegistryPage::size(&[]) + NAMESPACE_REGISTRY_PAGE_LEN * longest <= 10240);
+}
@@ -0,0 +1,288 @@
+//! Collections nested in collections: addressing by path, removal and authority inheritance
+
+use anchor_lang::prelude::*;
+use mythic_metadata::constants::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+use mythic_metadata::utils::*;
+
+const ROOT: u64 = 1;
+const SOCIALS: u64 = 2;
+const TWITTER: u64 = 3;
+const HANDLE: u64 = 4;
+const TREASURY: u64 = 5;
+
+fn metadata(update_authority: Pubkey) -> Metadata {
+    Metadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: Some(update_authority),
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    }
+}
+
+fn collection(
+    parent_path: &[u64],
+    metadata_key_id: u64,
+    update_authority: Option<Pubkey>,
+) -> MetadataCollection {
+    MetadataCollection {
+        metadata_key_id,
+        parent_path: parent_path.to_vec(),
+        update_slot: 0,
+        update_authority,
+        timelock_slots: 0,
+        frozen: false,
+        items: vec![],
+        rent_payer: Pubkey::default(),
+    }
+}
+
+fn item(metadata_key_id: u64, value: &[u8]) -> MetadataItem {
+    MetadataItem {
+        metadata_key_id,
+        update_slot: 1,
+        value: value.to_vec(),
+        rent_payer: Pubkey::default(),
+        growth_rent_shares: vec![],
+    }
+}
+
+fn paths(metadata: &Metadata) -> Vec<Vec<u64>> {
+    metadata
+        .collections
+        .iter()
+        .map(|collection| collection.path().copied().collect())
+        .collect()
+}
+
+/// socials, socials/twitter and treasury with the socials update authority
+fn socials_metadata(update_authority: Pubkey, socials_update_authority: Pubkey) -> Metadata {
+    let mut metadata = metadata(update_authority);
+    metadata
+        .append_collection(collection(&[], TREASURY, None))
+        .unwrap();
+    metadata
+        .append_collection(collection(&[], SOCIALS, Some(socials_update_authority)))
+        .unwrap();
+    metadata
+        .append_collection(collection(&[SOCIALS], TWITTER, None))
+        .unwrap();
+    metadata
+}
+
+#[test]
+fn collections_are_addressed_by_path() {
+    let mut metadata = socials_metadata(Pubkey::new_unique(), Pubkey::new_unique());
+
+    // Nested collections follow their parent
+    assert_eq!(
+        paths(&metadata),
+        vec![vec![SOCIALS], vec![SOCIALS, TWITTER], vec![TREASURY]]
+    );
+
+    // The same MetadataKey can be used at different paths
+    metadata
+        .append_collection(collection(&[TREASURY], TWITTER, None))
+        .unwrap();
+    assert_eq!(
+        metadata.append_collection(collection(&[SOCIALS], TWITTER, None)),
+        Err(MythicMetadataError::MetadataCollectionAlreadyExists.into())
+    );
+    assert_eq!(
+        metadata.append_collection(collection(&[HANDLE], TWITTER, None)),
+        Err(MythicMetadataError::MetadataCollectionNonExistent.into())
+    );
+
+    metadata
+        .append_item(&[SOCIALS, TWITTER], item(HANDLE, b"@mythic"))
+        .unwrap();
+    metadata
+        .set_item(&[SOCIALS, TWITTER], item(HANDLE, b"@mythic_dao"))
+        .unwrap();
+    metadata
+        .append_item(&[TREASURY, TWITTER], item(HANDLE, b"@treasury"))
+        .unwrap();
+
+    let socials_twitter =
+        &metadata.collections[metadata.collection_index(&[SOCIALS, TWITTER]).unwrap()];
+    assert_eq!(socials_twitter.items[0].value, b"@mythic_dao");
+    assert_eq!(socials_twitter.update_slot, 1);
+    assert!(
+        metadata.collections[metadata.collection_index(&[SOCIALS]).unwrap()]
+            .items
+            .is_empty()
+    );
+    assert_eq!(
+        metadata.append_item(&[TWITTER], item(HANDLE, b"@mythic")),
+        Err(MythicMetadataError::MetadataCollectionNonExistent.into())
+    );
+
+    // The size accounts for the parent paths, unset authorities and the finalized slot are reserved as well
+    let data = metadata.try_to_vec().unwrap();
+    let unset_authorities = metadata
+        .collections
+        .iter()
+        .filter(|collection| collection.update_authority.is_none())
+        .count();
+    assert_eq!(
+        Metadata::size(&metadata.items, &metadata.collections),
+        8 + data.len() + 32 * unset_authorities + 8 + 4
+    );
+}
+
+#[test]
+fn nested_collections_are_removed_with_their_parent() {
+    let mut metadata = socials_metadata(Pubkey::new_unique(), Pubkey::new_unique());
+    metadata
+        .append_collection(collection(&[SOCIALS, TWITTER], HANDLE, None))
+        .unwrap();
+
+    let removed = metadata.remove_collection(&[SOCIALS]).unwrap();
+    assert_eq!(
+        removed
+            .iter()
+            .map(|collection| collection.path().copied().collect())
+            .collect::<Vec<Vec<u64>>>(),
+        vec![
+            vec![SOCIALS],
+            vec![SOCIALS, TWITTER],
+            vec![SOCIALS, TWITTER, HANDLE]
+        ]
+    );
+    assert_eq!(paths(&metadata), vec![vec![TREASURY]]);
+
+    let mut metadata = socials_metadata(Pubkey::new_unique(), Pubkey::new_unique());
+    metadata.remove_collection(&[SOCIALS, TWITTER]).unwrap();
+    assert_eq!(paths(&metadata), vec![vec![SOCIALS], vec![TREASURY]]);
+}
+
+#[test]
+fn update_authorities_are_inherited_from_the_parents() {
+    let update_authority = Pubkey::new_unique();
+    let socials_update_authority = Pubkey::new_unique();
+    let twitter_update_authority = Pubkey::new_unique();
+    let mut metadata = socials_metadata(update_authority, socials_update_authority);
+    metadata
+        .set_update_authority(&[SOCIALS, TWITTER], Some(twitter_update_authority))
+        .unwrap();
+
+    for signer in [
+        update_authority,
+        socials_update_authority,
+        twitter_update_authority,
+    ] {
+        assert!(
+            verify_item_update_authority(&metadata, &[SOCIALS, TWITTER], &signer, None).is_ok()
+        );
+    }
+
+    // Authorities aren't inherited from the children or siblings
+    assert_eq!(
+        verify_item_update_authority(&metadata, &[SOCIALS], &twitter_update_authority, None),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+    assert_eq!(
+        verify_item_update_authority(&metadata, &[TREASURY], &socials_update_authority, None),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+}
+
+#[test]
+fn root_items_require_the_metadata_update_authority() {
+    let update_authority = Pubkey::new_unique();
+    let socials_update_authority = Pubkey::new_unique();
+    let mut metadata = socials_metadata(update_authority, socials_update_authority);
+
+    assert!(verify_item_update_authority(&metadata, &[ROOT], &update_authority, None).is_ok());
+
+    // Neither collection update authorities nor other signers update the root items
+    for signer in [socials_update_authority, Pubkey::new_unique()] {
+        assert_eq!(
+            verify_item_update_authority(&metadata, &[ROOT], &signer, None),
+            Err(MythicMetadataError::Unauthorized.into())
+        );
+    }
+
+    // A revoked update authority falls back to the issuing authority
+    metadata.update_authority = None;
+    assert_eq!(
+        verify_item_update_authority(&metadata, &[ROOT], &update_authority, None),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+    let issuing_authority = metadata.issuing_authority;
+    assert!(verify_item_update_authority(&metadata, &[ROOT], &issuing_authority, None).is_ok());
+}
+
+#[test]
+fn delegates_are_scoped_to_nested_collections() {
+    let metadata = socials_metadata(Pubkey::new_unique(), Pubkey::new_unique());
+    let delegate = Pubkey::new_unique();
+    let delegate_record = MetadataDelegate {
+        metadata: Pubkey::new_unique(),
+        collection_path: vec![SOCIALS],
+        delegate,
+        permissions: MetadataDelegate::APPEND,
+        bump: 255,
+    };
+
+    for collection_path in [&[SOCIALS][..], &[SOCIALS, TWITTER]] {
+        assert!(verify_issuing_authority(
+            &metadata,
+            &delegate,
+            Some(&delegate_record),
+            collection_path,
+            MetadataDelegate::APPEND
+        )
+        .is_ok());
+    }
+    assert_eq!(
+        verify_issuing_authority(
+            &metadata,
+            &delegate,
+            Some(&delegate_record),
+            &[TREASURY],
+            MetadataDelegate::APPEND
+        ),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+
+    // The scope is the collection path, not any collection with the same MetadataKey Id
+    let delegate_record = MetadataDelegate {
+        collection_path: vec![SOCIALS, TWITTER],
+        ..delegate_record
+    };
+    assert!(delegate_record.is_granted(&metadata, &[SOCIALS, TWITTER], MetadataDelegate::APPEND));
+    for collection_path in [&[SOCIALS][..], &[TREASURY, TWITTER], &[TWITTER]] {
+        assert!(!delegate_record.is_granted(&metadata, collection_path, MetadataDelegate::APPEND));
+    }
+}
+
+#[test]
+fn collection_depth_is_limited() {
+    let mut metadata = metadata(Pubkey::new_unique());
+    let mut parent_path = vec![];
+    for depth in 0..MAX_COLLECTION_DEPTH as u64 {
+        metadata
+            .append_collection(collection(&parent_path, 10 + depth, None))
+            .unwrap();
+        parent_path.push(10 + depth);
+    }
+    metadata.validate().unwrap();
+
+    metadata
+        .append_collection(collection(&parent_path, 100, None))
+        .unwrap();
+    assert_eq!(
+        metadata.validate(),
+        Err(MythicMetadataError::MetadataCollectionDepthExceeded.into())
+    );
+}
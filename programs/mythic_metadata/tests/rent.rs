@@ -0,0 +1,226 @@
+//! Rent shares attribute the bytes of a Metadata account to the rent payers which funded them
+
+use anchor_lang::prelude::*;
+use mythic_metadata::state::*;
+
+const ROOT: u64 = 1;
+const SOCIALS: u64 = 2;
+const TWITTER: u64 = 3;
+const HANDLE: u64 = 4;
+const LINKS: u64 = 5;
+
+fn item(metadata_key_id: u64, value: &[u8], rent_payer: Pubkey) -> MetadataItem {
+    MetadataItem {
+        metadata_key_id,
+        update_slot: 1,
+        value: value.to_vec(),
+        rent_payer,
+        growth_rent_shares: vec![],
+    }
+}
+
+fn collection(parent_path: &[u64], metadata_key_id: u64, rent_payer: Pubkey) -> MetadataCollection {
+    MetadataCollection {
+        metadata_key_id,
+        parent_path: parent_path.to_vec(),
+        update_slot: 0,
+        update_authority: None,
+        timelock_slots: 0,
+        frozen: false,
+        items: vec![],
+        rent_payer,
+    }
+}
+
+fn metadata() -> Metadata {
+    Metadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: None,
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    }
+}
+
+fn rent_payers(rent_shares: impl Iterator<Item = RentShare>) -> Vec<Pubkey> {
+    rent_shares
+        .map(|rent_share| rent_share.rent_payer)
+        .collect()
+}
+
+#[test]
+fn rent_shares_cover_the_items_and_collections() {
+    let issuer = Pubkey::new_unique();
+    let sponsor = Pubkey::new_unique();
+    let mut metadata = metadata();
+    metadata
+        .append_item(&[ROOT], item(HANDLE, b"mythic", issuer))
+        .unwrap();
+    metadata
+        .append_collection(collection(&[], SOCIALS, sponsor))
+        .unwrap();
+    metadata
+        .append_collection(collection(&[SOCIALS], TWITTER, issuer))
+        .unwrap();
+    metadata
+        .append_item(&[SOCIALS, TWITTER], item(HANDLE, b"@mythic", sponsor))
+        .unwrap();
+
+    assert_eq!(
+        rent_payers(metadata.rent_shares()),
+        vec![issuer, sponsor, issuer, sponsor]
+    );
+
+    // Only the fixed fields of the Metadata aren't attributed to a rent payer
+    assert_eq!(
+        Metadata::size(&[], &[])
+            + metadata
+                .rent_shares()
+                .map(|rent_share| rent_share.size)
+                .sum::<usize>(),
+        Metadata::size(&metadata.items, &metadata.collections)
+    );
+
+    // The nested collections are removed together with their rent shares
+    let removed_collections = metadata.remove_collection(&[SOCIALS]).unwrap();
+    assert_eq!(
+        rent_payers(
+            removed_collections
+                .iter()
+                .flat_map(MetadataCollection::rent_shares)
+        ),
+        vec![sponsor, issuer, sponsor]
+    );
+    assert_eq!(rent_payers(metadata.rent_shares()), vec![issuer]);
+}
+
+#[test]
+fn growth_is_funded_and_refunded_by_its_payers() {
+    let sponsor = Pubkey::new_unique();
+    let update_authority = Pubkey::new_unique();
+    let relayer = Pubkey::new_unique();
+    let mut metadata = metadata();
+    metadata
+        .append_item(&[ROOT], item(HANDLE, b"mythic", sponsor))
+        .unwrap();
+    let appended_size = metadata.items[0].size();
+
+    // The rent payer funds its own growth
+    assert!(metadata
+        .update_item(&[ROOT], item(HANDLE, b"mythic dao", sponsor))
+        .unwrap()
+        .is_empty());
+    assert_eq!(rent_payers(metadata.rent_shares()), vec![sponsor]);
+
+    // Other payers fund their growth and the share entry tracking it, each growth is stacked on
+    // the latest one and consecutive growths of the same payer share an entry
+    for (value, rent_payer) in [
+        (&b"mythic dao!"[..], update_authority),
+        (b"mythic dao!!", update_authority),
+        (b"mythic dao!!!!!", relayer),
+        (b"mythic dao!!!!!!", update_authority),
+    ] {
+        assert!(metadata
+            .update_item(&[ROOT], item(HANDLE, value, rent_payer))
+            .unwrap()
+            .is_empty());
+    }
+    assert_eq!(
+        metadata.items[0].growth_rent_shares,
+        vec![
+            GrowthRentShare {
+                rent_payer: update_authority,
+                size: 2 + GrowthRentShare::SIZE as u32,
+            },
+            GrowthRentShare {
+                rent_payer: relayer,
+                size: 3 + GrowthRentShare::SIZE as u32,
+            },
+            GrowthRentShare {
+                rent_payer: update_authority,
+                size: 1 + GrowthRentShare::SIZE as u32,
+            },
+        ]
+    );
+    assert_eq!(
+        metadata
+            .rent_shares()
+            .map(|rent_share| rent_share.size)
+            .sum::<usize>(),
+        metadata.items[0].size()
+    );
+
+    // Shrinks refund the latest growth first, the share entries are refunded with it
+    assert_eq!(
+        metadata
+            .update_item(&[ROOT], item(HANDLE, b"mythic dao!!!!", sponsor))
+            .unwrap(),
+        vec![
+            RentShare {
+                rent_payer: update_authority,
+                size: 1 + GrowthRentShare::SIZE,
+            },
+            RentShare {
+                rent_payer: relayer,
+                size: 1,
+            },
+        ]
+    );
+
+    // Once another payer grew the value, the rent payer's growth is stacked as well
+    assert!(metadata
+        .update_item(&[ROOT], item(HANDLE, b"mythic dao!!!!!", sponsor))
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        metadata
+            .update_item(&[ROOT], item(HANDLE, b"mythic", sponsor))
+            .unwrap(),
+        vec![
+            RentShare {
+                rent_payer: sponsor,
+                size: 1 + GrowthRentShare::SIZE,
+            },
+            RentShare {
+                rent_payer: relayer,
+                size: 2 + GrowthRentShare::SIZE,
+            },
+            RentShare {
+                rent_payer: update_authority,
+                size: 2 + GrowthRentShare::SIZE,
+            },
+            RentShare {
+                rent_payer: sponsor,
+                size: 4,
+            },
+        ]
+    );
+    assert!(metadata.items[0].growth_rent_shares.is_empty());
+    assert_eq!(metadata.items[0].size(), appended_size);
+
+    // List changes are funded by their payer and shrinks are refunded
+    metadata
+        .push_list_value(&[ROOT], LINKS, b"https://mythic.dao".to_vec(), 1, sponsor)
+        .unwrap();
+    metadata
+        .push_list_value(&[ROOT], LINKS, b"https://x.com".to_vec(), 2, relayer)
+        .unwrap();
+    let rent_shares = metadata.clear_list(&[ROOT], LINKS, 3).unwrap();
+    assert_eq!(rent_payers(rent_shares.into_iter()), vec![relayer, sponsor]);
+
+    let removed = metadata.remove_item(&[ROOT], HANDLE).unwrap();
+    assert_eq!(
+        removed.rent_shares().collect::<Vec<_>>(),
+        vec![RentShare {
+            rent_payer: sponsor,
+            size: appended_size,
+        }]
+    );
+}
@@ -0,0 +1,197 @@
+//! Moving items between collections and rekeying collections
+
+use anchor_lang::prelude::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+
+const ROOT: u64 = 1;
+const SOCIALS: u64 = 2;
+const TWITTER: u64 = 3;
+const HANDLE: u64 = 4;
+const TREASURY: u64 = 5;
+const PROFILES: u64 = 6;
+
+fn collection(parent_path: &[u64], metadata_key_id: u64) -> MetadataCollection {
+    MetadataCollection {
+        metadata_key_id,
+        parent_path: parent_path.to_vec(),
+        update_slot: 0,
+        update_authority: None,
+        timelock_slots: 0,
+        frozen: false,
+        items: vec![],
+        rent_payer: Pubkey::default(),
+    }
+}
+
+/// treasury, socials and socials/twitter holding a handle item
+fn metadata() -> Metadata {
+    let mut metadata = Metadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: None,
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    };
+    for (parent_path, metadata_key_id) in
+        [(&[][..], TREASURY), (&[], SOCIALS), (&[SOCIALS], TWITTER)]
+    {
+        metadata
+            .append_collection(collection(parent_path, metadata_key_id))
+            .unwrap();
+    }
+    metadata
+        .append_item(
+            &[SOCIALS, TWITTER],
+            MetadataItem {
+                metadata_key_id: HANDLE,
+                update_slot: 1,
+                value: b"@mythic".to_vec(),
+                rent_payer: Pubkey::default(),
+                growth_rent_shares: vec![],
+            },
+        )
+        .unwrap();
+    metadata
+}
+
+fn paths(metadata: &Metadata) -> Vec<Vec<u64>> {
+    metadata
+        .collections
+        .iter()
+        .map(|collection| collection.path().copied().collect())
+        .collect()
+}
+
+#[test]
+fn items_move_between_collections() {
+    let mut metadata = metadata();
+
+    metadata
+        .move_item(&[SOCIALS, TWITTER], &[TREASURY], HANDLE, 5)
+        .unwrap();
+    metadata.validate().unwrap();
+
+    let twitter = &metadata.collections[metadata.collection_index(&[SOCIALS, TWITTER]).unwrap()];
+    assert!(twitter.items.is_empty());
+    assert_eq!(twitter.update_slot, 5);
+
+    let treasury = &metadata.collections[metadata.collection_index(&[TREASURY]).unwrap()];
+    assert_eq!(treasury.update_slot, 5);
+    assert_eq!(treasury.items[0].metadata_key_id, HANDLE);
+    assert_eq!(treasury.items[0].update_slot, 5);
+    assert_eq!(treasury.items[0].value, b"@mythic");
+
+    // Items can move to and from the root items
+    metadata.move_item(&[TREASURY], &[ROOT], HANDLE, 6).unwrap();
+    assert_eq!(metadata.items[0].metadata_key_id, HANDLE);
+    assert_eq!(metadata.update_slot, 6);
+
+    assert_eq!(
+        metadata.move_item(&[TREASURY], &[ROOT], HANDLE, 7),
+        Err(MythicMetadataError::MetadataItemNonExistent.into())
+    );
+    assert_eq!(
+        metadata.move_item(&[ROOT], &[PROFILES], HANDLE, 7),
+        Err(MythicMetadataError::MetadataCollectionNonExistent.into())
+    );
+}
+
+#[test]
+fn moving_onto_an_existing_item_fails() {
+    let mut metadata = metadata();
+    metadata
+        .append_item(
+            &[TREASURY],
+            MetadataItem {
+                metadata_key_id: HANDLE,
+                update_slot: 1,
+                value: b"@treasury".to_vec(),
+                rent_payer: Pubkey::default(),
+                growth_rent_shares: vec![],
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        metadata.move_item(&[SOCIALS, TWITTER], &[TREASURY], HANDLE, 5),
+        Err(MythicMetadataError::MetadataItemAlreadyExists.into())
+    );
+}
+
+#[test]
+fn rekeyed_collections_keep_their_nested_collections() {
+    let mut metadata = metadata();
+
+    metadata.rekey_collection(&[SOCIALS], PROFILES, 5).unwrap();
+    metadata.validate().unwrap();
+
+    // The rekeyed collection and its nested collections are sorted by their new paths
+    assert_eq!(
+        paths(&metadata),
+        vec![vec![TREASURY], vec![PROFILES], vec![PROFILES, TWITTER]]
+    );
+    let profiles = &metadata.collections[metadata.collection_index(&[PROFILES]).unwrap()];
+    assert_eq!(profiles.update_slot, 5);
+    let twitter = &metadata.collections[metadata.collection_index(&[PROFILES, TWITTER]).unwrap()];
+    assert_eq!(twitter.items[0].value, b"@mythic");
+
+    // Nested collections are rekeyed within their parent
+    metadata
+        .rekey_collection(&[PROFILES, TWITTER], SOCIALS, 6)
+        .unwrap();
+    assert_eq!(
+        paths(&metadata),
+        vec![vec![TREASURY], vec![PROFILES], vec![PROFILES, SOCIALS]]
+    );
+}
+
+#[test]
+fn rekeying_onto_an_existing_collection_fails() {
+    let mut metadata = metadata();
+
+    assert_eq!(
+        metadata.rekey_collection(&[SOCIALS], TREASURY, 5),
+        Err(MythicMetadataError::MetadataCollectionAlreadyExists.into())
+    );
+    assert_eq!(
+        metadata.rekey_collection(&[SOCIALS], ROOT, 5),
+        Err(MythicMetadataError::InvalidMetadataKey.into())
+    );
+    assert_eq!(
+        metadata.rekey_collection(&[PROFILES], HANDLE, 5),
+        Err(MythicMetadataError::MetadataCollectionNonExistent.into())
+    );
+}
+
+#[test]
+fn rekeying_requires_the_records_to_be_closed() {
+    let mut metadata = metadata();
+    assert!(metadata.verify_records_closed(0).is_ok());
+
+    // A delegate record scoped to the collection would be left keyed by its previous path
+    metadata.open_record();
+    assert_eq!(
+        metadata.verify_records_closed(0),
+        Err(MythicMetadataError::MetadataRecordsOpen.into())
+    );
+    // Unless it's the delegate record authorizing the rekey
+    assert!(metadata.verify_records_closed(1).is_ok());
+
+    metadata.open_record();
+    assert_eq!(
+        metadata.verify_records_closed(1),
+        Err(MythicMetadataError::MetadataRecordsOpen.into())
+    );
+
+    metadata.close_record();
+    metadata.close_record();
+    assert!(metadata.verify_records_closed(0).is_ok());
+}
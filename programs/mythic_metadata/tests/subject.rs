@@ -0,0 +1,137 @@
+//! Verification of the subject authority against hand serialized mint and ProgramData accounts
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use mythic_metadata::constants::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::utils::verify_subject_authority;
+
+struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+impl TestAccount {
+    fn info(&mut self) -> AccountInfo {
+        AccountInfo::new(
+            &self.key,
+            false,
+            false,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+}
+
+fn mint(owner: Pubkey, mint_authority: Option<Pubkey>) -> TestAccount {
+    let mut data = match mint_authority {
+        Some(mint_authority) => [&[1, 0, 0, 0], mint_authority.as_ref()].concat(),
+        None => vec![0; 36],
+    };
+    data.extend(1_000u64.to_le_bytes()); // supply
+    data.extend([6, 1, 0, 0, 0, 0]); // decimals, is_initialized, freeze_authority tag
+
+    TestAccount {
+        key: Pubkey::new_unique(),
+        owner,
+        lamports: 0,
+        data,
+    }
+}
+
+fn program_data(program: &Pubkey, upgrade_authority: Option<Pubkey>) -> TestAccount {
+    let mut data = vec![3, 0, 0, 0];
+    data.extend(42u64.to_le_bytes()); // slot
+    data.extend(upgrade_authority.try_to_vec().unwrap());
+    data.extend([0; 16]); // program bytes
+
+    TestAccount {
+        key: Pubkey::find_program_address(&[program.as_ref()], &bpf_loader_upgradeable::ID).0,
+        owner: bpf_loader_upgradeable::ID,
+        lamports: 0,
+        data,
+    }
+}
+
+#[test]
+fn subject_can_sign_for_itself() {
+    let subject = Pubkey::new_unique();
+
+    assert!(verify_subject_authority(&subject, &subject, None).is_ok());
+    assert_eq!(
+        verify_subject_authority(&subject, &Pubkey::new_unique(), None),
+        Err(MythicMetadataError::InvalidSubjectAccount.into())
+    );
+}
+
+#[test]
+fn mint_authority_verifies_the_mint() {
+    let mint_authority = Pubkey::new_unique();
+
+    for token_program in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+        let mut subject = mint(token_program, Some(mint_authority));
+        let key = subject.key;
+
+        assert!(verify_subject_authority(&key, &mint_authority, Some(&subject.info())).is_ok());
+        assert_eq!(
+            verify_subject_authority(&key, &Pubkey::new_unique(), Some(&subject.info())),
+            Err(MythicMetadataError::Unauthorized.into())
+        );
+        // The mint must be the subject
+        assert_eq!(
+            verify_subject_authority(
+                &Pubkey::new_unique(),
+                &mint_authority,
+                Some(&subject.info())
+            ),
+            Err(MythicMetadataError::InvalidSubjectAccount.into())
+        );
+    }
+
+    let mut fixed_supply = mint(TOKEN_PROGRAM_ID, None);
+    let key = fixed_supply.key;
+    assert_eq!(
+        verify_subject_authority(&key, &mint_authority, Some(&fixed_supply.info())),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+
+    let mut foreign = mint(Pubkey::new_unique(), Some(mint_authority));
+    let key = foreign.key;
+    assert_eq!(
+        verify_subject_authority(&key, &mint_authority, Some(&foreign.info())),
+        Err(MythicMetadataError::InvalidSubjectAccount.into())
+    );
+}
+
+#[test]
+fn upgrade_authority_verifies_the_program() {
+    let program = Pubkey::new_unique();
+    let upgrade_authority = Pubkey::new_unique();
+    let mut subject = program_data(&program, Some(upgrade_authority));
+
+    assert!(verify_subject_authority(&program, &upgrade_authority, Some(&subject.info())).is_ok());
+    assert_eq!(
+        verify_subject_authority(&program, &Pubkey::new_unique(), Some(&subject.info())),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+    // The ProgramData of another program
+    assert_eq!(
+        verify_subject_authority(
+            &Pubkey::new_unique(),
+            &upgrade_authority,
+            Some(&subject.info())
+        ),
+        Err(MythicMetadataError::InvalidSubjectAccount.into())
+    );
+
+    let mut immutable = program_data(&program, None);
+    assert_eq!(
+        verify_subject_authority(&program, &upgrade_authority, Some(&immutable.info())),
+        Err(MythicMetadataError::Unauthorized.into())
+    );
+}
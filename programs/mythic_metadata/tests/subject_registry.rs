@@ -0,0 +1,60 @@
+//! The per subject registry of Metadata accounts
+
+use anchor_lang::prelude::*;
+use mythic_metadata::constants::*;
+use mythic_metadata::state::*;
+
+fn entry(metadata_key_id: u64, issuing_authority: Pubkey) -> SubjectRegistryEntry {
+    SubjectRegistryEntry {
+        metadata_key_id,
+        issuing_authority,
+    }
+}
+
+#[test]
+fn registry_pages_never_fill_up() {
+    let mut registry = SubjectRegistry {
+        subject: Pubkey::new_unique(),
+        registered_count: 0,
+        bump: 255,
+    };
+    assert_eq!(registry.next_page_index(), 0);
+    assert_eq!(registry.page_count(), 0);
+
+    registry.registered_count = SUBJECT_REGISTRY_PAGE_LEN as u64 - 1;
+    assert_eq!(registry.next_page_index(), 0);
+    assert_eq!(registry.page_count(), 1);
+
+    registry.registered_count += 1;
+    assert_eq!(registry.next_page_index(), 1);
+    assert_eq!(registry.page_count(), 1);
+
+    registry.registered_count = u32::MAX as u64 * SUBJECT_REGISTRY_PAGE_LEN as u64;
+    assert_eq!(registry.next_page_index(), u32::MAX);
+
+    assert_eq!(
+        registry.try_to_vec().unwrap().len() + 8,
+        SubjectRegistry::size()
+    );
+}
+
+#[test]
+fn closed_metadata_are_removed_from_their_page() {
+    let issuer = Pubkey::new_unique();
+    let other_issuer = Pubkey::new_unique();
+    let mut page = SubjectRegistryPage {
+        subject: Pubkey::new_unique(),
+        page_index: 0,
+        entries: vec![entry(2, issuer), entry(1, issuer), entry(2, other_issuer)],
+        bump: 255,
+    };
+    assert_eq!(
+        page.try_to_vec().unwrap().len() + 8,
+        SubjectRegistryPage::size(3)
+    );
+    assert!(SubjectRegistryPage::size(SUBJECT_REGISTRY_PAGE_LEN) <= 10240);
+
+    page.remove(&entry(2, issuer));
+    page.remove(&entry(3, issuer));
+    assert_eq!(page.entries, vec![entry(1, issuer), entry(2, other_issuer)]);
+}
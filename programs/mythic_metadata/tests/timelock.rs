@@ -0,0 +1,133 @@
+//! Timelocked collections and the PendingChanges queued for them
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::*;
+use mythic_metadata::utils::*;
+
+const ROOT: u64 = 1;
+const TREASURY: u64 = 2;
+const SOLANA: u64 = 3;
+const SOCIALS: u64 = 4;
+
+fn collection(parent_path: &[u64], metadata_key_id: u64) -> MetadataCollection {
+    MetadataCollection {
+        metadata_key_id,
+        parent_path: parent_path.to_vec(),
+        update_slot: 0,
+        update_authority: None,
+        timelock_slots: 0,
+        frozen: false,
+        items: vec![],
+        rent_payer: Pubkey::default(),
+    }
+}
+
+fn metadata() -> Metadata {
+    let mut metadata = Metadata {
+        subject: Pubkey::new_unique(),
+        metadata_key_id: ROOT,
+        issuing_authority: Pubkey::new_unique(),
+        update_slot: 0,
+        update_authority: Some(Pubkey::new_unique()),
+        items: vec![],
+        collections: vec![],
+        bump: 255,
+        verified_subject: false,
+        finalized_slot: None,
+        registry_page_index: None,
+        records_count: 0,
+    };
+    for (parent_path, metadata_key_id) in
+        [(&[][..], TREASURY), (&[TREASURY], SOLANA), (&[], SOCIALS)]
+    {
+        metadata
+            .append_collection(collection(parent_path, metadata_key_id))
+            .unwrap();
+    }
+    metadata
+}
+
+#[test]
+fn nested_collections_inherit_the_longest_timelock() {
+    let mut metadata = metadata();
+    metadata.set_timelock(&[TREASURY], 100).unwrap();
+
+    assert_eq!(metadata.timelock_slots(&[TREASURY]).unwrap(), 100);
+    assert_eq!(metadata.timelock_slots(&[TREASURY, SOLANA]).unwrap(), 100);
+    assert_eq!(metadata.timelock_slots(&[SOCIALS]).unwrap(), 0);
+    assert_eq!(metadata.timelock_slots(&[ROOT]).unwrap(), 0);
+
+    metadata.set_timelock(&[TREASURY, SOLANA], 50).unwrap();
+    assert_eq!(metadata.timelock_slots(&[TREASURY, SOLANA]).unwrap(), 100);
+    metadata.set_timelock(&[TREASURY, SOLANA], 500).unwrap();
+    assert_eq!(metadata.timelock_slots(&[TREASURY, SOLANA]).unwrap(), 500);
+
+    assert_eq!(
+        metadata.set_timelock(&[ROOT], 100),
+        Err(MythicMetadataError::MetadataCollectionNonExistent.into())
+    );
+}
+
+#[test]
+fn timelocked_items_must_be_queued() {
+    let mut metadata = metadata();
+    metadata.set_timelock(&[TREASURY], 100).unwrap();
+
+    for collection_path in [&[TREASURY][..], &[TREASURY, SOLANA]] {
+        assert_eq!(
+            verify_not_timelocked(&metadata, collection_path),
+            Err(MythicMetadataError::MetadataCollectionTimelocked.into())
+        );
+    }
+    assert_eq!(verify_not_timelocked(&metadata, &[SOCIALS]), Ok(()));
+    assert_eq!(verify_not_timelocked(&metadata, &[ROOT]), Ok(()));
+}
+
+#[test]
+fn collections_with_timelocked_nested_collections_cant_be_restructured() {
+    let mut metadata = metadata();
+    metadata.set_timelock(&[TREASURY, SOLANA], 100).unwrap();
+
+    let timelocked = || Err(MythicMetadataError::MetadataCollectionTimelocked.into());
+    for collection_path in [&[TREASURY][..], &[TREASURY, SOLANA]] {
+        assert_eq!(
+            metadata.rekey_collection(collection_path, SOCIALS + 1, 2),
+            timelocked()
+        );
+        assert_eq!(
+            metadata.remove_collection(collection_path).err(),
+            timelocked().err()
+        );
+    }
+
+    metadata.set_timelock(&[TREASURY, SOLANA], 0).unwrap();
+    metadata.remove_collection(&[TREASURY]).unwrap();
+}
+
+#[test]
+fn pending_change_size() {
+    for change in [
+        MetadataChange::Update {
+            new_value: b"So11111111111111111111111111111111111111112".to_vec(),
+        },
+        MetadataChange::Remove,
+    ] {
+        let pending_change = PendingChange {
+            metadata: Pubkey::new_unique(),
+            collection_path: vec![TREASURY, SOLANA],
+            item_metadata_key_id: 5,
+            change: change.clone(),
+            payer: Pubkey::new_unique(),
+            prepaid_lamports: 1_000,
+            executable_slot: 100,
+            bump: 255,
+        };
+
+        assert_eq!(
+            PendingChange::DISCRIMINATOR.len() + pending_change.try_to_vec().unwrap().len(),
+            PendingChange::size(2, &change)
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! The spl-token-metadata-interface adapter: instruction encoding and the mapping to Metadata
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use mythic_metadata::constants::*;
+use mythic_metadata::errors::MythicMetadataError;
+use mythic_metadata::state::{Metadata, RentShare};
+use mythic_metadata::token_metadata::*;
+
+const RENT_PAYER: Pubkey = Pubkey::new_from_array([7; 32]);
+
+fn initialize() -> Initialize {
+    Initialize {
+        name: "Mythic".to_string(),
+        symbol: "MYTH".to_string(),
+        uri: "https://example.com/myth.json".to_string(),
+    }
+}
+
+fn token_metadata(update_authority: Option<Pubkey>) -> (Pubkey, Metadata) {
+    let mint = Pubkey::new_unique();
+    let metadata =
+        new_token_metadata(mint, update_authority, initialize(), 1, 255, RENT_PAYER).unwrap();
+    (mint, metadata)
+}
+
+fn field(key: &str, value: &str) -> UpdateField {
+    UpdateField {
+        field: Field::Key(key.to_string()),
+        value: value.to_string(),
+    }
+}
+
+#[test]
+fn instructions_round_trip() {
+    let instructions = [
+        TokenMetadataInstruction::Initialize(initialize()),
+        TokenMetadataInstruction::UpdateField(field("color", "blue")),
+        TokenMetadataInstruction::UpdateField(UpdateField {
+            field: Field::Uri,
+            value: "https://example.com".to_string(),
+        }),
+        TokenMetadataInstruction::RemoveKey(RemoveKey {
+            idempotent: true,
+            key: "color".to_string(),
+        }),
+        TokenMetadataInstruction::UpdateAuthority(UpdateAuthority {
+            new_authority: Pubkey::new_unique(),
+        }),
+        TokenMetadataInstruction::Emit(Emit {
+            start: Some(1),
+            end: None,
+        }),
+    ];
+
+    for instruction in instructions {
+        let data = instruction.pack();
+        assert_eq!(
+            TokenMetadataInstruction::unpack(&data).unwrap().unwrap(),
+            instruction
+        );
+    }
+
+    // Field::Key is the fourth variant and strings are length prefixed
+    assert_eq!(
+        TokenMetadataInstruction::UpdateField(field("a", "b")).pack(),
+        [
+            TokenMetadataInstruction::UPDATE_FIELD.as_ref(),
+            &[3, 1, 0, 0, 0, b'a', 1, 0, 0, 0, b'b']
+        ]
+        .concat()
+    );
+
+    // Anchor instructions and short data aren't interface instructions
+    let anchor_data = [
+        mythic_metadata::instruction::CreateMetadata::DISCRIMINATOR,
+        [0; 8],
+    ]
+    .concat();
+    assert!(TokenMetadataInstruction::unpack(&anchor_data).is_none());
+    assert!(TokenMetadataInstruction::unpack(&[1, 2, 3]).is_none());
+    assert!(
+        TokenMetadataInstruction::unpack(&TokenMetadataInstruction::EMIT)
+            .unwrap()
+            .is_err()
+    );
+}
+
+#[test]
+fn initialized_metadata_maps_to_token_metadata() {
+    let update_authority = Pubkey::new_unique();
+    let (mint, metadata) = token_metadata(Some(update_authority));
+
+    assert_eq!(metadata.metadata_key_id, TOKEN_METADATA_KEY_ID);
+    assert_eq!(metadata.issuing_authority, mint);
+    assert!(metadata.verified_subject);
+    assert_eq!(
+        TokenMetadata::from_metadata(&metadata).unwrap(),
+        TokenMetadata {
+            update_authority,
+            mint,
+            name: "Mythic".to_string(),
+            symbol: "MYTH".to_string(),
+            uri: "https://example.com/myth.json".to_string(),
+            additional_metadata: vec![],
+        }
+    );
+
+    let (_, immutable) = token_metadata(None);
+    assert!(immutable.finalized_slot.is_some());
+    assert_eq!(
+        TokenMetadata::from_metadata(&immutable)
+            .unwrap()
+            .update_authority,
+        Pubkey::default()
+    );
+}
+
+#[test]
+fn fields_are_updated_and_removed() {
+    let (_, mut metadata) = token_metadata(Some(Pubkey::new_unique()));
+
+    update_field(
+        &mut metadata,
+        UpdateField {
+            field: Field::Name,
+            value: "Mythic DAO".to_string(),
+        },
+        2,
+        Some(RENT_PAYER),
+    )
+    .unwrap();
+    update_field(&mut metadata, field("color", "blue"), 2, Some(RENT_PAYER)).unwrap();
+    update_field(&mut metadata, field("size", "large"), 3, Some(RENT_PAYER)).unwrap();
+    // The rent of the bytes a field shrinks by is refunded to its rent payer
+    assert_eq!(
+        update_field(
+            &mut metadata,
+            field("color", "red"),
+            4,
+            Some(Pubkey::new_unique())
+        )
+        .unwrap(),
+        vec![RentShare {
+            rent_payer: RENT_PAYER,
+            size: 1,
+        }]
+    );
+    // Without a rent payer fields can shrink or keep their size but not grow
+    assert_eq!(
+        update_field(&mut metadata.clone(), field("size", "largest"), 4, None),
+        Err(MythicMetadataError::RentPayerRequired.into())
+    );
+    assert_eq!(
+        update_field(&mut metadata.clone(), field("shape", "round"), 4, None),
+        Err(MythicMetadataError::RentPayerRequired.into())
+    );
+    update_field(&mut metadata, field("size", "small"), 4, None).unwrap();
+    update_field(&mut metadata, field("size", "large"), 4, Some(RENT_PAYER)).unwrap();
+    metadata.validate().unwrap();
+
+    let mut expected_fields = vec![
+        ("color".to_string(), "red".to_string()),
+        ("size".to_string(), "large".to_string()),
+    ];
+    expected_fields.sort_by_key(|(key, _)| field_item_id(key));
+
+    let token_metadata = TokenMetadata::from_metadata(&metadata).unwrap();
+    assert_eq!(token_metadata.name, "Mythic DAO");
+    assert_eq!(token_metadata.additional_metadata, expected_fields);
+    assert_eq!(metadata.update_slot, 2);
+    assert_eq!(metadata.collections[0].update_slot, 4);
+
+    let rent_shares = remove_key(
+        &mut metadata,
+        RemoveKey {
+            idempotent: false,
+            key: "color".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        rent_shares
+            .iter()
+            .map(|rent_share| rent_share.rent_payer)
+            .collect::<Vec<_>>(),
+        vec![RENT_PAYER]
+    );
+    assert_eq!(
+        TokenMetadata::from_metadata(&metadata)
+            .unwrap()
+            .additional_metadata,
+        vec![("size".to_string(), "large".to_string())]
+    );
+
+    assert!(remove_key(
+        &mut metadata,
+        RemoveKey {
+            idempotent: true,
+            key: "color".to_string(),
+        },
+    )
+    .is_ok());
+    assert_eq!(
+        remove_key(
+            &mut metadata,
+            RemoveKey {
+                idempotent: false,
+                key: "color".to_string(),
+            },
+        ),
+        Err(MythicMetadataError::MetadataItemNonExistent.into())
+    );
+}
+
+#[test]
+fn field_id_collisions_are_rejected() {
+    let (_, mut metadata) = token_metadata(Some(Pubkey::new_unique()));
+    update_field(&mut metadata, field("color", "blue"), 2, Some(RENT_PAYER)).unwrap();
+
+    // Simulate another key hashing to the same item id
+    let item = &mut metadata.collections[0].items[0];
+    item.value = ("colour".to_string(), "blue".to_string())
+        .try_to_vec()
+        .unwrap();
+
+    assert_eq!(
+        update_field(&mut metadata, field("color", "red"), 3, Some(RENT_PAYER)),
+        Err(MythicMetadataError::InvalidTokenMetadataField.into())
+    );
+}
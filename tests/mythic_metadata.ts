@@ -10,11 +10,16 @@ import {
   SystemProgram,
 } from "@solana/web3.js";
 import { expect } from "chai";
+import { createHash } from "crypto";
 import { MythicMetadata } from "../target/types/mythic_metadata";
 
 const PREFIX = new TextEncoder().encode("mythic_metadata");
 const METADATA_KEY = new TextEncoder().encode("metadata_key");
 const METADATA = new TextEncoder().encode("metadata");
+const DELEGATE = new TextEncoder().encode("delegate");
+const SUBJECT_REGISTRY = new TextEncoder().encode("subject_registry");
+const NAMESPACE_REGISTRY = new TextEncoder().encode("namespace_registry");
+const AUTHORITY_TRANSFER = new TextEncoder().encode("authority_transfer");
 
 function getMetadataKey(id: number, programId: PublicKey) {
   return PublicKey.findProgramAddressSync(
@@ -41,6 +46,101 @@ function getMetadata(
   );
 }
 
+function getPathSeed(collectionPath: number[]) {
+  return createHash("sha256")
+    .update(
+      Buffer.concat(
+        collectionPath.map((id) =>
+          new anchor.BN(id).toArrayLike(Buffer, "le", 8)
+        )
+      )
+    )
+    .digest();
+}
+
+function getMetadataDelegate(
+  metadata: PublicKey,
+  collectionPath: number[],
+  delegate: PublicKey,
+  programId: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      PREFIX,
+      DELEGATE,
+      metadata.toBuffer(),
+      getPathSeed(collectionPath),
+      delegate.toBuffer(),
+    ],
+    programId
+  );
+}
+
+function getSubjectRegistry(subject: PublicKey, programId: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [PREFIX, SUBJECT_REGISTRY, subject.toBuffer()],
+    programId
+  );
+}
+
+function getSubjectRegistryPage(
+  subject: PublicKey,
+  pageIndex: number,
+  programId: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      PREFIX,
+      SUBJECT_REGISTRY,
+      subject.toBuffer(),
+      new anchor.BN(pageIndex).toArrayLike(Buffer, "le", 4),
+    ],
+    programId
+  );
+}
+
+function getNamespaceRegistry(
+  namespaceAuthority: PublicKey,
+  programId: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [PREFIX, NAMESPACE_REGISTRY, namespaceAuthority.toBuffer()],
+    programId
+  );
+}
+
+function getNamespaceRegistryPage(
+  namespaceAuthority: PublicKey,
+  pageIndex: number,
+  programId: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      PREFIX,
+      NAMESPACE_REGISTRY,
+      namespaceAuthority.toBuffer(),
+      new anchor.BN(pageIndex).toArrayLike(Buffer, "le", 4),
+    ],
+    programId
+  );
+}
+
+function getAuthorityTransfer(
+  metadata: PublicKey,
+  collectionPath: number[],
+  programId: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      PREFIX,
+      AUTHORITY_TRANSFER,
+      metadata.toBuffer(),
+      getPathSeed(collectionPath),
+    ],
+    programId
+  );
+}
+
 async function airdrop(connection: Connection, to: PublicKey, amount: number) {
   await connection.confirmTransaction({
     ...(await connection.getLatestBlockhash("confirmed")),
@@ -77,6 +177,7 @@ describe("metadata", () => {
   const metadataKeyAuthKeypair = new Keypair();
   const metadataRootCollectionAuthKeypair = new Keypair();
   const metadataCollectionUpdateAuthKeypair = new Keypair();
+  const metadataDelegateKeypair = new Keypair();
 
   let metadataMetadataKey: PublicKey;
   let metadataCollectionMetadataKey: PublicKey;
@@ -101,6 +202,11 @@ describe("metadata", () => {
       metadataCollectionUpdateAuthKeypair.publicKey,
       2 * LAMPORTS_PER_SOL
     );
+    await airdrop(
+      connection,
+      metadataDelegateKeypair.publicKey,
+      2 * LAMPORTS_PER_SOL
+    );
   });
 
   describe("Metadata", () => {
@@ -128,16 +234,26 @@ describe("metadata", () => {
             contentType,
             description,
             label,
+            multiValued: false,
             id: new anchor.BN(metadataRootCollectionMetadataId),
           })
           .accountsStrict({
             metadataKey: metadataMetadataKey,
             namespaceAuthority: metadataKeyAuthKeypair.publicKey,
+            namespaceRegistry: getNamespaceRegistry(
+              metadataKeyAuthKeypair.publicKey,
+              programId
+            )[0],
+            namespaceRegistryPage: getNamespaceRegistryPage(
+              metadataKeyAuthKeypair.publicKey,
+              0,
+              programId
+            )[0],
             payer: wallet.publicKey,
             systemProgram: SystemProgram.programId,
           })
           .preInstructions([
-            ComputeBudgetProgram.setComputeUnitLimit({ units: 25000 }),
+            ComputeBudgetProgram.setComputeUnitLimit({ units: 80000 }),
           ])
           .signers([metadataKeyAuthKeypair])
           .rpc(confirmOptions);
@@ -158,6 +274,22 @@ describe("metadata", () => {
         );
       });
 
+      it("should be listed in the namespace registry", async () => {
+        const page =
+          await mythicMetadataProgram.account.namespaceRegistryPage.fetch(
+            getNamespaceRegistryPage(
+              metadataKeyAuthKeypair.publicKey,
+              0,
+              programId
+            )[0]
+          );
+        expect(page.entries[0].id.toNumber()).to.eql(
+          metadataRootCollectionMetadataId
+        );
+        expect(page.entries[0].name).to.eql(name);
+        expect(page.entries[0].contentType).to.eql(contentType);
+      });
+
       it("should have right name", () => {
         expect(metadataMetadataKeyData.name).to.eql(name);
       });
@@ -204,7 +336,15 @@ describe("metadata", () => {
             metadata: metadataKey,
             metadataMetadataKey: metadataMetadataKey,
             payer: wallet.publicKey,
+            subjectRegistry: getSubjectRegistry(demoSubject, programId)[0],
+            subjectRegistryPage: getSubjectRegistryPage(
+              demoSubject,
+              0,
+              programId
+            )[0],
             systemProgram: SystemProgram.programId,
+            subjectAuthority: null,
+            subjectAccount: null,
           })
           .signers([metadataRootCollectionAuthKeypair])
           .rpc(confirmOptions);
@@ -233,6 +373,80 @@ describe("metadata", () => {
       it("should have right subject", () => {
         expect(metadataData.subject.toString()).to.eql(demoSubject.toString());
       });
+
+      it("should be listed in the subject registry", async () => {
+        const subjectRegistry =
+          await mythicMetadataProgram.account.subjectRegistry.fetch(
+            getSubjectRegistry(demoSubject, programId)[0]
+          );
+        expect(subjectRegistry.subject.toString()).to.eql(
+          demoSubject.toString()
+        );
+        expect(subjectRegistry.registeredCount.toNumber()).to.eql(1);
+        expect(metadataData.registryPageIndex).to.eql(0);
+
+        const subjectRegistryPage =
+          await mythicMetadataProgram.account.subjectRegistryPage.fetch(
+            getSubjectRegistryPage(demoSubject, 0, programId)[0]
+          );
+        expect(
+          subjectRegistryPage.entries.map((entry) =>
+            entry.issuingAuthority.toString()
+          )
+        ).to.include(metadataRootCollectionAuthKeypair.publicKey.toString());
+      });
+
+      it("should not have verified subject", () => {
+        expect(metadataData.verifiedSubject).to.eql(false);
+      });
+    });
+
+    describe("after creating self-issued metadata", () => {
+      const subjectKeypair = new Keypair();
+
+      let metadataData;
+      before(async () => {
+        const [selfIssuedMetadataKey] = getMetadata(
+          metadataMetadataKey,
+          metadataRootCollectionAuthKeypair.publicKey,
+          subjectKeypair.publicKey,
+          programId
+        );
+
+        await mythicMetadataProgram.methods
+          .createMetadata({
+            subject: subjectKeypair.publicKey,
+            updateAuthority: metadataRootCollectionAuthKeypair.publicKey,
+          })
+          .accountsStrict({
+            issuingAuthority: metadataRootCollectionAuthKeypair.publicKey,
+            metadata: selfIssuedMetadataKey,
+            metadataMetadataKey: metadataMetadataKey,
+            payer: wallet.publicKey,
+            subjectRegistry: getSubjectRegistry(
+              subjectKeypair.publicKey,
+              programId
+            )[0],
+            subjectRegistryPage: getSubjectRegistryPage(
+              subjectKeypair.publicKey,
+              0,
+              programId
+            )[0],
+            systemProgram: SystemProgram.programId,
+            subjectAuthority: subjectKeypair.publicKey,
+            subjectAccount: null,
+          })
+          .signers([metadataRootCollectionAuthKeypair, subjectKeypair])
+          .rpc(confirmOptions);
+
+        metadataData = await mythicMetadataProgram.account.metadata.fetch(
+          selfIssuedMetadataKey
+        );
+      });
+
+      it("should have verified subject", () => {
+        expect(metadataData.verifiedSubject).to.eql(true);
+      });
     });
 
     describe("after creating metadata collection key", () => {
@@ -259,16 +473,26 @@ describe("metadata", () => {
             contentType,
             description,
             label,
+            multiValued: false,
             id: new anchor.BN(metadataCollectionMetadataKeyId),
           })
           .accountsStrict({
             metadataKey: metadataCollectionMetadataKey,
             namespaceAuthority: metadataKeyAuthKeypair.publicKey,
+            namespaceRegistry: getNamespaceRegistry(
+              metadataKeyAuthKeypair.publicKey,
+              programId
+            )[0],
+            namespaceRegistryPage: getNamespaceRegistryPage(
+              metadataKeyAuthKeypair.publicKey,
+              0,
+              programId
+            )[0],
             payer: wallet.publicKey,
             systemProgram: SystemProgram.programId,
           })
           .preInstructions([
-            ComputeBudgetProgram.setComputeUnitLimit({ units: 25000 }),
+            ComputeBudgetProgram.setComputeUnitLimit({ units: 80000 }),
           ])
           .signers([metadataKeyAuthKeypair])
           .rpc(confirmOptions);
@@ -323,13 +547,15 @@ describe("metadata", () => {
         await mythicMetadataProgram.methods
           .appendMetadataCollection({
             updateAuthority: null,
+            parentPath: [],
           })
           .accountsStrict({
             collectionMetadataKey: metadataCollectionMetadataKey,
             metadata: metadataKey,
             metadataMetadataKey: metadataMetadataKey,
             payer: metadataRootCollectionAuthKeypair.publicKey,
-            issuingAuthority: metadataRootCollectionAuthKeypair.publicKey,
+            authority: metadataRootCollectionAuthKeypair.publicKey,
+            delegateRecord: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([metadataRootCollectionAuthKeypair])
@@ -362,12 +588,15 @@ describe("metadata", () => {
         await mythicMetadataProgram.methods
           .setCollectionUpdateAuthority({
             newUpdateAuthority: metadataCollectionUpdateAuthKeypair.publicKey,
+            unsafeSkipAcceptance: true,
+            parentPath: [],
           })
           .accountsStrict({
             collectionMetadataKey: metadataCollectionMetadataKey,
             metadata: metadataKey,
             metadataMetadataKey: metadataMetadataKey,
-            issuingAuthority: metadataRootCollectionAuthKeypair.publicKey,
+            authority: metadataRootCollectionAuthKeypair.publicKey,
+            delegateRecord: null,
           })
           .signers([metadataRootCollectionAuthKeypair])
           .rpc(confirmOptions);
@@ -385,6 +614,71 @@ describe("metadata", () => {
       });
     });
 
+    describe("after proposing and cancelling an update authority transfer", () => {
+      let authorityTransfer: PublicKey;
+      let authorityTransferData;
+      let metadataData;
+      before(async () => {
+        authorityTransfer = getAuthorityTransfer(
+          metadataKey,
+          [metadataCollectionMetadataKeyId],
+          programId
+        )[0];
+
+        await mythicMetadataProgram.methods
+          .proposeUpdateAuthorityTransfer({
+            newUpdateAuthority: new Keypair().publicKey,
+            parentPath: [],
+          })
+          .accountsStrict({
+            payer: wallet.publicKey,
+            authority: metadataRootCollectionAuthKeypair.publicKey,
+            metadata: metadataKey,
+            metadataMetadataKey: metadataMetadataKey,
+            collectionMetadataKey: metadataCollectionMetadataKey,
+            authorityTransfer,
+            delegateRecord: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([metadataRootCollectionAuthKeypair])
+          .rpc(confirmOptions);
+
+        authorityTransferData =
+          await mythicMetadataProgram.account.authorityTransfer.fetch(
+            authorityTransfer
+          );
+
+        await mythicMetadataProgram.methods
+          .cancelUpdateAuthorityTransfer()
+          .accountsStrict({
+            authority: metadataRootCollectionAuthKeypair.publicKey,
+            metadata: metadataKey,
+            authorityTransfer,
+            payer: wallet.publicKey,
+            delegateRecord: null,
+          })
+          .signers([metadataRootCollectionAuthKeypair])
+          .rpc(confirmOptions);
+
+        metadataData = await mythicMetadataProgram.account.metadata.fetch(
+          metadataKey
+        );
+      });
+
+      it("should have recorded the proposed transfer", () => {
+        expect(authorityTransferData.collectionPath.map(Number)).to.eql([
+          metadataCollectionMetadataKeyId,
+        ]);
+      });
+
+      it("should have closed the transfer without changing the authority", async () => {
+        expect(await connection.getAccountInfo(authorityTransfer)).to.be.null;
+        expect(
+          metadataData.collections[0].updateAuthority.toString()
+        ).to.eql(metadataCollectionUpdateAuthKeypair.publicKey.toString());
+      });
+    });
+
     describe("after creating collection metadata item key", () => {
       const { name, contentType, description, label } = {
         contentType: "string",
@@ -409,16 +703,26 @@ describe("metadata", () => {
             contentType,
             description,
             label,
+            multiValued: false,
             id: new anchor.BN(metadataItemMetadataKeyId),
           })
           .accountsStrict({
             metadataKey: metadataItemMetadataKey,
             namespaceAuthority: metadataKeyAuthKeypair.publicKey,
+            namespaceRegistry: getNamespaceRegistry(
+              metadataKeyAuthKeypair.publicKey,
+              programId
+            )[0],
+            namespaceRegistryPage: getNamespaceRegistryPage(
+              metadataKeyAuthKeypair.publicKey,
+              0,
+              programId
+            )[0],
             payer: wallet.publicKey,
             systemProgram: SystemProgram.programId,
           })
           .preInstructions([
-            ComputeBudgetProgram.setComputeUnitLimit({ units: 25000 }),
+            ComputeBudgetProgram.setComputeUnitLimit({ units: 80000 }),
           ])
           .signers([metadataKeyAuthKeypair])
           .rpc(confirmOptions);
@@ -473,13 +777,15 @@ describe("metadata", () => {
         await mythicMetadataProgram.methods
           .appendMetadataItem({
             value: Buffer.from(favoriteColor),
+            parentPath: [],
           })
           .accountsStrict({
             collectionMetadataKey: metadataCollectionMetadataKey,
             itemMetadataKey: metadataItemMetadataKey,
             metadata: metadataKey,
             metadataMetadataKey: metadataMetadataKey,
-            issuingAuthority: metadataRootCollectionAuthKeypair.publicKey,
+            authority: metadataRootCollectionAuthKeypair.publicKey,
+            delegateRecord: null,
             systemProgram: SystemProgram.programId,
             payer: wallet.publicKey
           })
@@ -534,6 +840,7 @@ describe("metadata", () => {
           description: "Favorite Color for DAO",
           label: "Deans List Favorite Color",
           name: "favorite-color",
+          multiValued: false,
           id: new anchor.BN(multipleMetadataItemMetadataKeyId1)
         };
         
@@ -542,6 +849,7 @@ describe("metadata", () => {
           description: "Favorite Car for DAO",
           label: "Deans List Favorite Car",
           name: "favorite-car",
+          multiValued: false,
           id: new anchor.BN(multipleMetadataItemMetadataKeyId2)
         };
         
@@ -551,21 +859,32 @@ describe("metadata", () => {
           .accountsStrict({
             metadataKey: metadataItemMetadataKey1,
             namespaceAuthority: metadataKeyAuthKeypair.publicKey,
+            namespaceRegistry: getNamespaceRegistry(
+              metadataKeyAuthKeypair.publicKey,
+              programId
+            )[0],
+            namespaceRegistryPage: getNamespaceRegistryPage(
+              metadataKeyAuthKeypair.publicKey,
+              0,
+              programId
+            )[0],
             payer: wallet.publicKey,
             systemProgram: SystemProgram.programId,
           })
           .preInstructions([
-            ComputeBudgetProgram.setComputeUnitLimit({ units: 25000 }),
+            ComputeBudgetProgram.setComputeUnitLimit({ units: 80000 }),
           ])
           .signers([metadataKeyAuthKeypair])
           .rpc(confirmOptions);
 
-        // Create Second Key
+        // Create Second Key, without listing it in the namespace registry
         await mythicMetadataProgram.methods
           .createMetadataKey(args2)
           .accountsStrict({
             metadataKey: metadataItemMetadataKey2,
             namespaceAuthority: metadataKeyAuthKeypair.publicKey,
+            namespaceRegistry: null,
+            namespaceRegistryPage: null,
             payer: wallet.publicKey,
             systemProgram: SystemProgram.programId,
           })
@@ -579,12 +898,14 @@ describe("metadata", () => {
           await mythicMetadataProgram.methods
           .appendMetadataItems({
             value: [Buffer.from(favoriteColor), Buffer.from(favoriteCar)],
+            parentPath: [],
           })
           .accountsStrict({
             collectionMetadataKey: metadataMetadataKey,
             metadata: metadataKey,
             metadataMetadataKey: metadataMetadataKey,
-            issuingAuthority: metadataRootCollectionAuthKeypair.publicKey,
+            authority: metadataRootCollectionAuthKeypair.publicKey,
+            delegateRecord: null,
             systemProgram: SystemProgram.programId,
             payer: wallet.publicKey
           })
@@ -620,6 +941,7 @@ describe("metadata", () => {
         await mythicMetadataProgram.methods
           .updateMetadataItem({
             newValue: Buffer.from(newFavoriteColor),
+            parentPath: [],
           })
           .accountsStrict({
             collectionMetadataKey: metadataCollectionMetadataKey,
@@ -627,6 +949,8 @@ describe("metadata", () => {
             metadata: metadataKey,
             metadataMetadataKey: metadataMetadataKey,
             updateAuthority: metadataCollectionUpdateAuthKeypair.publicKey,
+            delegateRecord: null,
+            multisigAuthority: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([metadataCollectionUpdateAuthKeypair])
@@ -644,18 +968,98 @@ describe("metadata", () => {
       });
     });
 
+    describe("after updating metadata item with a delegate", () => {
+      const delegatedFavoriteColor = "green";
+      const updatePermission = 1 << 1;
+
+      let delegateRecord: PublicKey;
+      let delegateRecordData;
+      let item;
+
+      before(async () => {
+        delegateRecord = getMetadataDelegate(
+          metadataKey,
+          [metadataCollectionMetadataKeyId],
+          metadataDelegateKeypair.publicKey,
+          programId
+        )[0];
+
+        await mythicMetadataProgram.methods
+          .createMetadataDelegate({
+            delegate: metadataDelegateKeypair.publicKey,
+            permissions: updatePermission,
+            parentPath: [],
+          })
+          .accountsStrict({
+            collectionMetadataKey: metadataCollectionMetadataKey,
+            delegateRecord,
+            issuingAuthority: metadataRootCollectionAuthKeypair.publicKey,
+            metadata: metadataKey,
+            metadataMetadataKey: metadataMetadataKey,
+            payer: wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([metadataRootCollectionAuthKeypair])
+          .rpc(confirmOptions);
+
+        delegateRecordData =
+          await mythicMetadataProgram.account.metadataDelegate.fetch(
+            delegateRecord
+          );
+
+        await mythicMetadataProgram.methods
+          .updateMetadataItem({
+            newValue: Buffer.from(delegatedFavoriteColor),
+            parentPath: [],
+          })
+          .accountsStrict({
+            collectionMetadataKey: metadataCollectionMetadataKey,
+            itemMetadataKey: metadataItemMetadataKey,
+            metadata: metadataKey,
+            metadataMetadataKey: metadataMetadataKey,
+            updateAuthority: metadataDelegateKeypair.publicKey,
+            delegateRecord,
+            multisigAuthority: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([metadataDelegateKeypair])
+          .rpc(confirmOptions);
+
+        const metadataData = await mythicMetadataProgram.account.metadata.fetch(
+          metadataKey
+        );
+        item = metadataData.collections[0].items[0];
+      });
+
+      it("should have right permissions", () => {
+        expect(delegateRecordData.permissions).to.eql(updatePermission);
+      });
+
+      it("should have right value", () => {
+        expect(item.value.toString()).to.eql(delegatedFavoriteColor);
+      });
+    });
+
     describe("after removing item from collection", () => {
       let metadataData;
       before(async () => {
         await mythicMetadataProgram.methods
-          .removeMetadataItem()
+          .removeMetadataItem({ parentPath: [] })
           .accountsStrict({
             collectionMetadataKey: metadataCollectionMetadataKey,
             metadata: metadataKey,
             itemMetadataKey: metadataItemMetadataKey,
             metadataMetadataKey: metadataMetadataKey,
-            issuingAuthority: metadataRootCollectionAuthKeypair.publicKey,
+            authority: metadataRootCollectionAuthKeypair.publicKey,
+            delegateRecord: null,
           })
+          .remainingAccounts([
+            {
+              pubkey: wallet.publicKey,
+              isSigner: false,
+              isWritable: true,
+            },
+          ])
           .signers([metadataRootCollectionAuthKeypair])
           .rpc(confirmOptions);
 
@@ -674,12 +1078,13 @@ describe("metadata", () => {
       let metadataData;
       before(async () => {
         await mythicMetadataProgram.methods
-          .revokeCollectionUpdateAuthority()
+          .revokeCollectionUpdateAuthority({ parentPath: [] })
           .accountsStrict({
             collectionMetadataKey: metadataCollectionMetadataKey,
             metadata: metadataKey,
             metadataMetadataKey: metadataMetadataKey,
-            issuingAuthority: metadataRootCollectionAuthKeypair.publicKey,
+            authority: metadataRootCollectionAuthKeypair.publicKey,
+            delegateRecord: null,
           })
           .signers([metadataRootCollectionAuthKeypair])
           .rpc(confirmOptions);
@@ -699,13 +1104,21 @@ describe("metadata", () => {
       let metadataData;
       before(async () => {
         await mythicMetadataProgram.methods
-          .removeMetadataCollection()
+          .removeMetadataCollection({ parentPath: [] })
           .accountsStrict({
             collectionMetadataKey: metadataCollectionMetadataKey,
             metadata: metadataKey,
             metadataMetadataKey: metadataMetadataKey,
-            issuingAuthority: metadataRootCollectionAuthKeypair.publicKey,
+            authority: metadataRootCollectionAuthKeypair.publicKey,
+            delegateRecord: null,
           })
+          .remainingAccounts([
+            {
+              pubkey: metadataRootCollectionAuthKeypair.publicKey,
+              isSigner: false,
+              isWritable: true,
+            },
+          ])
           .signers([metadataRootCollectionAuthKeypair])
           .rpc(confirmOptions);
 
@@ -718,5 +1131,39 @@ describe("metadata", () => {
         expect(metadataData.collections.length).to.eql(0);
       });
     });
+
+    describe("after finalizing metadata", () => {
+      let metadataData;
+      before(async () => {
+        const currentMetadataData =
+          await mythicMetadataProgram.account.metadata.fetch(metadataKey);
+        const encoded = await mythicMetadataProgram.coder.accounts.encode(
+          "metadata",
+          currentMetadataData
+        );
+        const stateHash = createHash("sha256")
+          .update(encoded.subarray(8))
+          .digest();
+
+        await mythicMetadataProgram.methods
+          .finalizeMetadata({ stateHash: [...stateHash] })
+          .accountsStrict({
+            authority: metadataRootCollectionAuthKeypair.publicKey,
+            metadata: metadataKey,
+            metadataMetadataKey: metadataMetadataKey,
+            multisigAuthority: null,
+          })
+          .signers([metadataRootCollectionAuthKeypair])
+          .rpc(confirmOptions);
+
+        metadataData = await mythicMetadataProgram.account.metadata.fetch(
+          metadataKey
+        );
+      });
+
+      it("should have recorded the finalization slot", () => {
+        expect(metadataData.finalizedSlot).to.not.be.null;
+      });
+    });
   });
 });